@@ -1,59 +1,1791 @@
-"""Type stubs for the Rust extension."""
+"""Type stubs for the Rust extension.
 
-from typing import List, Tuple
+Generated by scripts/generate_stubs.py from the Rust sources; do not edit.
+"""
+
+from typing import Any, Dict, List, Optional, Sequence, Tuple
+
+class PatternMatcher:
+    """High-performance pattern matcher using Rust regex."""
+
+    @property
+    def watchdog_ms(self) -> Optional[float]:
+        """Watchdog threshold in milliseconds (None if disabled)"""
+        ...
+
+    @property
+    def two_pass(self) -> bool:
+        """Whether this matcher runs the two-pass scan."""
+        ...
+
+    @property
+    def taxonomy(self) -> Taxonomy:
+        """Taxonomy classifying this matcher's findings."""
+        ...
+
+    @property
+    def pattern_count(self) -> int:
+        """Get number of successfully compiled patterns."""
+        ...
+
+    @property
+    def failed_count(self) -> int:
+        """Get number of patterns that failed to compile."""
+        ...
+
+    @property
+    def feedback_size(self) -> int:
+        """Number of distinct values with analyst feedback."""
+        ...
+
+    def __init__(
+        self,
+        patterns: List[Tuple[str, str, float, int]],
+        two_pass: bool = False,
+        postprocess: Optional[Dict[str, List[str]]] = None,
+        watchdog_ms: Optional[float] = None,
+        regions: Optional[Dict[str, List[str]]] = None,
+        suppress_hexdump: bool = False,
+        chunk_threshold: Optional[int] = 67108864,
+        chunk_overlap: int = 4096,
+        normalize: Optional[List[str]] = None,
+        repair: Optional[Dict[str, List[str]]] = None,
+        max_text_bytes: Optional[int] = None,
+        builtin_allowlist: bool = True,
+        taxonomy: Optional[Taxonomy] = None,
+    ) -> None:
+        """Create a new matcher, compiling the given patterns
+
+        Args:
+            patterns: List of (regex_str, entity_type, confidence, group_idx) tuples;
+                pure literal alternations of 100+ terms are compiled to an
+                Aho-Corasick automaton instead of a regex
+            two_pass: Verify patterns only at candidate positions found by a
+                cheap literal pre-pass (faster on mostly-clean documents)
+            postprocess: Optional {entity_type: [step, ...]} rules applied to
+                Finding.normalized; steps are "upper", "lower", "strip_spaces",
+                "strip_separators", "digits_only" and "truncate:N"
+            watchdog_ms: Time each pattern's pass and record passes slower
+                than this many milliseconds (see slow_patterns())
+            regions: Optional {entity_type: [kind, ...]} restricting patterns
+                of that type to document regions of those kinds ("prose",
+                "table", "code", "base64", "binary", "hexdump"; see classify_regions())
+            suppress_hexdump: Drop numeric matches (digits and hex digits
+                only, plus separators) inside hex dump regions such as xxd or
+                hexdump -C output and packet captures
+            chunk_threshold: Texts longer than this many bytes are scanned as
+                overlapping chunks of this size, bounding per-scan memory;
+                results are the same as a whole scan for matches shorter than
+                chunk_overlap. None scans every text whole. With normalize
+                stages the normalized copy of the whole text is built first
+                and chunked, so that copy is not bounded by the threshold
+            chunk_overlap: Bytes adjacent chunks share; at most half of
+                chunk_threshold
+            normalize: Optional list of normalization stages run in order
+                before matching: "unicode_fold", "collapse_whitespace",
+                "decode_entities", "decode_percent", "ocr_fold". Offsets
+                still refer to the input; RawMatch.text is the normalized form
+            repair: Optional {entity_type: [step, ...]} rules stripping
+                decorations from a match before validation; steps are
+                "labels" ("SSN#", "Tel.", "No.", "№"), "brackets" and
+                "trailing_punct". The validated value is Finding.repaired
+            max_text_bytes: Copy at most this many bytes of each match into
+                its text (cut at a character boundary) and set truncated;
+                start and end still span the whole match. None copies every
+                match whole
+            builtin_allowlist: Drop findings whose value is a well-known
+                non-sensitive one: processor test card numbers, SSNs from
+                documentation and advertising, reserved example domains
+                (and emails at them) and documentation IP ranges
+            taxonomy: Taxonomy giving Finding.identifier_class; None uses
+                the built-in classes
+
+        Returns:
+            PatternMatcher instance
+
+        Raises:
+            ValueError: For invalid postprocess, watchdog, regions, chunking,
+                normalize, repair or max_text_bytes arguments
+        """
+        ...
+
+    def memory_usage(self) -> Dict[str, int]:
+        """Approximate memory held by this matcher's compiled patterns
+
+        Dictionaries are not part of a PatternMatcher; add each
+        DictionaryMatcher.memory_usage() to size a worker's full footprint.
+
+        Returns:
+            Dict of byte counts: regex_bytes (per-pattern regexes),
+            regex_set_bytes, literal_bytes (literal alternation automata),
+            prefilter_bytes, two_pass_bytes, metadata_bytes and total_bytes
+        """
+        ...
+
+    def export_config(self, format: str = "json") -> str:
+        """Canonical serialization of the effective configuration
+
+        Lists every compiled pattern (source, entity type, confidence,
+        group, execution engine and region restriction), the input indices
+        of patterns that failed to compile, post-processing and repair rules,
+        the built-in allowlist switch, taxonomy overrides, two-pass mode and
+        the watchdog threshold. Keys are sorted and numbers use a fixed format, so
+        identical configurations export identical text.
+
+        Args:
+            format: "json" or "toml"
+
+        Raises:
+            ValueError: For an unknown format
+        """
+        ...
+
+    def stats(self) -> Dict[str, int]:
+        """Cumulative scan counters since creation (or the last reset_stats())
+
+        Returns:
+            Dict with scans (texts or byte-scan runs scanned), bytes_scanned,
+            matches and slow_events (watchdog threshold breaches)
+        """
+        ...
+
+    def slow_patterns(self) -> List[SlowPattern]:
+        """Pattern passes that exceeded the watchdog threshold, oldest first
+
+        Keeps the most recent 256; empty if the watchdog is off.
+        """
+        ...
+
+    def reset_stats(self) -> None:
+        """Zero the counters and clear recorded slow passes."""
+        ...
+
+    def warmup(self, sample: Optional[str] = None) -> float:
+        """Prime lazy DFA states and per-thread caches before real traffic
+
+        Scans synthetic text (plus the optional sample) with every pattern
+        on the calling thread and on every thread of the pool used by
+        find_matches_batch, with the GIL released. Nothing is returned and
+        stats() is unchanged. Safe to call more than once.
+
+        Args:
+            sample: Representative production text to warm on as well
+
+        Returns:
+            Elapsed wall time in milliseconds
+        """
+        ...
+
+    def find_matches(self, text: str, context: Any = None) -> List[RawMatch]:
+        """Find all pattern matches in text
+
+        This method releases the GIL during execution, allowing other
+        Python threads to run concurrently.
+
+        Args:
+            text: The text to scan
+            context: Optional object (document id, tenant, source path...)
+                attached by reference to every returned match
+
+        Returns:
+            List of RawMatch objects
+        """
+        ...
+
+    def scan_paged(self, text: str, page_size: int = 10000, context: Any = None) -> ScanCursor:
+        """Scan text and keep the matches natively, to be pulled in pages
+
+        Use instead of find_matches() when a scan may produce more matches
+        than fit in memory as Python objects. Releases the GIL while scanning.
+
+        Args:
+            text: The text to scan
+            page_size: Default number of matches per page
+            context: Optional object attached to every returned match
+
+        Returns:
+            ScanCursor yielding lists of RawMatch objects
+        """
+        ...
+
+    def detect(
+        self,
+        text: str,
+        context: Any = None,
+        min_score: float = 0.0,
+        ocr_confidence: Any = None,
+    ) -> List[Finding]:
+        """Detect entities: match, normalize, validate and score in one call
+
+        Higher-level counterpart of find_matches(). Each Finding carries the
+        normalized value, per-validator verdicts and a score. Releases the
+        GIL while scanning.
+
+        Args:
+            text: The text to scan
+            context: Optional metadata object attached to every finding
+            min_score: Drop findings scoring below this value
+            ocr_confidence: OCR confidence (0.0-1.0) for the text, either one
+                float per character or a list of (start, end, confidence)
+                byte spans; each score is multiplied by the lowest
+                confidence under the finding
+
+        Returns:
+            List of Finding objects
+
+        Raises:
+            ValueError: If ocr_confidence does not fit the text
+        """
+        ...
+
+    def detect_grouped(
+        self,
+        text: str,
+        context: Any = None,
+        min_score: float = 0.0,
+        ocr_confidence: Any = None,
+    ) -> List[GroupedFinding]:
+        """Detect entities grouped by value: one entry per distinct value
+
+        Same arguments as detect(). Findings with the same entity type and
+        normalized value (after post-processing) are collected into one
+        GroupedFinding listing every location, ordered by first occurrence.
+
+        Returns:
+            List of GroupedFinding objects
+
+        Raises:
+            ValueError: If ocr_confidence does not fit the text
+        """
+        ...
+
+    def detect_signed(
+        self,
+        text: str,
+        key: bytes,
+        min_score: float = 0.0,
+        scan_id: Optional[str] = None,
+        ocr_confidence: Any = None,
+    ) -> SignedResult:
+        """Detect entities and sign the results for a tamper-evident audit trail
+
+        Findings are serialized to canonical JSON (with SHA-256 digests of
+        the text and of export_config()) and signed with HMAC-SHA256 under
+        `key` before any Python object is created. Store the payload and
+        signature; verify_signed_result() checks them later.
+
+        Args:
+            text: Text to scan
+            key: Signing key (bytes)
+            min_score: Drop findings scoring below this
+            scan_id: Optional caller identifier recorded in the payload
+            ocr_confidence: As for detect()
+
+        Returns:
+            SignedResult with findings, payload and signature
+
+        Raises:
+            ValueError: If the key is empty or ocr_confidence does not fit the text
+        """
+        ...
+
+    def detect_summary(
+        self,
+        text: str,
+        max_examples: int = 3,
+        min_score: float = 0.0,
+        ocr_confidence: Any = None,
+    ) -> List[EntitySummary]:
+        """Detect entities and report only per-entity counts and a few examples
+
+        For summary reports that need how much of each entity type a text
+        holds rather than every finding. Examples are chosen by hashing the
+        normalized values, so the same text always gives the same examples.
+
+        Args:
+            text: Text to scan
+            max_examples: Most example values per entity type (0 for counts only)
+            min_score: Drop findings scoring below this
+            ocr_confidence: As for detect()
+
+        Returns:
+            List of EntitySummary objects ordered by entity type
+
+        Raises:
+            ValueError: If ocr_confidence does not fit the text
+        """
+        ...
+
+    def detect_documents(
+        self,
+        text: str,
+        min_confidence: float = 0.5,
+        min_score: float = 0.0,
+        ocr_confidence: Any = None,
+    ) -> List[IdentityDocument]:
+        """Recognize identity documents (W-2, I-9, driver's license) in a scanned form
+
+        A document is reported when enough field labels of its layout occur
+        together; a form title and validated values of the entity types it
+        carries raise the confidence. Each document lists the labels and
+        findings that contributed.
+
+        Args:
+            text: Text of the form, typically OCR output
+            min_confidence: Drop documents below this confidence
+            min_score: Drop findings scoring below this before they count
+            ocr_confidence: As for detect()
+
+        Returns:
+            List of IdentityDocument objects ordered by start offset
+
+        Raises:
+            ValueError: If ocr_confidence does not fit the text
+        """
+        ...
+
+    def evaluate(
+        self,
+        corpus: List[Tuple[str, List[Tuple[int, int, str]]]],
+        mode: str = "exact",
+        min_score: Optional[float] = None,
+    ) -> EvaluationReport:
+        """Precision, recall and F1 per entity type over a labeled corpus
+
+        Documents are scanned in parallel with the GIL released.
+
+        Args:
+            corpus: List of (text, expected) where expected is a list of
+                (start, end, entity_type) byte spans
+            mode: "exact" (identical spans) or "overlap" (spans share a byte)
+            min_score: If given, score detect() findings at or above this
+                score instead of raw matches
+
+        Returns:
+            EvaluationReport with per-entity and overall scores
+        """
+        ...
+
+    def load_feedback(self, verdicts: Dict[str, str]) -> int:
+        """Load analyst feedback used by detect() to demote false positives
+
+        Verdicts accumulate across calls; a value's score is multiplied by
+        1.0 with no false-positive votes, down to 0.1 when all votes are
+        false positives.
+
+        Args:
+            verdicts: Finding.feedback_hash -> "false_positive" / "fp" or
+                "true_positive" / "tp"
+
+        Returns:
+            Number of distinct values with feedback after loading
+
+        Raises:
+            ValueError: On a malformed hash or unknown verdict (nothing is loaded)
+        """
+        ...
+
+    def clear_feedback(self) -> None:
+        """Forget all loaded feedback."""
+        ...
+
+    def find_pairs(self, text: str, extractor: PairExtractor) -> List[LabeledPair]:
+        """Scan text and return values introduced by a label ("SSN: ...")
+
+        Runs find_matches() and pairs each value with the label keyword
+        directly before it. Releases the GIL while scanning.
+
+        Args:
+            text: The text to scan
+            extractor: PairExtractor holding the label keywords
+
+        Returns:
+            List of LabeledPair objects
+        """
+        ...
+
+    def find_matches_bytes(
+        self,
+        data: bytes,
+        mode: str = "lossy",
+        context: Any = None,
+    ) -> ByteScanResult:
+        """Find all pattern matches in a byte buffer that may not be valid UTF-8
+
+        Invalid sequences never abort the scan; their byte ranges are
+        reported alongside the matches. Releases the GIL while scanning.
+
+        Args:
+            data: Raw bytes to scan
+            mode: "lossy" (replace invalid sequences with U+FFFD and scan
+                across them) or "strict" (scan each valid run separately)
+            context: Optional object attached to every returned match
+
+        Returns:
+            ByteScanResult with byte-offset matches and invalid_spans
+        """
+        ...
+
+    def redact_inplace(self, buffer: Any, fill: bytes = b"*") -> List[Tuple[int, int]]:
+        """Mask every match directly in a writable buffer, keeping its length
+
+        Each matched byte is overwritten with `fill`; nothing is copied, so
+        offsets of everything outside the matches are unchanged. Valid UTF-8
+        runs are scanned where they lie, as in strict mode, so invalid bytes
+        are left alone and no match crosses them. The buffer is held through
+        the buffer protocol, so a bytearray cannot be resized during the
+        call, and the GIL is released while scanning; other threads must not
+        write to the buffer until the call returns.
+
+        Args:
+            buffer: Writable, contiguous bytes-like object (bytearray,
+                memoryview, mmap, ...) to redact in place
+            fill: Single replacement byte (default b"*")
+
+        Returns:
+            List of (start, end) byte ranges that were masked
+
+        Raises:
+            ValueError: If fill is not exactly one byte
+            TypeError: If buffer is read-only or not contiguous
+        """
+        ...
+
+    def has_pattern(self, index: int) -> bool:
+        """Check if a specific pattern index is available."""
+        ...
+
+    def find_matches_batch(
+        self,
+        texts: List[str],
+        contexts: Optional[List[Any]] = None,
+    ) -> List[List[RawMatch]]:
+        """Find matches in multiple texts in parallel (batch API)
+
+        Processes texts concurrently using Rayon's parallel iterator.
+        Significantly faster than calling find_matches() repeatedly.
+
+        Args:
+            texts: List of texts to scan
+            contexts: Optional list of context objects, one per text; each
+                text's matches carry its own context regardless of ordering
+
+        Returns:
+            List of lists of RawMatch objects (one per input text)
+        """
+        ...
+
+    def aggregate_counts(
+        self,
+        texts: List[str],
+        epsilon: Optional[float] = None,
+        max_per_document: Optional[int] = None,
+        seed: Optional[int] = None,
+    ) -> Dict[str, int]:
+        """Match counts per entity type over many texts, optionally noised
+
+        Every entity type of this matcher's patterns is reported, including
+        zero counts. Each text contributes at most max_per_document matches
+        to any one count. With epsilon, each count gets independent Laplace
+        noise of scale max_per_document / epsilon before leaving the native
+        layer, then is rounded and clamped at zero. Texts are scanned in
+        parallel with the GIL released.
+
+        Args:
+            texts: Texts to scan
+            epsilon: Privacy parameter (> 0); None returns exact counts
+            max_per_document: Per-text cap on each entity's contribution;
+                required with epsilon, optional otherwise
+            seed: Fixed noise seed for reproducible tests; never use for
+                shared telemetry
+
+        Returns:
+            Dict of entity_type -> count
+
+        Raises:
+            ValueError: If epsilon is not positive or max_per_document is
+                missing or zero with epsilon
+        """
+        ...
+
+    def might_contain_patterns(self, text: str) -> bool:
+        """Quick check if text likely contains any patterns (pre-filter)
+
+        Uses Aho-Corasick to quickly check for common pattern literals
+        before running full regex matching. Can skip texts that definitely
+        won't match any patterns.
+
+        Args:
+            text: The text to check
+
+        Returns:
+            True if text might contain patterns, False if definitely not
+        """
+        ...
 
 class RawMatch:
-    """A raw match from pattern matching (before validation)."""
+    """A raw match from pattern matching (before Python-side validation)"""
+
+    pattern_id: int
+    start: int
+    end: int
+    text: str
+    entity_type: str
+    confidence: float
+    truncated: bool
+
+    @property
+    def context(self) -> Any:
+        """Opaque context object passed to the scan call (None if not given)"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class ByteScanResult:
+    """Result of scanning a byte buffer."""
+
+    matches: List[RawMatch]
+    invalid_spans: List[Tuple[int, int]]
+    mode: str
+
+    @property
+    def is_valid_utf8(self) -> bool:
+        """True if the buffer was entirely valid UTF-8."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class ScanCursor:
+    """Natively held scan results, pulled in pages."""
+
+    page_size: int
+    total: int
+
+    @property
+    def remaining(self) -> int:
+        """Matches not yet retrieved."""
+        ...
+
+    @property
+    def position(self) -> int:
+        """Number of matches already retrieved."""
+        ...
+
+    def next_page(self, size: Optional[int] = None) -> List[RawMatch]:
+        """Next page of matches (empty once exhausted)
+
+        Args:
+            size: Matches to return (defaults to the cursor's page_size)
+        """
+        ...
+
+    def __iter__(self) -> "ScanCursor": ...
+
+    def __next__(self) -> List[RawMatch]:
+        """Iterate pages of page_size matches."""
+        ...
+
+    def __len__(self) -> int: ...
+
+    def __repr__(self) -> str: ...
+
+class SlowPattern:
+    """One pattern pass that exceeded the watchdog threshold."""
+
+    pattern_id: int
+    entity_type: str
+    elapsed_ms: float
+    input_len: int
+
+    def __repr__(self) -> str: ...
+
+class StreamScanner:
+    """Scanner for a stream of overlapping chunks of one object."""
+
+    overlap: int
+
+    @property
+    def tracked_matches(self) -> int:
+        """Number of matches remembered for deduplication."""
+        ...
+
+    @property
+    def seen_chunks(self) -> int:
+        """Number of distinct chunk ids seen."""
+        ...
+
+    def __init__(self, matcher: PatternMatcher, overlap: int = 256) -> None:
+        """Create a stream scanner using a matcher's patterns
+
+        Args:
+            matcher: PatternMatcher whose compiled patterns are shared
+            overlap: Bytes adjacent chunks share; must be at least the
+                longest match you expect
+        """
+        ...
+
+    def scan_chunk(
+        self,
+        text: str,
+        offset: int,
+        chunk_id: Optional[str] = None,
+        last: bool = False,
+    ) -> List[RawMatch]:
+        """Scan one chunk and return matches not already reported
+
+        Releases the GIL while scanning.
+
+        Args:
+            text: Chunk text
+            offset: Byte offset of the chunk within the whole object
+            chunk_id: Optional identity (e.g. part number); a chunk id seen
+                before returns no matches, so retried deliveries are safe
+            last: True for the final chunk (its end is not an overlap)
+
+        Returns:
+            List of RawMatch with offsets into the whole object
+        """
+        ...
+
+    def reset(self) -> None:
+        """Forget all state, ready for a new object."""
+        ...
+
+class TailScanner:
+    """Incremental scanner for any number of growing files
+
+    Not thread-safe across calls for the same file; give each tailing
+    thread its own scanner or serialize calls.
+    """
+
+    overlap: int
+
+    @property
+    def files(self) -> List[str]:
+        """Keys with state, sorted."""
+        ...
+
+    def __init__(self, matcher: PatternMatcher, overlap: int = 4096) -> None:
+        """Create a tail scanner using a matcher's patterns
+
+        Args:
+            matcher: PatternMatcher whose compiled patterns are shared
+            overlap: Bytes of already-scanned text rescanned with each
+                append; must be at least the longest match you expect
+
+        Raises:
+            ValueError: If overlap is 0
+        """
+        ...
+
+    def scan_file(self, path: str) -> List[RawMatch]:
+        """Scan the bytes appended to a file since the previous call
+
+        The first call for a path scans the whole file. Releases the GIL
+        while reading and scanning.
+
+        Args:
+            path: File to read; also the key its state is kept under
+
+        Returns:
+            List of RawMatch with byte offsets into the file
+
+        Raises:
+            OSError: If the file cannot be opened or read
+        """
+        ...
+
+    def feed(self, key: str, data: bytes) -> List[RawMatch]:
+        """Scan bytes the caller read from a stream
+
+        For sources the caller reads itself (sockets, journald, object
+        storage); `data` must directly follow the data fed before under
+        the same key. Releases the GIL while scanning.
+
+        Args:
+            key: Stream identity (e.g. a path)
+            data: Newly appended bytes
+
+        Returns:
+            List of RawMatch with byte offsets into the stream
+        """
+        ...
+
+    def flush(self, key: str) -> List[RawMatch]:
+        """Report matches held back because they touch the end of the data
+
+        Call when a file is closed or rotated away. Later appends are still
+        scanned; a held-back match that turns out to continue is reported
+        again with its longer span.
+
+        Returns:
+            List of RawMatch (empty for an unknown key)
+        """
+        ...
+
+    def offset(self, key: str) -> int:
+        """Bytes consumed so far for a key (0 if unknown)"""
+        ...
+
+    def forget(self, key: str) -> bool:
+        """Drop the state for a key; its next scan starts from the beginning."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class ScannerPool:
+    """Native scan worker pool."""
+
+    @property
+    def workers(self) -> int:
+        """Number of worker threads."""
+        ...
+
+    @property
+    def pending(self) -> int:
+        """Jobs submitted but not finished."""
+        ...
+
+    @property
+    def completed(self) -> int:
+        """Finished jobs (including failed ones) whose results have not been collected."""
+        ...
+
+    def __init__(self, matcher: PatternMatcher, workers: Optional[int] = None) -> None:
+        """Create a pool scanning with a matcher's patterns
+
+        Args:
+            matcher: PatternMatcher whose compiled patterns are shared
+            workers: Number of worker threads (default: one per CPU)
+
+        Raises:
+            ValueError: If workers is 0
+        """
+        ...
+
+    def submit(self, text: str, context: Any = None) -> int:
+        """Queue a document for scanning
+
+        Args:
+            text: The text to scan
+            context: Optional object attached to every match of this job
+
+        Returns:
+            Job id for wait()
+        """
+        ...
+
+    def wait(self, job_id: int, timeout: Optional[float] = None) -> Optional[List[RawMatch]]:
+        """Wait for one job and collect its matches
+
+        Releases the GIL while waiting.
+
+        Args:
+            job_id: Id returned by submit()
+            timeout: Seconds to wait (None: until done)
+
+        Returns:
+            List of RawMatch, or None if the timeout passed first
+
+        Raises:
+            ValueError: If the id is unknown or its results were already collected
+            RuntimeError: If the job panicked while scanning
+        """
+        ...
+
+    def poll(self, timeout: Optional[float] = None) -> List[Tuple[int, List[RawMatch]]]:
+        """Collect every finished job
+
+        Failed jobs are not returned; they stay until wait() raises their error.
+
+        Args:
+            timeout: If nothing has finished, wait up to this many seconds
+                for the first job (default: return immediately)
+
+        Returns:
+            List of (job_id, matches) in job id order
+        """
+        ...
+
+    def __repr__(self) -> str: ...
+
+class FixedWidthScanner:
+    """Pattern scanner for fixed-width records."""
+
+    record_length: Optional[int]
+
+    @property
+    def fields(self) -> List[str]:
+        """Column names in layout order."""
+        ...
+
+    def __init__(
+        self,
+        matcher: PatternMatcher,
+        layout: List[Tuple[str, int, int]],
+        record_length: Optional[int] = None,
+    ) -> None:
+        """Create a scanner for a column layout
+
+        Args:
+            matcher: PatternMatcher whose compiled patterns are shared
+            layout: List of (name, start, length), byte offsets within a record
+            record_length: Bytes per record when records are packed without
+                newlines; None for one record per line
+
+        Raises:
+            ValueError: If the layout is empty, has a zero-length or
+                duplicate column, or a column extends past record_length
+        """
+        ...
+
+    def scan(self, text: str) -> List[FieldMatch]:
+        """Scan every column of every record
+
+        Padding around each column value is ignored. Releases the GIL.
+
+        Returns:
+            List of FieldMatch with offsets into the whole text
+        """
+        ...
+
+    def __repr__(self) -> str: ...
+
+class LogScanner:
+    """Pattern scanner for syslog / CEF / LEEF log lines."""
+
+    @property
+    def format(self) -> str:
+        """Configured format name."""
+        ...
+
+    def __init__(self, matcher: PatternMatcher, format: str = "auto") -> None:
+        """Create a log scanner
+
+        Args:
+            matcher: PatternMatcher whose compiled patterns are shared
+            format: "auto" (detect per line), "syslog", "cef" or "leef"
+
+        Raises:
+            ValueError: If the format is unknown
+        """
+        ...
+
+    def scan(self, text: str) -> List[FieldMatch]:
+        """Scan every field of every line
+
+        Each FieldMatch has the line index as `record` and the field key
+        as `field`. Releases the GIL.
+        """
+        ...
+
+class Finding:
+    """A validated, scored detection."""
+
+    entity_type: str
+    start: int
+    end: int
+    text: str
+    truncated: bool
+    normalized: str
+    repaired: Optional[str]
+    verdicts: Dict[str, bool]
+    confidence: float
+    score: float
+    ocr_confidence: Optional[float]
+    pattern_id: int
+    context_start: int
+    context_end: int
+
+    @property
+    def valid(self) -> bool:
+        """True unless a validator rejected the value."""
+        ...
+
+    @property
+    def identifier_class(self) -> str:
+        """Identifier class under the matcher's taxonomy (the built-in one
+        unless the matcher was given another): "direct", "quasi",
+        "sensitive" or "other"
+        """
+        ...
+
+    @property
+    def feedback_hash(self) -> str:
+        """Key for analyst feedback on this value (see PatternMatcher.load_feedback)"""
+        ...
+
+    @property
+    def metadata(self) -> Any:
+        """Opaque metadata passed to detect() (None if not given)"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class GroupedFinding:
+    """All findings of one document with the same entity type and normalized value."""
+
+    entity_type: str
+    normalized: str
+    score: float
+    findings: List[Finding]
+
+    @property
+    def count(self) -> int:
+        """Number of occurrences."""
+        ...
+
+    @property
+    def locations(self) -> List[Tuple[int, int]]:
+        """(start, end) of every occurrence, in text order."""
+        ...
+
+    @property
+    def texts(self) -> List[str]:
+        """Distinct matched texts, in order of first appearance."""
+        ...
+
+    @property
+    def valid(self) -> bool:
+        """True unless a validator rejected every occurrence."""
+        ...
+
+    @property
+    def feedback_hash(self) -> str:
+        """Key for analyst feedback on this value (see PatternMatcher.load_feedback)"""
+        ...
+
+    def __len__(self) -> int: ...
+
+    def __repr__(self) -> str: ...
+
+class EntitySummary:
+    """Counts and example values for one entity type."""
+
+    entity_type: str
+    count: int
+    distinct: int
+    examples: List[str]
+    max_score: float
+
+    def __repr__(self) -> str: ...
+
+class SignedResult:
+    """Findings with their signed canonical serialization."""
+
+    findings: List[Finding]
+    payload: str
+    signature: str
+
+    @property
+    def algorithm(self) -> str:
+        """Always "hmac-sha256"."""
+        ...
+
+    def verify(self, key: bytes) -> bool:
+        """Check the signature against the payload under `key`."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class IdentityDocument:
+    """A recognized identity document and the evidence for it."""
+
+    doc_type: str
+    confidence: float
+    start: int
+    end: int
+    labels: List[DocumentLabel]
+    findings: List[Finding]
+
+    @property
+    def entity_type(self) -> str:
+        """Entity type for policy rules keyed on findings: "IDENTITY_DOCUMENT"."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class DocumentLabel:
+    """A field label found in the input."""
+
+    text: str
+    start: int
+    end: int
+    title: bool
+
+    def __repr__(self) -> str: ...
+
+class EvaluationReport:
+    """Corpus-level evaluation result."""
+
+    documents: int
+    entities: Dict[str, EntityScore]
+    overall: EntityScore
+
+    def __repr__(self) -> str: ...
 
-    pattern_id: int
+class EntityScore:
+    """Counts and scores for one entity type."""
+
+    true_positives: int
+    false_positives: int
+    false_negatives: int
+
+    @property
+    def precision(self) -> float:
+        """TP / (TP + FP); 0.0 with no predictions."""
+        ...
+
+    @property
+    def recall(self) -> float:
+        """TP / (TP + FN); 0.0 with no expected spans."""
+        ...
+
+    @property
+    def f1(self) -> float:
+        """Harmonic mean of precision and recall."""
+        ...
+
+    @property
+    def support(self) -> int:
+        """Number of expected spans (TP + FN)"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class Taxonomy:
+    """Entity classification with per-type overrides."""
+
+    def __init__(self, overrides: Optional[Dict[str, str]] = None) -> None:
+        """Create a taxonomy, optionally overriding built-in classes
+
+        Args:
+            overrides: Entity type -> "direct", "quasi", "sensitive" or "other"
+
+        Raises:
+            ValueError: If an override names an unknown class
+        """
+        ...
+
+    def classify(self, entity_type: str) -> str:
+        """Class of an entity type: "direct", "quasi", "sensitive" or "other"."""
+        ...
+
+    def document_risk(self, findings: Sequence[Any]) -> DocumentRisk:
+        """Re-identification risk of a document
+
+        Args:
+            findings: Entity type strings or objects with an entity_type
+                attribute (RawMatch, Finding, FieldMatch, ...)
+
+        Any direct identifier makes subjects identifiable; otherwise each
+        distinct quasi-identifier type halves the remaining anonymity.
+        Sensitive attributes raise the score of identifiable documents.
+        """
+        ...
+
+class DocumentRisk:
+    """Re-identification risk of one document."""
+
+    score: float
+    tier: str
+    identifiability: float
+    direct: List[str]
+    quasi: List[str]
+    sensitive: List[str]
+
+    def __repr__(self) -> str: ...
+
+class DictionaryMatcher:
+    """Term-list matcher backed by an in-memory or memory-mapped automaton."""
+
+    @property
+    def term_count(self) -> int:
+        """Number of distinct terms."""
+        ...
+
+    @property
+    def entity_type(self) -> str:
+        """Entity type reported for matches."""
+        ...
+
+    @property
+    def folding(self) -> str:
+        """Case folding of the table: "none", "unicode" or "turkic"."""
+        ...
+
+    @property
+    def is_mapped(self) -> bool:
+        """Whether the table is memory-mapped from disk."""
+        ...
+
+    def __init__(
+        self,
+        terms: List[str],
+        entity_type: str = "DICTIONARY",
+        case_insensitive: bool = True,
+        confidence: float = 0.85,
+        locale: Optional[str] = None,
+    ) -> None:
+        """Build an in-memory dictionary matcher
+
+        Args:
+            terms: Terms to match (multi-word terms match across any whitespace)
+            entity_type: Entity type reported for matches
+            case_insensitive: Match ignoring case (full Unicode case folding)
+            confidence: Confidence reported for matches
+            locale: "tr" or "az" for Turkic folding (I <-> ı, İ <-> i)
+
+        Raises:
+            ValueError: If the locale has no special folding rules
+        """
+        ...
+
+    @staticmethod
+    def build(
+        terms: List[str],
+        path: str,
+        entity_type: str = "DICTIONARY",
+        case_insensitive: bool = True,
+        locale: Optional[str] = None,
+    ) -> None:
+        """Write a dictionary file for later use with open()
+
+        An existing file is replaced atomically (written beside it, then
+        renamed over it), so processes that have it open keep reading the
+        old version. Replace dictionary files only this way, never by
+        rewriting them in place.
+
+        Args:
+            terms: Terms to store
+            path: Destination file path
+            entity_type: Entity type reported for matches
+            case_insensitive: Store case-folded keys
+            locale: "tr" or "az" for Turkic folding
+        """
+        ...
+
+    @staticmethod
+    def open(path: str, confidence: float = 0.85) -> "DictionaryMatcher":
+        """Open a dictionary file, memory-mapping it where supported
+
+        The mapping is read-only and shared, so processes opening the same
+        file share one copy in the OS page cache. The automaton is checked
+        once on open.
+
+        Args:
+            path: File written by build()
+            confidence: Confidence reported for matches
+        """
+        ...
+
+    def contains(self, term: str) -> bool:
+        """Check whether a term is in the dictionary."""
+        ...
+
+    def memory_usage(self) -> Dict[str, int]:
+        """Approximate memory held by the automaton
+
+        Returns:
+            Dict with table_bytes (private heap) and mapped_bytes (shared,
+            file-backed pages)
+        """
+        ...
+
+    def find_matches(self, text: str) -> List[RawMatch]:
+        """Find dictionary terms in text (releases the GIL)
+
+        Returns:
+            List of RawMatch objects; pattern_id is the term's index in sorted key order
+        """
+        ...
+
+class HashedPii:
+    """A hex digest stored under a PII field name."""
+
+    start: int
+    end: int
+    text: str
+    field: str
+    pii_type: str
+    digest: str
+    confidence: float
+
+    @property
+    def entity_type(self) -> str:
+        """Always "HASHED_PII"; the hashed value's kind is in pii_type."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class EnvSecret:
+    """A secret found in a KEY=value assignment."""
+
+    key: str
     start: int
     end: int
     text: str
+    line: int
+    provider: Optional[str]
+    secret_key: bool
+    entropy: float
+    confidence: float
+
+    @property
+    def entity_type(self) -> str:
+        """Always "SECRET"; the provider format, if known, is in provider."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class FieldMatch:
+    """A finding attributed to a named field of a structured record."""
+
+    record: int
+    field: str
     entity_type: str
+    start: int
+    end: int
+    text: str
     confidence: float
 
-class PatternMatcher:
-    """High-performance pattern matcher using Rust regex."""
+    def __repr__(self) -> str: ...
 
-    pattern_count: int
-    failed_count: int
+class Pseudonymizer:
+    """Keyed pseudonym generator."""
+
+    def __init__(self, key: bytes) -> None:
+        """Create a pseudonymizer; the same key always gives the same surrogates."""
+        ...
+
+    def pseudonym(self, entity_type: str, value: str) -> str:
+        """Surrogate value for one entity value."""
+        ...
 
-    def __init__(self, patterns: List[Tuple[str, str, float, int]]) -> None:
+    def replace(self, text: str, findings: List[Finding]) -> str:
+        """Replace every finding span in `text` with its surrogate
+
+        Overlapping findings keep the earliest-starting (then longest) one.
         """
-        Initialize the pattern matcher.
+        ...
+
+class Redactor:
+    """One-pass redaction with a policy per entity type."""
+
+    def __init__(
+        self,
+        policies: Optional[Dict[str, str]] = None,
+        default: str = "mask",
+        key: Optional[bytes] = None,
+    ) -> None:
+        """Create a redactor
 
         Args:
-            patterns: List of (regex, entity_type, confidence, group_idx) tuples
+            policies: Optional {entity_type: policy}; policies are "keep",
+                "remove", "mask", "mask:N" (keep the last N letters/digits),
+                "hash" ("[ENTITY:hex]" keyed hash of the normalized value)
+                and "pseudonym" (format-preserving surrogate)
+            default: Policy for entity types not in policies
+            key: Secret for hash and pseudonym policies
+
+        Raises:
+            ValueError: On an unknown policy, or hash/pseudonym without a key
         """
         ...
 
-    def find_matches(self, text: str) -> List[RawMatch]:
+    def redact(self, text: str, matches: Sequence[Any]) -> str:
+        """Text with every match redacted by its entity's policy
+
+        Args:
+            text: The scanned text
+            matches: Objects with start, end (byte offsets) and entity_type,
+                e.g. RawMatch or Finding
+
+        Overlapping matches keep the earliest-starting (then longest) one.
+
+        Raises:
+            ValueError: If a span is out of range or not on a character boundary
         """
-        Find all pattern matches in text.
+        ...
+
+class PairExtractor:
+    """Compiled label keywords per entity type."""
 
-        This method releases the GIL during execution, allowing
-        other Python threads to run concurrently.
+    def __init__(self, labels: Dict[str, List[str]], max_gap: int = 12) -> None:
+        """Create an extractor
 
         Args:
-            text: The text to scan
+            labels: {entity_type: [label keyword, ...]}, e.g.
+                {"SSN": ["ssn", "social security number"]}
+            max_gap: Maximum characters between label and value
+        """
+        ...
 
-        Returns:
-            List of RawMatch objects
+    def pair(self, text: str, matches: List[RawMatch]) -> List[LabeledPair]:
+        """Pair matches from any scan (PatternMatcher, DictionaryMatcher...)
+        with the labels preceding them in `text`
         """
         ...
 
-    def has_pattern(self, index: int) -> bool:
-        """Check if a specific pattern index is available."""
+class LabeledPair:
+    """A value together with the label that introduced it."""
+
+    entity_type: str
+    label: str
+    label_start: int
+    label_end: int
+    value: str
+    value_start: int
+    value_end: int
+    confidence: float
+    pattern_id: int
+
+    @property
+    def start(self) -> int:
+        """Start of the whole pair (the label)"""
+        ...
+
+    @property
+    def end(self) -> int:
+        """End of the whole pair (the value)"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+class MatchTrace:
+    """Result of match_debug()"""
+
+    pattern: str
+    entity_type: str
+    engine: str
+    triggers: Optional[List[str]]
+    steps: List[TraceStep]
+    findings: List[Finding]
+
+    def report(self) -> str:
+        """The trace as an aligned, one-step-per-line text report."""
         ...
 
+    def __repr__(self) -> str: ...
+
+class TraceStep:
+    """One recorded decision."""
+
+    stage: str
+    start: Optional[int]
+    end: Optional[int]
+    accepted: bool
+    detail: str
+
+    def __repr__(self) -> str: ...
+
+class BenchmarkResult:
+    """Throughput for one pattern count."""
+
+    pattern_count: int
+    bytes: int
+    seconds: float
+    mb_per_s: float
+    matches: int
+
+    def __repr__(self) -> str: ...
+
 def validate_luhn(number: str) -> bool:
     """Validate credit card number using Luhn algorithm."""
     ...
 
-def validate_ssn_format(ssn: str) -> bool:
-    """Validate SSN format (not context)."""
+def validate_ssn_format(ssn: str, area_9xx: str = "reject") -> bool:
+    """Validate SSN format (not context)
+
+    Args:
+        ssn: Candidate number, separators ignored
+        area_9xx: "reject" (default) treats every 9xx area as invalid;
+            "itin" also accepts 9xx numbers that are valid ITINs. Detection
+            does not use this: SSN findings always reject 9xx areas and only
+            ITIN findings accept them
+    """
+    ...
+
+def classify_ssn(ssn: str, area_9xx: str = "itin") -> str:
+    """Classify a nine-digit taxpayer number as "ssn", "itin" or "invalid"
+
+    Args:
+        ssn: Candidate number, separators ignored
+        area_9xx: "itin" (default) classifies 9xx numbers with an ITIN group
+            as ITINs; "reject" treats every 9xx area as invalid
+    """
+    ...
+
+def validate_phone_format(phone: str) -> bool:
+    """Validate US phone number format."""
+    ...
+
+def validate_ipv4_format(ip: str) -> bool:
+    """Validate IPv4 address format."""
+    ...
+
+def is_private_ip(ip: str) -> bool:
+    """Check if IP is private/reserved (likely false positive)"""
+    ...
+
+def validate_cidr(cidr: str) -> bool:
+    """Validate CIDR notation (IPv4 prefix 0-32, IPv6 prefix 0-128)"""
+    ...
+
+def validate_host_port(value: str) -> bool:
+    """Validate host:port (IPv4, [IPv6] or hostname; port 1-65535)"""
+    ...
+
+def validate_domain(host: str) -> bool:
+    """Validate a domain name against the embedded public suffix list
+
+    True only for syntactically valid names under a known public suffix
+    that are not themselves a suffix ("example.co.uk", not "co.uk").
+    """
+    ...
+
+def classify_hostname(host: str) -> str:
+    """Classify a hostname as "public", "internal", "ip" or "invalid"."""
+    ...
+
+def registrable_domain(host: str) -> Optional[str]:
+    """Registrable domain of a host ("mail.example.co.uk" -> "example.co.uk")"""
+    ...
+
+def validate_postal(code: str, country: str) -> bool:
+    """Validate a postal code for a country (ISO 3166 alpha-2, "UK" accepted)
+
+    Raises ValueError for countries without a format check.
+    """
+    ...
+
+def score_serial(
+    value: str,
+    excluded_prefixes: Optional[List[str]] = None,
+    min_length: int = 6,
+    max_length: int = 24,
+) -> float:
+    """Structural plausibility (0.0-1.0) that a value is a serial number
+
+    Args:
+        value: Candidate serial number
+        excluded_prefixes: Prefixes that score 0 (defaults to SKU, PO, INV, ...)
+        min_length: Minimum alphanumeric length
+        max_length: Maximum alphanumeric length
+    """
+    ...
+
+def validate_teudat_zehut(value: str) -> bool:
+    """Validate an Israeli Teudat Zehut (up to 9 digits, weighted check digit)"""
+    ...
+
+def validate_amka(value: str) -> bool:
+    """Validate a Greek AMKA (DDMMYY birth date + serial + Luhn check digit)"""
+    ...
+
+def validate_nhs_number(value: str) -> bool:
+    """Validate a UK NHS number (10 digits, mod-11 check digit)"""
+    ...
+
+def validate_nir(value: str) -> bool:
+    """Validate a French NIR / INSEE number including its 2-digit key (2A/2B allowed)"""
+    ...
+
+def luhn_checksum_digit(partial: str) -> str:
+    """Luhn check digit to append to a partial number
+
+    Spaces and hyphens are ignored; raises ValueError on other characters.
+    """
+    ...
+
+def mod97_checksum_digits(partial: str) -> str:
+    """Two ISO 7064 MOD 97-10 check digits to append to a partial value."""
+    ...
+
+def verhoeff_checksum_digit(partial: str) -> str:
+    """Verhoeff check digit to append to a partial number."""
+    ...
+
+def iban_check_digits(country: str, bban: str) -> str:
+    """IBAN check digits for a country code and BBAN ("GB", "WEST12345698765432" -> "82")"""
+    ...
+
+def validate_mod97(value: str) -> bool:
+    """Validate a value ending in ISO 7064 MOD 97-10 check digits."""
+    ...
+
+def validate_iban(iban: str) -> bool:
+    """Validate an IBAN (spaces allowed)"""
+    ...
+
+def validate_verhoeff(number: str) -> bool:
+    """Validate a number ending in a Verhoeff check digit."""
+    ...
+
+def find_hashed_pii(text: str) -> List[HashedPii]:
+    """Find hex digests (MD5/SHA-*) assigned to PII-named fields
+
+    Detects values like email_md5=... or "ssnHash": "..." and reports the
+    PII kind from the field name and the digest type from its length.
+    Releases the GIL while scanning.
+    """
+    ...
+
+def find_metro2(text: str) -> List[FieldMatch]:
+    """Find consumer fields in Metro 2 credit-report base segments
+
+    Recognizes the fixed-width record structure (newline-delimited or
+    packed) and reports each populated consumer field (name, SSN, date of
+    birth, address, ...) with its record index and field name.
+    Releases the GIL while scanning.
+    """
+    ...
+
+def find_env_secrets(text: str, min_confidence: float = 0.5) -> List[EnvSecret]:
+    """Find secrets in .env files and KEY=value environment dumps
+
+    Classifies each assignment by its key name (TOKEN, SECRET, KEY,
+    PASSWORD, ...), provider token formats (AWS, GitHub, Slack, Stripe,
+    JWT, PEM keys, ...) and value entropy. Placeholders and ${VAR}
+    references under secret-bearing keys are not reported.
+    Releases the GIL while scanning.
+
+    Args:
+        text: Contents of a .env file or `env` output
+        min_confidence: Drop secrets below this confidence
+    """
+    ...
+
+def segment(text: str, level: str = "sentence") -> List[Tuple[int, int]]:
+    """Split text into sentence or paragraph spans
+
+    Args:
+        text: The text to segment
+        level: "sentence" or "paragraph"
+
+    Returns:
+        List of (start, end) byte offsets, whitespace trimmed
+    """
+    ...
+
+def classify_regions(text: str) -> List[Tuple[int, int, str]]:
+    """Split a document into prose, table, code, base64, binary and hexdump regions
+
+    Consecutive lines of one kind form a region and blank lines join the
+    region before them, so the regions cover the whole text. These are the
+    regions PatternMatcher(regions=...) restricts patterns to.
+
+    Returns:
+        List of (start, end, kind) byte offsets in document order
+    """
+    ...
+
+def context_window(
+    text: str,
+    start: int,
+    end: int,
+    radius: int = 40,
+    grapheme_safe: bool = True,
+) -> Tuple[int, int]:
+    """Context window around a span, safe for RTL and combining-mark text
+
+    Args:
+        text: The source text
+        start: Span start (byte offset)
+        end: Span end (byte offset)
+        radius: Characters of context on each side
+        grapheme_safe: Widen edges so no combining mark is split from its base
+
+    Returns:
+        (start, end) byte offsets of the window
+    """
+    ...
+
+def normalize_text(text: str, stages: List[str]) -> str:
+    """Run normalization stages over a text, as PatternMatcher(normalize=...) does
+
+    Args:
+        text: Text to normalize
+        stages: Stage names in the order to run them ("unicode_fold",
+            "collapse_whitespace", "decode_entities", "decode_percent",
+            "ocr_fold")
+
+    Raises:
+        ValueError: For an unknown stage name
+    """
+    ...
+
+def parse_log_line(
+    line: str,
+    format: str = "auto",
+) -> Optional[Tuple[str, List[Tuple[str, int, int]]]]:
+    """Split one syslog / CEF / LEEF line into named fields
+
+    Args:
+        line: A single log line
+        format: "auto", "syslog", "cef" or "leef"
+
+    Returns:
+        (format, [(key, start, end), ...]) with byte offsets into the line,
+        or None if the line is not in a supported format
+    """
+    ...
+
+def annotate(
+    text: str,
+    matches: Sequence[Any],
+    start_marker: str = "\u27e6{entity}\u27e7",
+    end_marker: str = "\u27e6/{entity}\u27e7",
+) -> str:
+    """Text with entity markers inserted around matches
+
+    Args:
+        text: The scanned text
+        matches: Objects with start, end (byte offsets) and entity_type,
+            e.g. RawMatch, Finding or FieldMatch
+        start_marker: Opening marker; "{entity}" is replaced by the entity type
+        end_marker: Closing marker, same substitution
+
+    Overlapping matches keep the earliest-starting (then longest) one.
+
+    Raises:
+        ValueError: If a span is out of range or not on a character boundary
+    """
+    ...
+
+def render_html(text: str, matches: Sequence[Any], mark_class: Optional[str] = None) -> str:
+    """HTML preview with a <mark data-entity="..."> around each match
+
+    The document text and entity types are HTML-escaped; only the marks
+    themselves are markup.
+
+    Args:
+        text: The scanned text
+        matches: Objects with start, end (byte offsets) and entity_type
+        mark_class: Optional class attribute for every mark
+
+    Overlapping matches keep the earliest-starting (then longest) one.
+
+    Raises:
+        ValueError: If a span is out of range or not on a character boundary
+    """
+    ...
+
+def merge_results(results_list: List[List[Any]], overlap_policy: str = "earliest") -> List[Any]:
+    """Combine results from several matchers with the native overlap rule
+
+    Args:
+        results_list: One list of matches per matcher, in precedence order;
+            items need start, end and entity_type, plus score (Finding) or
+            confidence (RawMatch) for "highest_score"
+        overlap_policy: "earliest" (earliest start, then longest, as in
+            annotate and render_html), "highest_score" (highest score, then
+            earliest/longest) or "keep_all" (no overlap resolution)
+
+    Exact duplicates (same start, end and entity_type) keep the item from
+    the earliest list.
+
+    Returns:
+        The kept match objects, sorted by start (then longest first)
+
+    Raises:
+        ValueError: On an unknown policy or a span with end before start
+    """
+    ...
+
+def match_debug(
+    pattern: str,
+    text: str,
+    entity_type: str = "DEBUG",
+    group: int = 0,
+    confidence: float = 1.0,
+    min_score: float = 0.0,
+    limit: int = 1000,
+) -> MatchTrace:
+    """Trace one pattern over a text, recording why candidates were kept or dropped
+
+    Meant for interactive pattern development: runs the pattern the way a
+    PatternMatcher would (same engine choice, same span rules) followed by
+    detect()'s validation and scoring, and records every step.
+
+    Args:
+        pattern: Regex to test
+        text: Sample text
+        entity_type: Entity type, which selects the native validators
+        group: Capture group reported as the match (0 = whole match)
+        confidence: Pattern confidence used for scoring
+        min_score: Score cut applied as in detect()
+        limit: Most engine matches to trace
+
+    Returns:
+        MatchTrace with the engine, two-pass triggers, steps and findings;
+        print(trace.report()) for a readable summary. Compile errors are
+        reported as a rejected "compile" step rather than raised.
+    """
+    ...
+
+def verify_signed_result(payload: str, signature: str, key: bytes) -> bool:
+    """Check a stored PatternMatcher.detect_signed() payload against its signature
+
+    Args:
+        payload: SignedResult.payload exactly as stored
+        signature: SignedResult.signature (hex)
+        key: The key the result was signed with
+
+    Returns:
+        True if the payload is unmodified and was signed with `key`
+    """
     ...
 
 def is_native_available() -> bool:
     """Check if native extension is working."""
     ...
+
+def capabilities() -> Dict[str, bool]:
+    """Optional features available in this build
+
+    Returns:
+        Dict of capability name -> bool for archive, mmap, parallel,
+        parquet, pdf, simd and two_pass; every name is always present
+    """
+    ...
+
+def simd_tier() -> str:
+    """Vector instruction set used for byte searching on this CPU
+
+    Returns:
+        "avx2", "sse2", "neon" or "none"
+    """
+    ...
+
+def benchmark_self(
+    pattern_counts: Optional[List[int]] = None,
+    megabytes: float = 4.0,
+    rounds: int = 3,
+    two_pass: bool = False,
+) -> List[BenchmarkResult]:
+    """Measure scan throughput on this machine with the built-in workload
+
+    Scans the embedded benchmark corpus, repeated to `megabytes`, with the
+    first N patterns of the embedded PII pattern set for each N in
+    `pattern_counts`, on one thread. Each matcher is warmed before timing
+    and the fastest of `rounds` scans is reported. Releases the GIL.
+
+    Args:
+        pattern_counts: Pattern counts to measure (default 1, 8, 16, 32)
+        megabytes: Text size per round in MB (10^6 bytes)
+        rounds: Timed scans per pattern count
+        two_pass: Compile the matchers with two-pass scanning
+
+    Returns:
+        List of BenchmarkResult, one per pattern count
+
+    Raises:
+        ValueError: For a pattern count outside 1..=32, a non-positive
+            size or zero rounds
+    """
+    ...
+
+def benchmark_corpus() -> str:
+    """The synthetic corpus benchmark_self() scans, for comparing other tools."""
+    ...
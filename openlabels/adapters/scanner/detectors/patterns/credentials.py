@@ -26,6 +26,15 @@ add_pattern(r'\b([0-9a-fA-F]{1,4}(?::[0-9a-fA-F]{1,4}){7})\b', 'IP_ADDRESS', CON
 add_pattern(r'\b([0-9a-fA-F]{1,4}(?::[0-9a-fA-F]{1,4}){2,7})\b', 'IP_ADDRESS', CONFIDENCE_WEAK)  # Compressed
 
 
+# Network topology: CIDR blocks and host:port endpoints (distinct from bare IPs)
+
+add_pattern(r'\b(\d{1,3}(?:\.\d{1,3}){3}/\d{1,2})\b', 'IP_CIDR', CONFIDENCE_MEDIUM)
+add_pattern(r'\b([0-9a-fA-F]{1,4}(?::[0-9a-fA-F]{0,4}){2,7}/\d{1,3})\b', 'IP_CIDR', CONFIDENCE_LOW)
+add_pattern(r'\b(\d{1,3}(?:\.\d{1,3}){3}:\d{1,5})\b', 'HOST_PORT', CONFIDENCE_MEDIUM)
+add_pattern(r'(\[[0-9a-fA-F:]{2,39}\]:\d{1,5})\b', 'HOST_PORT', CONFIDENCE_MEDIUM)
+add_pattern(r'\b((?:[a-zA-Z0-9-]{1,63}\.)+[a-zA-Z]{2,63}:\d{1,5})\b', 'HOST_PORT', CONFIDENCE_WEAK)
+
+
 # MAC Address
 
 add_pattern(r'\b([0-9A-Fa-f]{2}[:-]){5}[0-9A-Fa-f]{2}\b', 'MAC_ADDRESS', CONFIDENCE_MEDIUM)
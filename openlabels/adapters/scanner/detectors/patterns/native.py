@@ -26,7 +26,14 @@ logger = logging.getLogger(__name__)
 
 # Try to import the Rust extension
 try:
-    from openlabels._rust import PatternMatcher, RawMatch, validate_luhn, is_native_available
+    from openlabels._rust import (
+        PatternMatcher,
+        RawMatch,
+        is_native_available,
+        validate_cidr,
+        validate_host_port,
+        validate_luhn,
+    )
 
     _NATIVE_AVAILABLE = is_native_available()
 except ImportError as e:
@@ -35,6 +42,8 @@ except ImportError as e:
     PatternMatcher = None
     RawMatch = None
     validate_luhn = None
+    validate_cidr = None
+    validate_host_port = None
 
 
 class NativePatternDetector:
@@ -151,6 +160,12 @@ class NativePatternDetector:
         if et == "IP_ADDRESS":
             return validate_ip(value)
 
+        if et == "IP_CIDR":
+            return validate_cidr(value)
+
+        if et == "HOST_PORT":
+            return validate_host_port(value)
+
         if et in ("PHONE", "PHONE_MOBILE", "PHONE_HOME", "PHONE_WORK", "FAX"):
             return validate_phone(value)
 
@@ -224,6 +239,10 @@ class NativePatternDetector:
                 # Apply same validation as Rust path
                 if entity_type == "IP_ADDRESS" and not validate_ip(value):
                     continue
+                if entity_type == "IP_CIDR" and not validate_cidr(value):
+                    continue
+                if entity_type == "HOST_PORT" and not validate_host_port(value):
+                    continue
                 if entity_type in ("PHONE", "PHONE_MOBILE", "PHONE_HOME", "PHONE_WORK", "FAX"):
                     if not validate_phone(value):
                         continue
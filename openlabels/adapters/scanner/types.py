@@ -85,7 +85,7 @@ KNOWN_ENTITY_TYPES = frozenset([
     "USERNAME",
 
     # --- NETWORK & DEVICE ---
-    "IP_ADDRESS", "IP", "IPADDRESS", "IPV4", "IPV6",
+    "IP_ADDRESS", "IP", "IPADDRESS", "IPV4", "IPV6", "IP_CIDR", "HOST_PORT",
     "MAC_ADDRESS", "MAC", "MACADDRESS",
     "DEVICE_ID", "IMEI", "DEVICE", "BIOID", "USERAGENT", "USER_AGENT",
     "BIOMETRIC_ID", "FINGERPRINT", "RETINAL", "IRIS", "VOICEPRINT", "DNA_ID",
@@ -118,7 +118,10 @@ ENTITY_CATEGORIES: Dict[str, str] = {
 
     # Digital identifiers
     "IP_ADDRESS": "digital_identifier",
+    "IP_CIDR": "digital_identifier",
+    "HOST_PORT": "digital_identifier",
     "MAC_ADDRESS": "digital_identifier",
+    "HASHED_PII": "digital_identifier",
     "DEVICE_ID": "digital_identifier",
     "IMEI": "digital_identifier",
     "USERNAME": "digital_identifier",
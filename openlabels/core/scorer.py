@@ -9,9 +9,14 @@ Formula:
     final_score = min(100, content_score × exposure_multiplier)
 
 Weights are sourced from registry.py (1-10 scale) and scaled for scoring.
+When a native Taxonomy is passed (opt-in), co-occurrence categories also
+include its identifier classes (direct, quasi), so its per-type overrides
+affect the score, and a combination of quasi-identifiers that singles
+people out counts as a direct identifier. Without one, scores are
+unchanged.
 """
 
-from typing import List, Dict, Set, Tuple
+from typing import List, Dict, Optional, Set, Tuple
 from dataclasses import dataclass
 from enum import Enum
 import logging
@@ -25,6 +30,11 @@ from .registry import (
 from .entity_types import normalize_entity_type
 from .constants import DEFAULT_CONFIDENCE_THRESHOLD
 
+try:
+    from openlabels._rust import Taxonomy
+except ImportError:
+    Taxonomy = None
+
 
 class RiskTier(Enum):
     """Risk tier classification."""
@@ -60,6 +70,16 @@ CO_OCCURRENCE_RULES: List[Tuple[Set[str], float, str]] = [
     ({'classification_marking'}, 2.5, 'classified_data'),
 ]
 
+# Identifier classes of the native taxonomy -> co-occurrence categories
+TAXONOMY_CATEGORIES = {
+    'direct': 'direct_identifier',
+    'quasi': 'quasi_identifier',
+}
+
+# Identifiability at which quasi-identifiers alone count as a direct
+# identifier: three distinct types (ZIP + DOB + gender) reach 0.875
+REIDENTIFIABLE = 0.875
+
 # Tier thresholds
 TIER_THRESHOLDS = {
     'critical': 80,
@@ -92,6 +112,7 @@ class ScoringResult:
     co_occurrence_rules: List[str]    # Which rules triggered
     categories: Set[str]              # Entity categories present
     exposure: str                     # Exposure level
+    identifiability: float = 0.0      # Taxonomy re-identification measure (0-1)
 
     def to_dict(self) -> dict:
         return {
@@ -103,6 +124,7 @@ class ScoringResult:
             'co_occurrence_rules': self.co_occurrence_rules,
             'categories': list(self.categories),
             'exposure': self.exposure,
+            'identifiability': self.identifiability,
         }
 
 
@@ -116,26 +138,52 @@ def get_entity_weight(entity_type: str) -> float:
     return raw_weight * WEIGHT_SCALE
 
 
-def get_categories(entities: Dict[str, int]) -> Set[str]:
-    """Get set of categories present in entities."""
+def _canonical_type(entity_type: str) -> str:
+    """Registry name of an entity type (SIN -> SIN_CA, etc.)."""
+    return normalize_type(normalize_entity_type(entity_type))
+
+
+def get_identifiability(entities: Dict[str, int], taxonomy=None) -> float:
+    """Re-identification measure (0-1) of the entities under the taxonomy (0 without one)."""
+    if taxonomy is None or not entities:
+        return 0.0
+    types = [_canonical_type(t) for t in entities]
+    return taxonomy.document_risk(types).identifiability
+
+
+def get_categories(entities: Dict[str, int], taxonomy=None) -> Set[str]:
+    """
+    Get set of categories present in entities.
+
+    Registry categories, plus the taxonomy's identifier classes (see
+    TAXONOMY_CATEGORIES) when a taxonomy is given.
+    """
     categories = set()
     for entity_type in entities:
-        normalized = normalize_entity_type(entity_type)
-        canonical = normalize_type(normalized)
-        cat = registry_get_category(canonical)
+        cat = registry_get_category(_canonical_type(entity_type))
         if cat and cat != "unknown":
             categories.add(cat)
+
+    if taxonomy is not None and entities:
+        types = [_canonical_type(t) for t in entities]
+        for entity_type in types:
+            category = TAXONOMY_CATEGORIES.get(taxonomy.classify(entity_type))
+            if category:
+                categories.add(category)
+        if taxonomy.document_risk(types).identifiability >= REIDENTIFIABLE:
+            categories.add('direct_identifier')
     return categories
 
 
 def get_co_occurrence_multiplier(
-    entities: Dict[str, int]
+    entities: Dict[str, int],
+    taxonomy=None,
 ) -> Tuple[float, List[str]]:
     """Get the highest applicable co-occurrence multiplier and triggered rules."""
     if not entities:
         return 1.0, []
 
-    categories = get_categories(entities)
+    categories = get_categories(entities, taxonomy)
     max_mult = 1.0
     triggered_rules = []
 
@@ -153,6 +201,7 @@ def get_co_occurrence_multiplier(
 def calculate_content_score(
     entities: Dict[str, int],
     confidence: float = DEFAULT_CONFIDENCE_THRESHOLD,
+    taxonomy=None,
 ) -> float:
     """
     Calculate content sensitivity score from detected entities.
@@ -160,6 +209,7 @@ def calculate_content_score(
     Args:
         entities: Dict of {entity_type: count}
         confidence: Average detection confidence (0.0-1.0)
+        taxonomy: Native Taxonomy adding identifier-class categories (None = registry only)
 
     Returns:
         Content score (0-100 scale, before exposure adjustment)
@@ -176,7 +226,7 @@ def calculate_content_score(
         base_score += entity_score
 
     # Apply co-occurrence multiplier
-    multiplier, _ = get_co_occurrence_multiplier(entities)
+    multiplier, _ = get_co_occurrence_multiplier(entities, taxonomy)
     adjusted_score = base_score * multiplier
 
     # Cap at 100
@@ -201,6 +251,7 @@ def score(
     entities: Dict[str, int],
     exposure: str = 'PRIVATE',
     confidence: float = DEFAULT_CONFIDENCE_THRESHOLD,
+    taxonomy: Optional["Taxonomy"] = None,
 ) -> ScoringResult:
     """
     Calculate risk score from detected entities and exposure context.
@@ -211,6 +262,9 @@ def score(
         entities: Dict of {entity_type: count} from detection
         exposure: Exposure level (PRIVATE, INTERNAL, ORG_WIDE, PUBLIC)
         confidence: Average detection confidence
+        taxonomy: Native Taxonomy classifying identifiers, e.g. the
+            matcher's (PatternMatcher.taxonomy); opt-in, None scores with
+            registry categories only
 
     Returns:
         ScoringResult with score, tier, and breakdown
@@ -221,10 +275,10 @@ def score(
         Risk: 100 (CRITICAL)
     """
     # Calculate content score
-    content_score = calculate_content_score(entities, confidence)
+    content_score = calculate_content_score(entities, confidence, taxonomy)
 
     # Get co-occurrence info
-    co_mult, co_rules = get_co_occurrence_multiplier(entities)
+    co_mult, co_rules = get_co_occurrence_multiplier(entities, taxonomy)
 
     # Apply exposure multiplier
     exp_mult = EXPOSURE_MULTIPLIERS.get(exposure.upper(), 1.0)
@@ -240,8 +294,9 @@ def score(
         exposure_multiplier=exp_mult,
         co_occurrence_multiplier=co_mult,
         co_occurrence_rules=co_rules,
-        categories=get_categories(entities),
+        categories=get_categories(entities, taxonomy),
         exposure=exposure.upper(),
+        identifiability=get_identifiability(entities, taxonomy),
     )
 
 
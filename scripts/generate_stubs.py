@@ -0,0 +1,589 @@
+#!/usr/bin/env python3
+"""
+Generate openlabels/_rust.pyi from the Rust extension sources.
+
+The stubs are derived from the pyo3 declarations in src/: every class and
+function registered in src/lib.rs, with parameter names and defaults from
+the `#[pyo3(signature = ...)]` attributes, types mapped from the Rust
+signatures, and docstrings from the `///` doc comments (the same text pyo3
+exposes as __doc__ at runtime). No compiled extension is needed.
+
+Usage:
+    python scripts/generate_stubs.py            # rewrite openlabels/_rust.pyi
+    python scripts/generate_stubs.py --check    # exit 1 if the stubs are stale
+
+tests/test_rust_stubs.py runs the check, so a Rust API change without
+regenerated stubs fails the test suite.
+"""
+
+import argparse
+import json
+import re
+import sys
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Dict, List, Optional, Tuple
+
+ROOT = Path(__file__).resolve().parent.parent
+SRC = ROOT / "src"
+STUBS = ROOT / "openlabels" / "_rust.pyi"
+
+HEADER = '''"""Type stubs for the Rust extension.
+
+Generated by scripts/generate_stubs.py from the Rust sources; do not edit.
+"""
+
+from typing import Any, Dict, List, Optional, Sequence, Tuple
+
+'''
+
+# Rust types with a fixed Python equivalent
+SCALARS = {
+    "String": "str",
+    "str": "str",
+    "char": "str",
+    "bool": "bool",
+    "f32": "float",
+    "f64": "float",
+    "u8": "int",
+    "u16": "int",
+    "u32": "int",
+    "u64": "int",
+    "usize": "int",
+    "i32": "int",
+    "i64": "int",
+    "isize": "int",
+    "PyObject": "Any",
+    "PyAny": "Any",
+    "PyByteArray": "bytearray",
+    "PyBytes": "bytes",
+    "PyDict": "Dict[str, Any]",
+    "PyList": "List[Any]",
+    "()": "None",
+}
+
+# Wrappers that are transparent in Python
+TRANSPARENT = {"PyResult", "Bound", "Py", "PyRef", "PyRefMut", "Arc", "Box", "Cow"}
+
+# Parameters pyo3 fills in itself
+IMPLICIT_PARAMS = {"self", "slf", "py"}
+
+
+class StubError(Exception):
+    pass
+
+
+@dataclass
+class Function:
+    name: str
+    params: List[Tuple[str, str, Optional[str]]]  # (name, type, default)
+    returns: str
+    doc: List[str]
+    kind: str = "method"  # method, new, getter, staticmethod
+
+
+@dataclass
+class PyClass:
+    name: str
+    doc: List[str]
+    attributes: List[Tuple[str, str]] = field(default_factory=list)
+    methods: List[Function] = field(default_factory=list)
+
+
+# --- Rust source scanning -------------------------------------------------
+
+
+def strip_comments(line: str) -> str:
+    """Drop a trailing // comment (but not inside string literals)."""
+    in_string = False
+    prev = ""
+    for i, c in enumerate(line):
+        if c == '"' and prev != "\\":
+            in_string = not in_string
+        elif c == "/" and prev == "/" and not in_string:
+            return line[: i - 1]
+        prev = "" if prev == "\\" and c == "\\" else c
+    return line
+
+
+def split_top_level(text: str, sep: str = ",") -> List[str]:
+    """Split on `sep` outside (), [], {}, <> and string literals."""
+    parts: List[str] = []
+    depth = 0
+    in_string = False
+    current = ""
+    prev = ""
+    for c in text:
+        if in_string:
+            current += c
+            if c == '"' and prev != "\\":
+                in_string = False
+            prev = "" if prev == "\\" and c == "\\" else c
+            continue
+        if c == '"':
+            in_string = True
+        elif c in "([{<":
+            depth += 1
+        elif c in ")]}>" and not (c == ">" and prev == "-"):
+            depth -= 1
+        if c == sep and depth == 0:
+            parts.append(current.strip())
+            current = ""
+        else:
+            current += c
+        prev = c
+    if current.strip():
+        parts.append(current.strip())
+    return parts
+
+
+def read_item(lines: List[str], i: int) -> Tuple[str, int]:
+    """Join lines from i up to the opening `{` or `;` of an item."""
+    text = ""
+    while i < len(lines):
+        line = strip_comments(lines[i]).strip()
+        text += " " + line
+        i += 1
+        if line.endswith("{") or line.endswith(";") or line.endswith("}"):
+            break
+    return text.strip(), i
+
+
+def collect_attributes_and_doc(lines: List[str], i: int) -> Tuple[List[str], List[str]]:
+    """Doc comment lines and attributes directly above line i."""
+    j = i
+    while j > 0:
+        line = lines[j - 1].strip()
+        if line.startswith("///"):
+            j -= 1
+            continue
+        if not line or line == "}" or line.endswith("{") or line.endswith(";") or line.startswith("//"):
+            break
+        j -= 1
+
+    doc: List[str] = []
+    attrs: List[str] = []
+    current = ""
+    depth = 0
+    for raw in lines[j:i]:
+        line = raw.strip()
+        if depth == 0 and line.startswith("///"):
+            text = line[3:]
+            doc.append(text[1:] if text.startswith(" ") else text)
+            continue
+        if depth == 0 and not line.startswith("#["):
+            continue
+        current += (" " if current else "") + line
+        depth += line.count("[") + line.count("(") - line.count("]") - line.count(")")
+        if depth <= 0:
+            attrs.append(current)
+            current = ""
+            depth = 0
+    return attrs, doc
+
+
+def attribute_args(attrs: List[str], name: str) -> Dict[str, str]:
+    """Key/value arguments of #[pyo3(...)] attributes."""
+    args: Dict[str, str] = {}
+    for attr in attrs:
+        m = re.match(r"#\[" + name + r"\((.*)\)\]", re.sub(r"\s+", " ", attr).replace("( ", "(").replace(" )", ")"))
+        if not m:
+            continue
+        for part in split_top_level(m.group(1)):
+            if "=" in part:
+                key, value = part.split("=", 1)
+                args[key.strip()] = value.strip()
+            else:
+                args[part.strip()] = ""
+    return args
+
+
+def parse_fn(header: str) -> Tuple[str, List[Tuple[str, str]], Optional[str]]:
+    """Name, (param, type) pairs and return type of a fn header."""
+    m = re.search(r"\bfn\s+(\w+)\s*(<[^(]*>)?\s*\(", header)
+    if not m:
+        raise StubError("not a function: " + header)
+    name = m.group(1)
+    depth = 1
+    i = m.end()
+    start = i
+    while depth:
+        if header[i] == "(":
+            depth += 1
+        elif header[i] == ")":
+            depth -= 1
+        i += 1
+    params: List[Tuple[str, str]] = []
+    for part in split_top_level(header[start : i - 1]):
+        if part in ("&self", "&mut self", "self"):
+            continue
+        pname, ptype = part.split(":", 1)
+        params.append((re.sub(r"^mut\s+", "", pname.strip()), ptype.strip()))
+    rest = header[i:].strip()
+    returns = None
+    if rest.startswith("->"):
+        returns = re.sub(r"\s*(where\b.*)?[{;]$", "", rest[2:]).strip()
+    return name, params, returns
+
+
+# --- Type mapping -----------------------------------------------------------
+
+
+def generic_args(rust: str) -> Tuple[str, List[str]]:
+    m = re.match(r"^([\w:]+)\s*<(.*)>$", rust)
+    if not m:
+        return rust, []
+    args = [a for a in split_top_level(m.group(2)) if not a.startswith("'")]
+    return m.group(1).split("::")[-1], args
+
+
+def py_type(rust: str, owner: Optional[str]) -> str:
+    """Python annotation for a Rust type."""
+    rust = rust.strip()
+    rust = re.sub(r"^&\s*('\w+\s+)?(mut\s+)?", "", rust)
+    if rust.startswith("(") and rust.endswith(")") and rust != "()":
+        items = split_top_level(rust[1:-1])
+        return "Tuple[" + ", ".join(py_type(t, owner) for t in items) + "]"
+    if rust.startswith("[") and rust.endswith("]"):
+        inner = rust[1:-1].split(";")[0]
+        return "bytes" if inner.strip() == "u8" else "Sequence[" + py_type(inner, owner) + "]"
+    base, args = generic_args(rust)
+    if base in SCALARS and not args:
+        return SCALARS[base]
+    if base == "Self":
+        return '"' + owner + '"' if owner else "Any"
+    if base in TRANSPARENT:
+        return py_type(args[-1], owner)
+    if base == "Option":
+        inner = py_type(args[0], owner)
+        return inner if inner == "Any" else "Optional[" + inner + "]"
+    if base == "Vec":
+        return "bytes" if args[0] == "u8" else "List[" + py_type(args[0], owner) + "]"
+    if base in ("HashMap", "BTreeMap"):
+        return "Dict[" + py_type(args[0], owner) + ", " + py_type(args[1], owner) + "]"
+    if base in ("HashSet", "BTreeSet"):
+        return "List[" + py_type(args[0], owner) + "]"
+    if base in TYPE_ALIASES:
+        return py_type(TYPE_ALIASES[base], owner)
+    if base in CLASSES:
+        return base if base != owner else '"' + base + '"'
+    raise StubError("no Python type for Rust type '{}'".format(rust))
+
+
+def param_type(rust: str, owner: Optional[str]) -> str:
+    """Like py_type, but sequence arguments accept any sequence."""
+    annotation = py_type(rust, owner)
+    if annotation.startswith("List[") and annotation != "List[Any]":
+        return annotation
+    if annotation == "List[Any]":
+        return "Sequence[Any]"
+    return annotation
+
+
+# --- Default values -----------------------------------------------------------
+
+
+def rust_string(literal: str) -> str:
+    """Value of a Rust string literal."""
+    body = literal[1:-1]
+    body = re.sub(r"\\u\{([0-9a-fA-F]+)\}", lambda m: chr(int(m.group(1), 16)), body)
+    return body.encode("latin-1", "backslashreplace").decode("unicode_escape") if "\\" in body else body
+
+
+def py_default(value: str, module: str) -> str:
+    value = value.strip()
+    if value in ("true", "false"):
+        return value.capitalize()
+    if value == "None":
+        return "None"
+    if value.startswith("Some(") and value.endswith(")"):
+        return py_default(value[5:-1], module)
+    if value.startswith('b"'):
+        # b"*" or b"*".as_slice()
+        return value[: value.index('"', 2) + 1]
+    if value.startswith('"'):
+        return json.dumps(rust_string(value))
+    if re.match(r"^-?[\d_]+(\.[\d_]+)?$", value):
+        return value.replace("_", "")
+    if re.match(r"^[\w:]+$", value):
+        path = value.split("::")
+        const_module = path[-2] if len(path) > 1 else module
+        resolved = CONSTANTS.get((const_module, path[-1]))
+        if resolved is None:
+            raise StubError("cannot resolve default '{}'".format(value))
+        return py_default(resolved, const_module)
+    raise StubError("cannot convert default '{}'".format(value))
+
+
+def signature_params(
+    signature: Optional[str], rust_params: List[Tuple[str, str]], module: str, owner: Optional[str]
+) -> List[Tuple[str, str, Optional[str]]]:
+    types = {name: ptype for name, ptype in rust_params}
+    visible = [name for name, ptype in rust_params if name not in IMPLICIT_PARAMS and not is_python_token(ptype)]
+    if signature is None:
+        entries = [(name, None) for name in visible]
+    else:
+        entries = []
+        for part in split_top_level(signature.strip()[1:-1]):
+            if part in ("*", "/"):
+                entries.append((part, None))
+            elif "=" in part:
+                name, default = part.split("=", 1)
+                entries.append((name.strip(), default.strip()))
+            else:
+                entries.append((part.strip(), None))
+    params: List[Tuple[str, str, Optional[str]]] = []
+    for name, default in entries:
+        if name in ("*", "/"):
+            params.append((name, "", None))
+            continue
+        if name not in types:
+            raise StubError("signature names unknown parameter '{}'".format(name))
+        params.append((name, param_type(types[name], owner), None if default is None else py_default(default, module)))
+    return params
+
+
+def is_python_token(rust: str) -> bool:
+    return bool(re.match(r"^Python\s*(<.*>)?$", rust.strip()))
+
+
+# --- Source model ---------------------------------------------------------------
+
+CLASSES: Dict[str, PyClass] = {}
+TYPE_ALIASES: Dict[str, str] = {}
+CONSTANTS: Dict[Tuple[str, str], str] = {}
+
+
+def api_lines(path: Path) -> List[str]:
+    """Lines of a module before its test section."""
+    lines = path.read_text(encoding="utf-8").splitlines()
+    for i, line in enumerate(lines):
+        if line.strip() == "#[cfg(test)]":
+            return lines[:i]
+    return lines
+
+
+def scan_declarations(path: Path) -> None:
+    """Class names, type aliases and string constants, needed before any signature is mapped."""
+    module = path.stem
+    lines = api_lines(path)
+    for i, line in enumerate(lines):
+        if line.strip().startswith("#[pyclass"):
+            j = i + 1
+            while not re.search(r"\bstruct\s+\w+", lines[j]):
+                j += 1
+            name = re.search(r"struct\s+(\w+)", lines[j]).group(1)
+            CLASSES.setdefault(name, PyClass(name, []))
+
+    for line in lines:
+        m = re.match(r"^\s*(?:pub(?:\([\w:]+\))?\s+)?type\s+(\w+)\s*=\s*(.+);", line)
+        if m:
+            TYPE_ALIASES[m.group(1)] = m.group(2)
+        m = re.match(r'^\s*(?:pub(?:\([\w:]+\))?\s+)?const\s+(\w+)\s*:\s*[^=]+=\s*(".*"|[-\d_.]+);', line)
+        if m:
+            CONSTANTS[(module, m.group(1))] = m.group(2)
+
+
+def scan_module(path: Path, functions: Dict[str, Tuple[Function, str]]) -> None:
+    module = path.stem
+    lines = api_lines(path)
+    i = 0
+    while i < len(lines):
+        stripped = lines[i].strip()
+        if stripped.startswith("#[pyclass"):
+            attrs, doc = collect_attributes_and_doc(lines, i + 1)
+            j = i + 1
+            while not re.match(r"^\s*pub(\([\w:]+\))?\s+struct\s+\w+", lines[j]):
+                j += 1
+            name = re.search(r"struct\s+(\w+)", lines[j]).group(1)
+            cls = CLASSES.setdefault(name, PyClass(name, []))
+            cls.doc = doc
+            k = j + 1
+            while k < len(lines) and not lines[k].startswith("}"):
+                if "#[pyo3(get" in lines[k]:
+                    f = k + 1
+                    while not re.match(r"^\s*(pub(\([\w:]+\))?\s+)?\w+\s*:", lines[f]):
+                        f += 1
+                    m = re.match(r"^\s*(?:pub(?:\([\w:]+\))?\s+)?(\w+)\s*:\s*(.+),\s*$", lines[f])
+                    cls.attributes.append((m.group(1), m.group(2)))
+                k += 1
+            i = k
+        elif stripped == "#[pymethods]":
+            j = i + 1
+            owner = re.search(r"impl\s+(\w+)", lines[j]).group(1)
+            cls = CLASSES.setdefault(owner, PyClass(owner, []))
+            k = j + 1
+            while k < len(lines) and not lines[k].startswith("}"):
+                if re.match(r"^    (pub(\([\w:]+\))?\s+)?fn\s", lines[k]):
+                    attrs, doc = collect_attributes_and_doc(lines, k)
+                    header, _ = read_item(lines, k)
+                    cls.methods.append(build_function(header, attrs, doc, module, owner))
+                k += 1
+            i = k
+        elif stripped == "#[pyfunction]":
+            k = i + 1
+            while not re.match(r"^(pub(\([\w:]+\))?\s+)?fn\s", lines[k]):
+                k += 1
+            attrs, doc = collect_attributes_and_doc(lines, k)
+            header, _ = read_item(lines, k)
+            function = build_function(header, attrs, doc, module, None)
+            rust_name = parse_fn(header)[0]
+            functions[rust_name] = (function, module)
+            i = k + 1
+        else:
+            i += 1
+
+
+def build_function(header: str, attrs: List[str], doc: List[str], module: str, owner: Optional[str]) -> Function:
+    rust_name, rust_params, returns = parse_fn(header)
+    pyo3 = attribute_args(attrs, "pyo3")
+    name = json.loads(pyo3["name"]) if "name" in pyo3 else rust_name
+    kind = "method"
+    if "#[new]" in attrs:
+        kind = "new"
+    elif "#[getter]" in attrs:
+        kind = "getter"
+    elif "#[staticmethod]" in attrs:
+        kind = "staticmethod"
+    params = signature_params(pyo3.get("signature"), rust_params, module, owner)
+    if kind == "new":
+        py_returns = "None"
+    elif returns is None:
+        py_returns = "None"
+    elif name == "__next__":
+        # Returning None ends the iteration
+        base, args = generic_args(returns)
+        inner = args[-1] if base == "PyResult" else returns
+        base, args = generic_args(inner)
+        py_returns = py_type(args[0] if base == "Option" else inner, owner)
+    else:
+        py_returns = py_type(returns, owner)
+    return Function(name, params, py_returns, doc, kind)
+
+
+# --- Rendering --------------------------------------------------------------------
+
+
+def render_doc(doc: List[str], indent: str) -> List[str]:
+    while doc and not doc[-1].strip():
+        doc = doc[:-1]
+    if not doc:
+        return []
+    if len(doc) == 1:
+        text = doc[0].strip()
+        if text and not text.endswith((".", "?", "!", ")", ":")):
+            text += "."
+        return [indent + '"""' + text.replace('"""', '\\"\\"\\"') + '"""']
+    out = [indent + '"""' + doc[0].strip()]
+    for line in doc[1:]:
+        out.append((indent + line).rstrip() if line.strip() else "")
+    out.append(indent + '"""')
+    return out
+
+
+def render_function(function: Function, indent: str) -> List[str]:
+    out: List[str] = []
+    if function.kind == "staticmethod":
+        out.append(indent + "@staticmethod")
+    name = "__init__" if function.kind == "new" else function.name
+    params = ["self"] if indent and function.kind != "staticmethod" else []
+    for pname, ptype, default in function.params:
+        if pname in ("*", "/"):
+            params.append(pname)
+        elif default is None:
+            params.append("{}: {}".format(pname, ptype))
+        else:
+            params.append("{}: {} = {}".format(pname, ptype, default))
+    one_line = "{}def {}({}) -> {}:".format(indent, name, ", ".join(params), function.returns)
+    if len(one_line) <= 100:
+        out.append(one_line)
+    else:
+        out.append("{}def {}(".format(indent, name))
+        for p in params:
+            out.append("{}    {},".format(indent, p))
+        out.append("{}) -> {}:".format(indent, function.returns))
+    body = render_doc(function.doc, indent + "    ")
+    if body:
+        out.extend(body)
+        out.append(indent + "    ...")
+    else:
+        out[-1] += " ..."
+    return out
+
+
+def render_class(cls: PyClass) -> List[str]:
+    """Class header and docstring, then attributes, properties and methods
+    as blank-line separated blocks."""
+    blocks: List[List[str]] = []
+    if cls.attributes:
+        blocks.append(["    {}: {}".format(attr, py_type(rust, cls.name)) for attr, rust in cls.attributes])
+    for method in cls.methods:
+        if method.kind == "getter":
+            blocks.append(["    @property"] + render_function(method, "    "))
+    for method in cls.methods:
+        if method.kind != "getter":
+            blocks.append(render_function(method, "    "))
+
+    out = ["class {}:".format(cls.name)] + render_doc(cls.doc, "    ")
+    if len(out) == 1 and not blocks:
+        return [out[0] + " ..."]
+    for k, block in enumerate(blocks):
+        if k > 0 or len(out) > 1:
+            out.append("")
+        out.extend(block)
+    return out
+
+
+def registered(lib: str) -> Tuple[List[str], List[str]]:
+    classes = re.findall(r"add_class::<(\w+)>", lib)
+    functions = re.findall(r"wrap_pyfunction!\((\w+)", lib)
+    return classes, functions
+
+
+def generate() -> str:
+    CLASSES.clear()
+    TYPE_ALIASES.clear()
+    CONSTANTS.clear()
+    functions: Dict[str, Tuple[Function, str]] = {}
+    paths = sorted(SRC.glob("*.rs"))
+    for path in paths:
+        scan_declarations(path)
+    for path in paths:
+        scan_module(path, functions)
+    class_names, function_names = registered((SRC / "lib.rs").read_text(encoding="utf-8"))
+
+    blocks: List[List[str]] = []
+    for name in class_names:
+        if name not in CLASSES:
+            raise StubError("registered class {} has no #[pyclass]".format(name))
+        blocks.append(render_class(CLASSES[name]))
+    for name in function_names:
+        if name not in functions:
+            raise StubError("registered function {} has no #[pyfunction]".format(name))
+        blocks.append(render_function(functions[name][0], ""))
+    return HEADER + "\n\n".join("\n".join(block) for block in blocks) + "\n"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.split("\n\n")[0].strip())
+    parser.add_argument("--check", action="store_true", help="fail if the stubs differ from the generated ones")
+    args = parser.parse_args()
+
+    try:
+        stubs = generate()
+    except StubError as e:
+        print("error: {}".format(e), file=sys.stderr)
+        return 2
+    if args.check:
+        current = STUBS.read_text(encoding="utf-8") if STUBS.exists() else ""
+        if current != stubs:
+            print("{} is out of date; run scripts/generate_stubs.py".format(STUBS.relative_to(ROOT)), file=sys.stderr)
+            return 1
+        return 0
+    STUBS.write_text(stubs, encoding="utf-8")
+    print("wrote {}".format(STUBS.relative_to(ROOT)))
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,138 @@
+//! Built-in allowlist of well-known non-sensitive values
+//!
+//! Processor test card numbers, SSNs printed in documentation and
+//! advertising, reserved documentation domains and address blocks turn up
+//! in fixtures, samples and manuals, never as real identifiers. The lists
+//! are embedded from `src/data/allowlist.txt`; PatternMatcher.detect() drops
+//! findings with these values unless built with builtin_allowlist=False.
+
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::net::IpAddr;
+
+use crate::finding::Finding;
+use crate::network;
+
+/// Values parsed from the embedded allowlist data
+#[derive(Default)]
+struct Allowlist {
+    /// Card numbers, digits only
+    cards: HashSet<&'static str>,
+    /// SSNs, digits only
+    ssns: HashSet<&'static str>,
+    /// Reserved names, matching themselves and their subdomains
+    domains: Vec<&'static str>,
+    /// Documentation networks as (network address, prefix length)
+    networks: Vec<(IpAddr, u32)>,
+}
+
+static BUILTIN: Lazy<Allowlist> = Lazy::new(|| {
+    let mut list = Allowlist::default();
+    let mut section = "";
+    for line in include_str!("data/allowlist.txt").lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name;
+            continue;
+        }
+        match section {
+            "cards" => {
+                list.cards.insert(line);
+            }
+            "ssns" => {
+                list.ssns.insert(line);
+            }
+            "domains" => list.domains.push(line),
+            "networks" => {
+                if let Some((addr, prefix, _)) = network::parse_cidr(line) {
+                    if let Ok(addr) = addr.parse() {
+                        list.networks.push((addr, prefix));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    list
+});
+
+fn digits(value: &str) -> String {
+    value.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+fn in_network(ip: IpAddr, (network, prefix): (IpAddr, u32)) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
+
+fn reserved_domain(host: &str) -> bool {
+    let host = host.trim().trim_end_matches('.').to_ascii_lowercase();
+    BUILTIN
+        .domains
+        .iter()
+        .any(|d| host == *d || host.strip_suffix(d).is_some_and(|rest| rest.ends_with('.')))
+}
+
+/// Whether a matched value of `entity_type` is on the built-in allowlist
+pub fn is_allowlisted(entity_type: &str, value: &str) -> bool {
+    match entity_type {
+        "CREDIT_CARD" => BUILTIN.cards.contains(digits(value).as_str()),
+        "SSN" => BUILTIN.ssns.contains(digits(value).as_str()),
+        "DOMAIN" | "HOSTNAME" => reserved_domain(value),
+        "EMAIL" => value.rsplit_once('@').is_some_and(|(_, host)| reserved_domain(host)),
+        "IP_ADDRESS" => value
+            .trim()
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| BUILTIN.networks.iter().any(|&net| in_network(ip, net))),
+        _ => false,
+    }
+}
+
+/// Drop findings whose value is on the built-in allowlist
+///
+/// The repaired value is checked when there is one; truncated findings are
+/// kept since only a preview of the value is known.
+pub fn drop_allowlisted(findings: &mut Vec<Finding>) {
+    findings.retain(|f| f.truncated || !is_allowlisted(&f.entity_type, f.repaired.as_deref().unwrap_or(&f.text)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators;
+
+    #[test]
+    fn test_builtin_values() {
+        assert!(is_allowlisted("CREDIT_CARD", "4111 1111 1111 1111"));
+        assert!(!is_allowlisted("CREDIT_CARD", "4111 1111 1111 1112"));
+        assert!(is_allowlisted("SSN", "078-05-1120"));
+        assert!(!is_allowlisted("SSN", "078-05-1121"));
+        assert!(is_allowlisted("EMAIL", "Jane.Doe@mail.Example.com"));
+        assert!(!is_allowlisted("EMAIL", "jane@notexample.com"));
+        assert!(is_allowlisted("HOSTNAME", "api.staging.test."));
+        assert!(is_allowlisted("IP_ADDRESS", "198.51.100.23"));
+        assert!(is_allowlisted("IP_ADDRESS", "2001:db8::1"));
+        assert!(!is_allowlisted("IP_ADDRESS", "198.51.101.1"));
+        // Lists are per entity type
+        assert!(!is_allowlisted("PHONE", "4111111111111111"));
+    }
+
+    #[test]
+    fn test_embedded_lists_parse() {
+        assert!(BUILTIN.cards.iter().all(|card| validators::luhn(card)));
+        assert!(BUILTIN.ssns.iter().all(|ssn| ssn.len() == 9));
+        assert_eq!(BUILTIN.networks.len(), 4);
+    }
+}
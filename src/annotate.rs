@@ -0,0 +1,61 @@
+//! Inline annotation: the original text with entity markers inserted
+//!
+//! `My SSN is ⟦SSN⟧123-45-6789⟦/SSN⟧`. Markers are templates in which
+//! `{entity}` is replaced by the span's entity type.
+
+use crate::overlap::{self, Span};
+
+/// Default opening marker
+pub const DEFAULT_START_MARKER: &str = "\u{27e6}{entity}\u{27e7}";
+/// Default closing marker
+pub const DEFAULT_END_MARKER: &str = "\u{27e6}/{entity}\u{27e7}";
+
+/// Insert markers around spans of `text`
+///
+/// Spans must be valid for `text` (see `overlap::in_bounds`); overlapping
+/// spans are reduced with `overlap::select_non_overlapping`.
+pub fn annotate(text: &str, spans: Vec<Span>, start_marker: &str, end_marker: &str) -> String {
+    let spans = overlap::select_non_overlapping(spans);
+    let mut out = String::with_capacity(text.len() + spans.len() * (start_marker.len() + end_marker.len() + 16));
+    let mut last = 0;
+    for span in spans {
+        out.push_str(&text[last..span.start]);
+        out.push_str(&start_marker.replace("{entity}", &span.label));
+        out.push_str(&text[span.start..span.end]);
+        out.push_str(&end_marker.replace("{entity}", &span.label));
+        last = span.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize, label: &str) -> Span {
+        Span { start, end, label: label.to_string() }
+    }
+
+    #[test]
+    fn test_default_markers() {
+        let text = "SSN 123-45-6789 ok";
+        let out = annotate(text, vec![span(4, 15, "SSN")], DEFAULT_START_MARKER, DEFAULT_END_MARKER);
+        assert_eq!(out, "SSN \u{27e6}SSN\u{27e7}123-45-6789\u{27e6}/SSN\u{27e7} ok");
+    }
+
+    #[test]
+    fn test_multibyte_text_and_overlaps() {
+        let text = "Zoë → zoe@example.com, José";
+        let email = text.find("zoe@").unwrap();
+        let jose = text.find("José").unwrap();
+        let spans = vec![
+            span(jose, text.len(), "NAME"),
+            span(email, email + 15, "EMAIL"),
+            span(email, email + 3, "NAME"),
+            span(0, 4, "NAME"),
+        ];
+        let out = annotate(text, spans, "[{entity}:", "]");
+        assert_eq!(out, "[NAME:Zoë] → [EMAIL:zoe@example.com], [NAME:José]");
+    }
+}
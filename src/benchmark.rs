@@ -0,0 +1,169 @@
+//! Built-in throughput benchmark
+//!
+//! "How fast will this be on our hardware" needs a fixed workload: a
+//! synthetic corpus (email, logs, CSV, JSON, source code, clinical notes,
+//! prose) and a typical PII pattern set, both embedded in the binary. The
+//! corpus is repeated to the requested size and scanned single-threaded
+//! with growing prefixes of the pattern set, so results show both raw
+//! throughput and how it falls off as patterns are added.
+
+use pyo3::prelude::*;
+use std::time::Instant;
+
+use crate::matcher::{compile_patterns, find_matches_impl};
+
+/// The embedded benchmark corpus (synthetic; every identifier is fictitious)
+pub const CORPUS: &str = include_str!("data/benchmark_corpus.txt");
+
+/// Pattern counts measured when the caller does not choose
+pub const DEFAULT_PATTERN_COUNTS: &[usize] = &[1, 8, 16, 32];
+
+/// (pattern, entity type, confidence, group), most common entity types first
+pub const PATTERNS: &[(&str, &str, f32, usize)] = &[
+    (r"\b\d{3}-\d{2}-\d{4}\b", "SSN", 0.9, 0),
+    (r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b", "EMAIL", 0.95, 0),
+    (r"(?:\+1[ .-]?)?\(?\b\d{3}\)?[ .-]?\d{3}[ .-]\d{4}\b", "PHONE", 0.7, 0),
+    (r"\b(?:\d{4}[ -]?){3}\d{4}\b", "CREDIT_CARD", 0.8, 0),
+    (r"\b3[47]\d{2}[ -]?\d{6}[ -]?\d{5}\b", "CREDIT_CARD", 0.8, 0),
+    (r"\b(?:\d{1,3}\.){3}\d{1,3}\b", "IP_ADDRESS", 0.6, 0),
+    (r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,4})?\b", "IBAN", 0.7, 0),
+    (r"\b(?:0[1-9]|1[0-2])/(?:0[1-9]|[12]\d|3[01])/(?:19|20)\d{2}\b", "DATE", 0.5, 0),
+    (r"\b(?:19|20)\d{2}-(?:0[1-9]|1[0-2])-(?:0[1-9]|[12]\d|3[01])\b", "DATE", 0.5, 0),
+    (r"(?i)\bMRN:?\s*(\d{6,10})\b", "MRN", 0.85, 1),
+    (r"\bAKIA[0-9A-Z]{16}\b", "AWS_ACCESS_KEY", 0.95, 0),
+    (r"\beyJ[A-Za-z0-9_-]+\.eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+", "JWT", 0.9, 0),
+    (r"\b[a-z][a-z0-9+.-]*://[^:/@\s]+:[^@\s]+@[^\s]+", "URL_CREDENTIALS", 0.9, 0),
+    (r"\b\d{5}(?:-\d{4})?\b", "ZIP_CODE", 0.3, 0),
+    (r"\b[0-9A-HJ-NPR-Z]{17}\b", "VIN", 0.6, 0),
+    (r"\b[2-9]\d{3} ?\d{4} ?\d{4}\b", "AADHAAR", 0.6, 0),
+    (r"\b[A-Z]{5}\d{4}[A-Z]\b", "PAN_IN", 0.7, 0),
+    (r"\b\d[A-Z][A-Z0-9]\d-?[A-Z][A-Z0-9]\d-?[A-Z]{2}\d{2}\b", "MBI", 0.8, 0),
+    (r"(?i)\bNPI:?\s*(\d{10})\b", "NPI", 0.8, 1),
+    (r"\b[A-Z]\d{8}\b", "PASSPORT", 0.4, 0),
+    (r"(?i)\b(?:routing|aba)\s*(?:number|no\.?)?\s*:?\s*(\d{9})\b", "ROUTING_NUMBER", 0.8, 1),
+    (r"(?i)\baccount\s*(?:number|no\.?)?\s*:?\s*(\d{8,17})\b", "ACCOUNT_NUMBER", 0.7, 1),
+    (r"\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b", "UUID", 0.3, 0),
+    (r"(?i)\b(?:[0-9a-f]{1,4}:){2,7}:?[0-9a-f]{0,4}(?::[0-9a-f]{1,4}){0,5}\b", "IPV6_ADDRESS", 0.5, 0),
+    (r"\b(?:[a-z0-9-]+\.)+(?:com|org|net|io|internal)(?::\d{2,5})\b", "HOST_PORT", 0.6, 0),
+    (r"\b\d{1,5} [A-Z][a-z]+(?: [A-Z][a-z]+)* (?:St|Ave|Rd|Blvd|Dr|Ln)\b\.?", "ADDRESS", 0.6, 0),
+    (r"\b(?:Dr|Mr|Mrs|Ms)\. [A-Z]\.? ?[A-Z][a-z]+\b", "NAME", 0.5, 0),
+    (r"(?i)\bpolicy\s+([A-Z]{2}\d{8})\b", "INSURANCE_ID", 0.7, 1),
+    (r"(?i)\b(?:password|passwd|pwd)\s*[:=]\s*\S+", "PASSWORD", 0.8, 0),
+    (r"\bcus_[A-Za-z0-9]{12,}\b", "STRIPE_CUSTOMER", 0.6, 0),
+    (r"\bch_[A-Za-z0-9]{20,}\b", "STRIPE_CHARGE", 0.6, 0),
+    (r"\b[A-Z][0-9][A-Z]? ?[0-9][A-Z]{2}\b|\b[A-Z]{2}[0-9]{1,2} ?[0-9][A-Z]{2}\b", "POSTCODE_UK", 0.4, 0),
+];
+
+/// Throughput for one pattern count
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct BenchmarkResult {
+    /// Patterns compiled into the matcher
+    #[pyo3(get)]
+    pub pattern_count: usize,
+    /// Bytes scanned per round
+    #[pyo3(get)]
+    pub bytes: usize,
+    /// Wall time of the fastest round
+    #[pyo3(get)]
+    pub seconds: f64,
+    /// Throughput of the fastest round in MB/s (10^6 bytes)
+    #[pyo3(get)]
+    pub mb_per_s: f64,
+    /// Matches found per round
+    #[pyo3(get)]
+    pub matches: usize,
+}
+
+#[pymethods]
+impl BenchmarkResult {
+    fn __repr__(&self) -> String {
+        format!("BenchmarkResult(patterns={}, mb_per_s={:.1}, matches={})", self.pattern_count, self.mb_per_s, self.matches)
+    }
+}
+
+/// The corpus repeated (whole lines only) to at least `bytes` bytes
+pub fn corpus_text(bytes: usize) -> String {
+    let mut text = String::with_capacity(bytes + CORPUS.len());
+    while text.len() < bytes.max(1) {
+        text.push_str(CORPUS);
+    }
+    text
+}
+
+/// Check benchmark arguments, returning the pattern counts to run
+pub fn check_args(pattern_counts: Option<Vec<usize>>, megabytes: f64, rounds: usize) -> Result<Vec<usize>, String> {
+    if !(megabytes.is_finite() && megabytes > 0.0) {
+        return Err(format!("megabytes must be a positive number, got {}", megabytes));
+    }
+    if rounds == 0 {
+        return Err("rounds must be at least 1".to_string());
+    }
+    let counts = pattern_counts.unwrap_or_else(|| DEFAULT_PATTERN_COUNTS.to_vec());
+    if let Some(bad) = counts.iter().find(|&&n| n == 0 || n > PATTERNS.len()) {
+        return Err(format!("pattern count {} out of range: expected 1 to {}", bad, PATTERNS.len()));
+    }
+    Ok(counts)
+}
+
+/// Time `rounds` scans of `text` with the first `count` patterns
+///
+/// The matcher is warmed on the corpus first so the timed rounds measure
+/// steady-state scanning, not lazy DFA construction.
+pub fn run(count: usize, text: &str, rounds: usize, two_pass: bool) -> BenchmarkResult {
+    let patterns: Vec<(String, String, f32, usize)> = PATTERNS[..count]
+        .iter()
+        .map(|&(pattern, entity_type, confidence, group)| (pattern.to_string(), entity_type.to_string(), confidence, group))
+        .collect();
+    let compiled = compile_patterns(&patterns, two_pass);
+    compiled.warm(CORPUS);
+
+    let mut best = f64::INFINITY;
+    let mut matches = 0;
+    for _ in 0..rounds {
+        let started = Instant::now();
+        matches = find_matches_impl(&compiled, text).len();
+        best = best.min(started.elapsed().as_secs_f64());
+    }
+    BenchmarkResult {
+        pattern_count: count,
+        bytes: text.len(),
+        seconds: best,
+        mb_per_s: text.len() as f64 / 1e6 / best.max(f64::MIN_POSITIVE),
+        matches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_patterns_compile() {
+        for (pattern, _, _, group) in PATTERNS {
+            let regex = regex_automata::meta::Regex::new(pattern).unwrap();
+            assert!(regex.group_info().group_len(regex_automata::PatternID::ZERO) > *group, "{}", pattern);
+        }
+        assert_eq!(DEFAULT_PATTERN_COUNTS.last(), Some(&PATTERNS.len()));
+    }
+
+    #[test]
+    #[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+    fn test_run_reports_matches() {
+        let text = corpus_text(10_000);
+        assert!(text.len() >= 10_000 && text.len() % CORPUS.len() == 0);
+        let one = run(1, &text, 1, false);
+        let all = run(PATTERNS.len(), &text, 1, true);
+        assert_eq!(one.matches, 2 * text.len() / CORPUS.len());
+        assert!(all.matches > one.matches && all.mb_per_s > 0.0);
+    }
+
+    #[test]
+    fn test_check_args() {
+        assert_eq!(check_args(None, 1.0, 1).unwrap(), DEFAULT_PATTERN_COUNTS);
+        assert!(check_args(Some(vec![0]), 1.0, 1).is_err());
+        assert!(check_args(Some(vec![PATTERNS.len() + 1]), 1.0, 1).is_err());
+        assert!(check_args(None, 0.0, 1).is_err());
+        assert!(check_args(None, 1.0, 0).is_err());
+    }
+}
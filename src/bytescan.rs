@@ -0,0 +1,277 @@
+//! Byte-oriented scanning with explicit invalid UTF-8 handling
+//!
+//! Raw bytes from mixed-encoding sources (log files, exports) are decoded
+//! without failing on bad sequences. Invalid runs are recorded by byte
+//! position and the scan continues:
+//!
+//! - lossy: invalid runs become U+FFFD and matches may span them
+//! - strict: each valid run is scanned on its own; no match crosses an
+//!   invalid sequence
+//!
+//! In both modes match offsets refer to the original byte buffer.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::matcher::{find_match_spans, find_matches_impl, CompiledPatterns, RawMatch};
+
+/// How invalid UTF-8 sequences are treated during a byte scan
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Utf8Mode {
+    Lossy,
+    Strict,
+}
+
+impl Utf8Mode {
+    pub fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "lossy" => Ok(Self::Lossy),
+            "strict" => Ok(Self::Strict),
+            other => Err(PyValueError::new_err(format!(
+                "unknown UTF-8 mode '{}', expected 'lossy' or 'strict'",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lossy => "lossy",
+            Self::Strict => "strict",
+        }
+    }
+}
+
+/// Result of scanning a byte buffer
+#[pyclass]
+#[derive(Clone)]
+pub struct ByteScanResult {
+    /// Matches with byte offsets into the original buffer
+    #[pyo3(get)]
+    pub matches: Vec<RawMatch>,
+    /// (start, end) byte ranges of invalid UTF-8 sequences
+    #[pyo3(get)]
+    pub invalid_spans: Vec<(usize, usize)>,
+    /// Mode used for the scan ("lossy" or "strict")
+    #[pyo3(get)]
+    pub mode: String,
+}
+
+#[pymethods]
+impl ByteScanResult {
+    /// True if the buffer was entirely valid UTF-8
+    #[getter]
+    fn is_valid_utf8(&self) -> bool {
+        self.invalid_spans.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ByteScanResult(matches={}, invalid_spans={}, mode={})",
+            self.matches.len(),
+            self.invalid_spans.len(),
+            self.mode
+        )
+    }
+}
+
+/// One piece of the decoded text and where it came from
+struct Piece {
+    decoded_start: usize,
+    orig_start: usize,
+    orig_len: usize,
+    replacement: bool,
+}
+
+/// Lossily decoded buffer with an offset map back to the original bytes
+pub struct DecodedBytes {
+    pub text: String,
+    pieces: Vec<Piece>,
+    orig_len: usize,
+}
+
+impl DecodedBytes {
+    /// Decode bytes, replacing each invalid sequence with U+FFFD
+    pub fn decode(data: &[u8]) -> Self {
+        let mut text = String::with_capacity(data.len());
+        let mut pieces = Vec::new();
+        let mut orig = 0;
+
+        for chunk in data.utf8_chunks() {
+            let valid = chunk.valid();
+            if !valid.is_empty() {
+                pieces.push(Piece {
+                    decoded_start: text.len(),
+                    orig_start: orig,
+                    orig_len: valid.len(),
+                    replacement: false,
+                });
+                text.push_str(valid);
+                orig += valid.len();
+            }
+            let invalid = chunk.invalid();
+            if !invalid.is_empty() {
+                pieces.push(Piece {
+                    decoded_start: text.len(),
+                    orig_start: orig,
+                    orig_len: invalid.len(),
+                    replacement: true,
+                });
+                text.push(char::REPLACEMENT_CHARACTER);
+                orig += invalid.len();
+            }
+        }
+
+        Self {
+            text,
+            pieces,
+            orig_len: data.len(),
+        }
+    }
+
+    /// Byte ranges of invalid sequences in the original buffer
+    pub fn invalid_spans(&self) -> Vec<(usize, usize)> {
+        self.pieces
+            .iter()
+            .filter(|p| p.replacement)
+            .map(|p| (p.orig_start, p.orig_start + p.orig_len))
+            .collect()
+    }
+
+    /// Map a decoded offset (on a char boundary) to an original byte offset
+    pub fn to_original(&self, offset: usize) -> usize {
+        if offset >= self.text.len() {
+            return self.orig_len;
+        }
+        let idx = self
+            .pieces
+            .partition_point(|p| p.decoded_start <= offset)
+            .saturating_sub(1);
+        let piece = &self.pieces[idx];
+        let delta = offset - piece.decoded_start;
+        if piece.replacement {
+            // Offsets only land on the replacement char's edges
+            if delta == 0 {
+                piece.orig_start
+            } else {
+                piece.orig_start + piece.orig_len
+            }
+        } else {
+            piece.orig_start + delta
+        }
+    }
+}
+
+/// Scan each valid UTF-8 run of `data` in place; returns (matches, invalid_spans)
+fn scan_valid_runs(compiled: &CompiledPatterns, data: &[u8]) -> (Vec<RawMatch>, Vec<(usize, usize)>) {
+    let mut matches = Vec::new();
+    let mut invalid_spans = Vec::new();
+    let mut orig = 0;
+    for chunk in data.utf8_chunks() {
+        let valid = chunk.valid();
+        for mut m in find_matches_impl(compiled, valid) {
+            m.start += orig;
+            m.end += orig;
+            matches.push(m);
+        }
+        orig += valid.len();
+        let invalid_len = chunk.invalid().len();
+        if invalid_len > 0 {
+            invalid_spans.push((orig, orig + invalid_len));
+            orig += invalid_len;
+        }
+    }
+    (matches, invalid_spans)
+}
+
+/// Byte ranges of all matches in `data`, scanned without a decoded copy
+///
+/// Each valid run is scanned where it lies, as in strict mode, so no
+/// match crosses an invalid sequence. No matched text is copied.
+pub fn match_spans(compiled: &CompiledPatterns, data: &[u8]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut orig = 0;
+    for chunk in data.utf8_chunks() {
+        let valid = chunk.valid();
+        spans.extend(find_match_spans(compiled, valid).into_iter().map(|(start, end)| (orig + start, orig + end)));
+        orig += valid.len() + chunk.invalid().len();
+    }
+    spans
+}
+
+/// Scan a byte buffer, tolerating invalid UTF-8 according to `mode`
+pub fn scan_bytes(compiled: &CompiledPatterns, data: &[u8], mode: Utf8Mode) -> ByteScanResult {
+    let (matches, invalid_spans) = match mode {
+        Utf8Mode::Lossy => match std::str::from_utf8(data) {
+            // Valid input needs no decoded copy
+            Ok(text) => (find_matches_impl(compiled, text), Vec::new()),
+            Err(_) => {
+                let decoded = DecodedBytes::decode(data);
+                let mut matches = find_matches_impl(compiled, &decoded.text);
+                for m in matches.iter_mut() {
+                    m.start = decoded.to_original(m.start);
+                    m.end = decoded.to_original(m.end);
+                }
+                (matches, decoded.invalid_spans())
+            }
+        },
+        Utf8Mode::Strict => scan_valid_runs(compiled, data),
+    };
+
+    ByteScanResult {
+        matches,
+        invalid_spans,
+        mode: mode.as_str().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::compile_patterns;
+
+    #[test]
+    fn test_decode_offsets() {
+        let data = b"ab\xffcd\xe2\x82xy";
+        let decoded = DecodedBytes::decode(data);
+        assert_eq!(decoded.text, "ab\u{FFFD}cd\u{FFFD}xy");
+        assert_eq!(decoded.invalid_spans(), vec![(2, 3), (5, 7)]);
+
+        // "cd" starts after a 3-byte replacement char
+        assert_eq!(decoded.to_original(5), 3);
+        // "xy" starts after the second (2 invalid bytes) replacement
+        assert_eq!(decoded.to_original(10), 7);
+        assert_eq!(decoded.to_original(decoded.text.len()), data.len());
+    }
+
+    #[test]
+    fn test_scan_bytes_modes() {
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.95, 0)];
+        let compiled = compile_patterns(&patterns, false);
+        let data = b"\xfe\xfeSSN 123-45-6789 ok \xff 987-65-4321";
+
+        let lossy = scan_bytes(&compiled, data, Utf8Mode::Lossy);
+        assert_eq!(lossy.invalid_spans, vec![(0, 1), (1, 2), (21, 22)]);
+        assert_eq!(lossy.matches.len(), 2);
+        assert_eq!(&data[lossy.matches[0].start..lossy.matches[0].end], b"123-45-6789");
+        assert_eq!(&data[lossy.matches[1].start..lossy.matches[1].end], b"987-65-4321");
+
+        let strict = scan_bytes(&compiled, data, Utf8Mode::Strict);
+        assert_eq!(strict.invalid_spans, lossy.invalid_spans);
+        let spans = |r: &ByteScanResult| r.matches.iter().map(|m| (m.start, m.end)).collect::<Vec<_>>();
+        assert_eq!(spans(&strict), spans(&lossy));
+        assert_eq!(match_spans(&compiled, data), spans(&strict));
+    }
+
+    #[test]
+    fn test_strict_does_not_bridge_invalid_bytes() {
+        let patterns = vec![(r"123.+6789".to_string(), "ID".to_string(), 0.5, 0)];
+        let compiled = compile_patterns(&patterns, false);
+        let data = b"123-45\xff-6789";
+
+        assert!(scan_bytes(&compiled, data, Utf8Mode::Strict).matches.is_empty());
+        assert!(match_spans(&compiled, data).is_empty());
+        let lossy = scan_bytes(&compiled, data, Utf8Mode::Lossy);
+        assert_eq!((lossy.matches[0].start, lossy.matches[0].end), (0, data.len()));
+    }
+}
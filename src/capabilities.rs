@@ -0,0 +1,64 @@
+//! Optional-feature registry
+//!
+//! Wheels for different platforms are not built identically, so Python
+//! asks the extension what it has instead of probing imports and catching
+//! failures. Every known capability is always listed, enabled or not, so
+//! a missing key never has to be interpreted.
+
+use std::collections::BTreeMap;
+
+/// Vector instruction set the byte searchers use on this CPU
+///
+/// memchr and aho-corasick pick it at runtime: AVX2 where the CPU has it,
+/// else the SSE2 baseline on x86_64; NEON on aarch64.
+pub fn simd_tier() -> &'static str {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            "avx2"
+        } else {
+            "sse2"
+        }
+    }
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        "neon"
+    }
+    #[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+    {
+        "none"
+    }
+}
+
+/// Capability name -> available in this build
+pub fn capabilities() -> BTreeMap<&'static str, bool> {
+    let mut caps = BTreeMap::new();
+    // Document format support, enabled by the Cargo features of the same name
+    caps.insert("archive", cfg!(feature = "archive"));
+    caps.insert("parquet", cfg!(feature = "parquet"));
+    caps.insert("pdf", cfg!(feature = "pdf"));
+    // Shared, memory-mapped dictionary files (DictionaryMatcher.open)
+    caps.insert("mmap", cfg!(unix));
+    // Rayon batch scanning (find_matches_batch)
+    caps.insert("parallel", true);
+    // Vectorized byte searching; simd_tier() tells which instructions
+    caps.insert("simd", cfg!(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))));
+    caps.insert("two_pass", true);
+    caps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_capability_reported() {
+        let caps = capabilities();
+        let names: Vec<&str> = caps.keys().copied().collect();
+        assert_eq!(names, ["archive", "mmap", "parallel", "parquet", "pdf", "simd", "two_pass"]);
+        assert!(caps["parallel"]);
+        assert_eq!(caps["mmap"], cfg!(unix));
+        assert_eq!(caps["pdf"], cfg!(feature = "pdf"));
+        assert_eq!(caps["simd"], simd_tier() != "none");
+    }
+}
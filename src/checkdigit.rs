@@ -0,0 +1,184 @@
+//! Check-digit computation and verification
+//!
+//! Validators only answer "is this valid?". Generating synthetic values
+//! and verifying partial matches both need the other direction: given the
+//! payload, what is the check digit? Covers Luhn (cards, IMEI), ISO 7064
+//! MOD 97-10 (IBAN and similar) and Verhoeff (Aadhaar).
+//!
+//! Separators (spaces, hyphens) in inputs are ignored; any other
+//! non-digit (or, for MOD 97, non-alphanumeric) character makes the input
+//! invalid and the computation returns None.
+
+/// Digits of `s` with spaces and hyphens skipped; None on any other character
+fn digits(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'0'..=b'9' => out.push(b - b'0'),
+            b' ' | b'-' => {}
+            _ => return None,
+        }
+    }
+    (!out.is_empty()).then_some(out)
+}
+
+/// Luhn check digit to append to `partial`
+pub fn luhn_digit(partial: &str) -> Option<char> {
+    let digits = digits(partial)?;
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            // The appended digit takes position 0, so doubling starts here
+            let d = d as u32;
+            if i % 2 == 0 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                d
+            }
+        })
+        .sum();
+    char::from_digit((10 - sum % 10) % 10, 10)
+}
+
+/// Remainder mod 97 of an alphanumeric string (A=10 ... Z=35)
+fn mod97(s: &str) -> Option<u32> {
+    let mut rem = 0u32;
+    let mut any = false;
+    for c in s.chars() {
+        let value = match c {
+            ' ' | '-' => continue,
+            '0'..='9' => c as u32 - '0' as u32,
+            'A'..='Z' => c as u32 - 'A' as u32 + 10,
+            'a'..='z' => c as u32 - 'a' as u32 + 10,
+            _ => return None,
+        };
+        rem = if value >= 10 { (rem * 100 + value) % 97 } else { (rem * 10 + value) % 97 };
+        any = true;
+    }
+    any.then_some(rem)
+}
+
+/// Two ISO 7064 MOD 97-10 check digits to append to `partial`
+pub fn mod97_digits(partial: &str) -> Option<String> {
+    let rem = mod97(&format!("{}00", partial))?;
+    Some(format!("{:02}", 98 - rem))
+}
+
+/// Whether `value` (payload followed by its check digits) satisfies MOD 97-10
+pub fn mod97_valid(value: &str) -> bool {
+    mod97(value) == Some(1)
+}
+
+/// IBAN check digits for a country code and BBAN
+pub fn iban_check_digits(country: &str, bban: &str) -> Option<String> {
+    if country.len() != 2 || !country.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    let rem = mod97(&format!("{}{}00", bban, country))?;
+    Some(format!("{:02}", 98 - rem))
+}
+
+/// Validate an IBAN ("GB82 WEST 1234 5698 7654 32")
+pub fn iban_valid(iban: &str) -> bool {
+    let compact: String = iban.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.len() < 15 || compact.len() > 34 || !compact.is_ascii() {
+        return false;
+    }
+    let (head, bban) = compact.split_at(4);
+    let head = head.as_bytes();
+    if !head[..2].iter().all(|b| b.is_ascii_alphabetic()) || !head[2..].iter().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    mod97_valid(&format!("{}{}", bban, &compact[..4]))
+}
+
+const VERHOEFF_D: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+const VERHOEFF_P: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+const VERHOEFF_INV: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// Verhoeff checksum over digits, with positions shifted by `offset`
+fn verhoeff(digits: &[u8], offset: usize) -> u8 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .fold(0, |c, (i, &d)| VERHOEFF_D[c as usize][VERHOEFF_P[(i + offset) % 8][d as usize] as usize])
+}
+
+/// Verhoeff check digit to append to `partial`
+pub fn verhoeff_digit(partial: &str) -> Option<char> {
+    let digits = digits(partial)?;
+    char::from_digit(VERHOEFF_INV[verhoeff(&digits, 1) as usize] as u32, 10)
+}
+
+/// Whether `number` (including its check digit) passes Verhoeff
+pub fn verhoeff_valid(number: &str) -> bool {
+    digits(number).is_some_and(|d| d.len() > 1 && verhoeff(&d, 0) == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators;
+
+    #[test]
+    fn test_luhn_digit() {
+        assert_eq!(luhn_digit("411111111111111"), Some('1'));
+        assert_eq!(luhn_digit("7992739871"), Some('3'));
+        assert_eq!(luhn_digit("4111-1111-1111-111"), Some('1'));
+        assert_eq!(luhn_digit("41x1"), None);
+        assert_eq!(luhn_digit(""), None);
+        // Appending the digit always yields a Luhn-valid number
+        for partial in ["550000000000000", "37828224631000", "601100009013942"] {
+            let full = format!("{}{}", partial, luhn_digit(partial).unwrap());
+            assert!(validators::luhn(&full), "{}", full);
+        }
+    }
+
+    #[test]
+    fn test_mod97_and_iban() {
+        assert_eq!(iban_check_digits("GB", "WEST12345698765432").as_deref(), Some("82"));
+        assert!(iban_valid("GB82 WEST 1234 5698 7654 32"));
+        assert!(iban_valid("DE89370400440532013000"));
+        assert!(!iban_valid("GB83WEST12345698765432"));
+
+        let digits = mod97_digits("123456789").unwrap();
+        assert!(mod97_valid(&format!("123456789{}", digits)));
+        assert_eq!(mod97_digits("12!"), None);
+    }
+
+    #[test]
+    fn test_verhoeff() {
+        assert_eq!(verhoeff_digit("236"), Some('3'));
+        assert!(verhoeff_valid("2363"));
+        assert!(!verhoeff_valid("2364"));
+        // Aadhaar test number
+        assert_eq!(verhoeff_digit("49911866524"), Some('6'));
+        assert!(verhoeff_valid("4991 1866 5246"));
+    }
+}
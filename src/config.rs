@@ -0,0 +1,283 @@
+//! Canonical export of a matcher's effective configuration
+//!
+//! Auditors need to know exactly which rules ran: the patterns that
+//! compiled (after pack merging and filtering on the Python side), how each
+//! one is executed, and every option that changes results. The export is
+//! a plain value tree rendered as JSON or TOML with sorted keys and fixed
+//! number formatting, so the same configuration always produces the same
+//! bytes and can be hashed or diffed.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+
+/// Version of the export layout, bumped when fields change meaning
+pub const FORMAT_VERSION: i64 = 1;
+
+/// A configuration value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    /// Float as shortest round-trip text, always with a decimal point
+    Float(String),
+    Str(String),
+    List(Vec<Value>),
+    Table(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Float value; non-finite numbers (not representable in JSON) become strings
+    pub fn float(x: impl Display) -> Self {
+        let text = x.to_string();
+        if text.contains(|c: char| c.is_ascii_alphabetic()) {
+            return Value::Str(text);
+        }
+        if text.contains('.') {
+            Value::Float(text)
+        } else {
+            Value::Float(text + ".0")
+        }
+    }
+
+    pub fn strings<S: AsRef<str>>(items: &[S]) -> Self {
+        Value::List(items.iter().map(|s| Value::Str(s.as_ref().to_string())).collect())
+    }
+
+    fn is_table(&self) -> bool {
+        matches!(self, Value::Table(_))
+    }
+
+    fn is_table_list(&self) -> bool {
+        matches!(self, Value::List(items) if !items.is_empty() && items.iter().all(Value::is_table))
+    }
+}
+
+/// Export formats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "toml" => Some(Format::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Render a top-level table in the given format
+pub fn render(root: &BTreeMap<String, Value>, format: Format) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Json => {
+            write_json_table(&mut out, root, 0);
+            out.push('\n');
+        }
+        Format::Toml => write_toml_table(&mut out, root, ""),
+    }
+    out
+}
+
+/// Double-quoted string valid in both JSON and TOML
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() && (c as u32) < 0x80 => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Scalars and lists of scalars, identical in JSON and TOML
+fn write_inline(out: &mut String, value: &Value) {
+    match value {
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Int(n) => {
+            let _ = write!(out, "{}", n);
+        }
+        Value::Float(text) => out.push_str(text),
+        Value::Str(s) => write_string(out, s),
+        Value::List(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_inline(out, item);
+            }
+            out.push(']');
+        }
+        Value::Table(table) => {
+            out.push('{');
+            for (i, (key, item)) in table.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_string(out, key);
+                out.push_str(": ");
+                write_inline(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_json(out: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Table(table) => write_json_table(out, table, depth),
+        Value::List(items) if value.is_table_list() => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                indent(out, depth + 1);
+                write_json(out, item, depth + 1);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            indent(out, depth);
+            out.push(']');
+        }
+        _ => write_inline(out, value),
+    }
+}
+
+fn write_json_table(out: &mut String, table: &BTreeMap<String, Value>, depth: usize) {
+    if table.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    out.push_str("{\n");
+    for (i, (key, value)) in table.iter().enumerate() {
+        indent(out, depth + 1);
+        write_string(out, key);
+        out.push_str(": ");
+        write_json(out, value, depth + 1);
+        out.push_str(if i + 1 < table.len() { ",\n" } else { "\n" });
+    }
+    indent(out, depth);
+    out.push('}');
+}
+
+fn indent(out: &mut String, depth: usize) {
+    out.extend(std::iter::repeat_n(' ', depth * 2));
+}
+
+/// Bare key when possible, quoted otherwise
+fn write_toml_key(out: &mut String, key: &str) {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        out.push_str(key);
+    } else {
+        write_string(out, key);
+    }
+}
+
+fn toml_path(prefix: &str, key: &str) -> String {
+    let mut path = String::from(prefix);
+    if !path.is_empty() {
+        path.push('.');
+    }
+    write_toml_key(&mut path, key);
+    path
+}
+
+/// Key/value lines first, then [sub.tables], then [[arrays.of.tables]]
+fn write_toml_table(out: &mut String, table: &BTreeMap<String, Value>, prefix: &str) {
+    for (key, value) in table {
+        if !value.is_table() && !value.is_table_list() {
+            write_toml_key(out, key);
+            out.push_str(" = ");
+            write_inline(out, value);
+            out.push('\n');
+        }
+    }
+    for (key, value) in table {
+        if let Value::Table(sub) = value {
+            let path = toml_path(prefix, key);
+            let _ = write!(out, "\n[{}]\n", path);
+            write_toml_table(out, sub, &path);
+        }
+    }
+    for (key, value) in table {
+        if let (true, Value::List(items)) = (value.is_table_list(), value) {
+            let path = toml_path(prefix, key);
+            for item in items {
+                if let Value::Table(sub) = item {
+                    let _ = write!(out, "\n[[{}]]\n", path);
+                    write_toml_table(out, sub, &path);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> BTreeMap<String, Value> {
+        let mut pattern = BTreeMap::new();
+        pattern.insert("regex".to_string(), Value::Str(r#"\d{3}"x"#.to_string()));
+        pattern.insert("confidence".to_string(), Value::float(0.95f32));
+        let mut post = BTreeMap::new();
+        post.insert("MY TYPE".to_string(), Value::strings(&["upper"]));
+
+        let mut root = BTreeMap::new();
+        root.insert("two_pass".to_string(), Value::Bool(true));
+        root.insert("patterns".to_string(), Value::List(vec![Value::Table(pattern)]));
+        root.insert("postprocess".to_string(), Value::Table(post));
+        root.insert("failed".to_string(), Value::List(vec![Value::Int(2)]));
+        root
+    }
+
+    #[test]
+    fn test_float_text() {
+        assert_eq!(Value::float(0.95f32), Value::Float("0.95".to_string()));
+        assert_eq!(Value::float(1.0f64), Value::Float("1.0".to_string()));
+        assert_eq!(Value::float(f32::NAN), Value::Str("NaN".to_string()));
+    }
+
+    #[test]
+    fn test_json() {
+        let expected = r#"{
+  "failed": [2],
+  "patterns": [
+    {
+      "confidence": 0.95,
+      "regex": "\\d{3}\"x"
+    }
+  ],
+  "postprocess": {
+    "MY TYPE": ["upper"]
+  },
+  "two_pass": true
+}
+"#;
+        assert_eq!(render(&sample(), Format::Json), expected);
+    }
+
+    #[test]
+    fn test_toml() {
+        let expected = r#"failed = [2]
+two_pass = true
+
+[postprocess]
+"MY TYPE" = ["upper"]
+
+[[patterns]]
+confidence = 0.95
+regex = "\\d{3}\"x"
+"#;
+        assert_eq!(render(&sample(), Format::Toml), expected);
+    }
+}
@@ -0,0 +1,145 @@
+//! Paged retrieval of scan results
+//!
+//! A scan that produces millions of matches can't afford one Python object
+//! per match at once. `ScanCursor` keeps the matches as native structs and
+//! hands them to Python a page at a time; pages already taken are released,
+//! so peak Python-side memory is bounded by the page size.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::matcher::RawMatch;
+
+/// Natively held scan results, pulled in pages
+#[pyclass]
+pub struct ScanCursor {
+    pending: VecDeque<RawMatch>,
+    /// Default number of matches per page
+    #[pyo3(get)]
+    page_size: usize,
+    /// Total matches found by the scan
+    #[pyo3(get)]
+    total: usize,
+    context: Option<Arc<PyObject>>,
+}
+
+impl ScanCursor {
+    pub fn new(matches: Vec<RawMatch>, page_size: usize, context: Option<PyObject>) -> PyResult<Self> {
+        if page_size == 0 {
+            return Err(PyValueError::new_err("page_size must be at least 1"));
+        }
+        Ok(Self {
+            total: matches.len(),
+            pending: matches.into(),
+            page_size,
+            context: context.map(Arc::new),
+        })
+    }
+
+    /// Take up to `n` matches off the front, attaching the scan context
+    fn take(&mut self, n: usize) -> Vec<RawMatch> {
+        let n = n.min(self.pending.len());
+        let mut page: Vec<RawMatch> = self.pending.drain(..n).collect();
+        if let Some(ctx) = &self.context {
+            for m in page.iter_mut() {
+                m.context = Some(Arc::clone(ctx));
+            }
+        }
+        // Give memory back once most of the buffer has been consumed
+        if self.pending.capacity() > 4 * self.pending.len().max(self.page_size) {
+            self.pending.shrink_to_fit();
+        }
+        page
+    }
+}
+
+#[pymethods]
+impl ScanCursor {
+    /// Matches not yet retrieved
+    #[getter]
+    fn remaining(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of matches already retrieved
+    #[getter]
+    fn position(&self) -> usize {
+        self.total - self.pending.len()
+    }
+
+    /// Next page of matches (empty once exhausted)
+    ///
+    /// Args:
+    ///     size: Matches to return (defaults to the cursor's page_size)
+    #[pyo3(signature = (size=None))]
+    fn next_page(&mut self, size: Option<usize>) -> Vec<RawMatch> {
+        let n = size.unwrap_or(self.page_size);
+        self.take(n)
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Iterate pages of page_size matches
+    fn __next__(&mut self) -> Option<Vec<RawMatch>> {
+        if self.pending.is_empty() {
+            None
+        } else {
+            Some(self.take(self.page_size))
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ScanCursor(total={}, remaining={}, page_size={})",
+            self.total,
+            self.pending.len(),
+            self.page_size
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(n: usize) -> Vec<RawMatch> {
+        (0..n)
+            .map(|i| RawMatch {
+                pattern_id: 0,
+                start: i,
+                end: i + 1,
+                text: "x".to_string(),
+                entity_type: "X".to_string(),
+                confidence: 0.5,
+                truncated: false,
+                context: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pages_cover_all_matches_in_order() {
+        let mut cursor = ScanCursor::new(matches(10), 4, None).unwrap();
+        let sizes: Vec<usize> = std::iter::from_fn(|| cursor.__next__()).map(|p| p.len()).collect();
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert_eq!(cursor.position(), 10);
+
+        let mut cursor = ScanCursor::new(matches(5), 4, None).unwrap();
+        assert_eq!(cursor.next_page(Some(3))[2].start, 2);
+        assert_eq!(cursor.next_page(None)[0].start, 3);
+        assert!(cursor.next_page(None).is_empty());
+    }
+
+    #[test]
+    fn test_zero_page_size_rejected() {
+        assert!(ScanCursor::new(matches(1), 0, None).is_err());
+    }
+}
@@ -0,0 +1,339 @@
+//! Step-by-step trace of one pattern against one text
+//!
+//! Pattern authors need to see more than the final matches: whether the
+//! pattern compiled (and to which engine), which literals would trigger
+//! it in a two-pass scan, every span the engine matched, and why a match
+//! did not become a finding (capture group absent, span reduced to
+//! formatting characters, validator failure, score below the cut).
+//! `match_debug` runs the same steps as a scan plus detect() and records
+//! each decision instead of silently dropping candidates.
+
+use pyo3::prelude::*;
+use regex_automata::meta;
+use std::fmt::Write;
+
+use crate::finding::{validators_for, Finding};
+use crate::grapheme;
+use crate::literal::LiteralAlternation;
+use crate::matcher::RawMatch;
+use crate::twopass::prefix_literals;
+
+/// One recorded decision
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct TraceStep {
+    /// "compile", "prefilter", "match", "span", "validate", "score" or "limit"
+    #[pyo3(get)]
+    pub stage: &'static str,
+    /// Byte span the step is about (None for whole-pattern steps)
+    #[pyo3(get)]
+    pub start: Option<usize>,
+    #[pyo3(get)]
+    pub end: Option<usize>,
+    /// False when this step rejected the candidate (or the pattern)
+    #[pyo3(get)]
+    pub accepted: bool,
+    /// Human-readable explanation
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl TraceStep {
+    fn __repr__(&self) -> String {
+        format!("TraceStep({}, {}, '{}')", self.stage, if self.accepted { "ok" } else { "rejected" }, self.detail)
+    }
+}
+
+/// Result of match_debug()
+#[pyclass]
+pub struct MatchTrace {
+    #[pyo3(get)]
+    pub pattern: String,
+    #[pyo3(get)]
+    pub entity_type: String,
+    /// "regex", "literal" (Aho-Corasick) or "invalid"
+    #[pyo3(get)]
+    pub engine: &'static str,
+    /// Two-pass trigger literals (None if the pattern would be swept)
+    #[pyo3(get)]
+    pub triggers: Option<Vec<String>>,
+    /// Every decision, in order
+    #[pyo3(get)]
+    pub steps: Vec<TraceStep>,
+    /// Findings that survived every step
+    #[pyo3(get)]
+    pub findings: Vec<Finding>,
+}
+
+#[pymethods]
+impl MatchTrace {
+    /// The trace as an aligned, one-step-per-line text report
+    fn report(&self) -> String {
+        let mut out = format!("pattern {:?} ({}, {})\n", self.pattern, self.entity_type, self.engine);
+        for step in &self.steps {
+            let span = match (step.start, step.end) {
+                (Some(s), Some(e)) => format!("{}:{}", s, e),
+                _ => String::new(),
+            };
+            let verdict = if step.accepted { "ok" } else { "REJECT" };
+            let _ = writeln!(out, "  {:<10}{:<8}{:<12}{}", step.stage, verdict, span, step.detail);
+        }
+        let _ = writeln!(out, "  {} finding(s)", self.findings.len());
+        out
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MatchTrace(engine={}, steps={}, findings={})",
+            self.engine,
+            self.steps.len(),
+            self.findings.len()
+        )
+    }
+}
+
+enum DebugEngine {
+    Regex(meta::Regex),
+    Literals(LiteralAlternation),
+}
+
+/// Byte span (start, end)
+type ByteSpan = (usize, usize);
+
+struct Tracer {
+    steps: Vec<TraceStep>,
+}
+
+impl Tracer {
+    fn step(&mut self, stage: &'static str, span: Option<ByteSpan>, accepted: bool, detail: String) {
+        self.steps.push(TraceStep {
+            stage,
+            start: span.map(|s| s.0),
+            end: span.map(|s| s.1),
+            accepted,
+            detail,
+        });
+    }
+}
+
+/// Display form of a trigger literal
+fn literal_text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Trace `pattern` over `text` as a one-pattern matcher followed by detect()
+///
+/// At most `limit` engine matches are traced; a final "limit" step notes
+/// when more were left unexamined.
+pub fn trace(
+    pattern: &str,
+    text: &str,
+    entity_type: &str,
+    group: usize,
+    confidence: f32,
+    min_score: f32,
+    limit: usize,
+) -> MatchTrace {
+    let mut tracer = Tracer { steps: Vec::new() };
+    let mut result = MatchTrace {
+        pattern: pattern.to_string(),
+        entity_type: entity_type.to_string(),
+        engine: "invalid",
+        triggers: None,
+        steps: Vec::new(),
+        findings: Vec::new(),
+    };
+
+    // Compile exactly as compile_patterns does
+    let engine = match LiteralAlternation::parse(pattern, group) {
+        Some(alt) => {
+            tracer.step("compile", None, true, format!("literal alternation of {} terms (Aho-Corasick)", alt.term_count()));
+            DebugEngine::Literals(alt)
+        }
+        None => match meta::Regex::new(pattern) {
+            Ok(regex) => {
+                let groups = regex.group_info().group_len(regex_automata::PatternID::ZERO) - 1;
+                if group > groups {
+                    tracer.step("compile", None, false, format!("group {} requested but the pattern has {} capture groups", group, groups));
+                    result.steps = tracer.steps;
+                    return result;
+                }
+                tracer.step("compile", None, true, format!("regex with {} capture groups, reporting group {}", groups, group));
+                DebugEngine::Regex(regex)
+            }
+            Err(e) => {
+                tracer.step("compile", None, false, e.to_string());
+                result.steps = tracer.steps;
+                return result;
+            }
+        },
+    };
+
+    match &engine {
+        DebugEngine::Literals(_) => {
+            result.engine = "literal";
+            tracer.step("prefilter", None, true, "literal alternations are always swept".to_string());
+        }
+        DebugEngine::Regex(_) => {
+            result.engine = "regex";
+            match prefix_literals(pattern) {
+                Some(lits) => {
+                    let shown: Vec<String> = lits.iter().take(8).map(|l| format!("{:?}", literal_text(l))).collect();
+                    let more = if lits.len() > 8 { format!(" and {} more", lits.len() - 8) } else { String::new() };
+                    tracer.step("prefilter", None, true, format!("two-pass triggers: {}{}", shown.join(", "), more));
+                    result.triggers = Some(lits.iter().map(|l| literal_text(l)).collect());
+                }
+                None => tracer.step(
+                    "prefilter",
+                    None,
+                    true,
+                    "no finite literal prefix; a two-pass scan sweeps this pattern".to_string(),
+                ),
+            }
+        }
+    }
+
+    // (whole match, reported span) in text order
+    let mut spans: Vec<(ByteSpan, Option<ByteSpan>)> = Vec::new();
+    let mut truncated = false;
+    match &engine {
+        DebugEngine::Literals(alt) => alt.find_iter(text, 0, text.len(), |s, e| {
+            if spans.len() < limit {
+                spans.push(((s, e), Some((s, e))));
+            } else {
+                truncated = true;
+            }
+        }),
+        DebugEngine::Regex(regex) => {
+            for caps in regex.captures_iter(text) {
+                if spans.len() == limit {
+                    truncated = true;
+                    break;
+                }
+                let Some(whole) = caps.get_match() else {
+                    continue;
+                };
+                let reported = caps.get_group(group).map(|s| (s.start, s.end));
+                spans.push(((whole.start(), whole.end()), reported));
+            }
+        }
+    }
+    if spans.is_empty() {
+        tracer.step("match", None, false, "the engine found no match in the text".to_string());
+    }
+
+    for (whole, reported) in spans {
+        let quoted = format!("{:?}", &text[whole.0..whole.1]);
+        let Some((start, end)) = reported else {
+            tracer.step("match", Some(whole), false, format!("{} matched but group {} did not participate", quoted, group));
+            continue;
+        };
+        tracer.step("match", Some(whole), true, quoted);
+
+        let Some((adj_start, adj_end)) = grapheme::adjust_span(text, start, end) else {
+            tracer.step("span", Some((start, end)), false, "only formatting characters remain".to_string());
+            continue;
+        };
+        if (adj_start, adj_end) != (start, end) {
+            tracer.step(
+                "span",
+                Some((adj_start, adj_end)),
+                true,
+                format!("adjusted from {}:{} to whole grapheme clusters", start, end),
+            );
+        }
+        let matched = &text[adj_start..adj_end];
+        if matched.trim().is_empty() {
+            tracer.step("span", Some((adj_start, adj_end)), false, "empty or whitespace-only match".to_string());
+            continue;
+        }
+
+        let raw = RawMatch {
+            pattern_id: 0,
+            start: adj_start,
+            end: adj_end,
+            text: matched.to_string(),
+            entity_type: entity_type.to_string(),
+            confidence,
+            truncated: false,
+            context: None,
+        };
+        let finding = Finding::from_raw(raw);
+        let span = Some((adj_start, adj_end));
+        if validators_for(entity_type).is_empty() {
+            tracer.step("validate", span, true, format!("no native validators for {}", entity_type));
+        }
+        for (name, &passed) in &finding.verdicts {
+            tracer.step("validate", span, passed, format!("{} {}", name, if passed { "passed" } else { "failed" }));
+        }
+        let passed = finding.score >= min_score;
+        let relation = if passed { ">=" } else { "<" };
+        tracer.step(
+            "score",
+            span,
+            passed,
+            format!("{:.2} {} min_score {:.2} (normalized {:?})", finding.score, relation, min_score, finding.normalized),
+        );
+        if passed {
+            result.findings.push(finding);
+        }
+    }
+    if truncated {
+        tracer.step("limit", None, false, format!("stopped after {} matches", limit));
+    }
+    result.steps = tracer.steps;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stages(trace: &MatchTrace) -> Vec<(&str, bool)> {
+        trace.steps.iter().map(|s| (s.stage, s.accepted)).collect()
+    }
+
+    #[test]
+    fn test_trace_explains_rejections() {
+        let text = "cards 4111 1111 1111 1111 and 4111 1111 1111 1112";
+        let trace = trace(r"\d{4}( \d{4}){3}", text, "CREDIT_CARD", 0, 0.9, 0.5, 100);
+        assert_eq!(trace.engine, "regex");
+        assert_eq!(
+            stages(&trace),
+            [
+                ("compile", true),
+                ("prefilter", true),
+                ("match", true),
+                ("validate", true),
+                ("score", true),
+                ("match", true),
+                ("validate", false),
+                ("score", false),
+            ]
+        );
+        assert_eq!(trace.findings.len(), 1);
+        assert!(trace.steps[7].detail.contains("< min_score 0.50"));
+    }
+
+    #[test]
+    fn test_trace_group_and_compile_errors() {
+        let trace = trace(r"SSN: (\d{3})?x", "SSN: x", "SSN", 1, 0.9, 0.0, 100);
+        assert!(trace.triggers.as_ref().is_some_and(|t| t.iter().any(|l| l == "SSN: x")));
+        assert_eq!(stages(&trace)[2], ("match", false));
+        assert!(trace.steps[2].detail.contains("group 1 did not participate"));
+
+        let bad = super::trace(r"(\d", "1", "X", 0, 0.9, 0.0, 100);
+        assert_eq!(bad.engine, "invalid");
+        assert_eq!(stages(&bad), [("compile", false)]);
+        let missing = super::trace(r"\d", "1", "X", 2, 0.9, 0.0, 100);
+        assert!(missing.steps[0].detail.contains("has 0 capture groups"));
+    }
+
+    #[test]
+    fn test_trace_limit() {
+        let trace = trace(r"\d", "1 2 3 4", "X", 0, 0.9, 0.0, 2);
+        assert_eq!(trace.findings.len(), 2);
+        assert_eq!(trace.steps.last().map(|s| s.stage), Some("limit"));
+    }
+}
@@ -0,0 +1,94 @@
+//! Platform-independent floating point functions
+//!
+//! `f64::ln`, `f32::log2` and `powi` defer to the platform math library or
+//! compiler builtins, whose last-bit rounding differs between glibc,
+//! macOS, MSVC and musl. Scores, entropies and noised counts derived from
+//! them could then differ between fleets scanning the same input. The
+//! versions here use only IEEE basic operations (+, -, *, /), which are
+//! correctly rounded everywhere, so results are bit-for-bit identical.
+
+use std::f64::consts::{LN_2, SQRT_2};
+
+/// Terms of the atanh series; |s| <= 0.172 so 14 terms reach below 1e-21
+const SERIES_TERMS: usize = 14;
+
+/// Natural logarithm
+pub fn ln(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if x.is_infinite() {
+        return f64::INFINITY;
+    }
+
+    // Split x = m * 2^e with m in [sqrt(1/2), sqrt(2)); subnormals are
+    // scaled into the normal range first
+    let (x, mut e) = if x < f64::MIN_POSITIVE { (x * (1u64 << 54) as f64, -54) } else { (x, 0) };
+    let bits = x.to_bits();
+    e += ((bits >> 52) & 0x7ff) as i32 - 1023;
+    let mut m = f64::from_bits((bits & !(0x7ff << 52)) | (1023 << 52));
+    if m >= SQRT_2 {
+        m /= 2.0;
+        e += 1;
+    }
+
+    // ln(m) = 2 * atanh(s) = 2 * (s + s^3/3 + s^5/5 + ...)
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.0;
+    for k in 0..SERIES_TERMS {
+        sum += term / (2 * k + 1) as f64;
+        term *= s2;
+    }
+    2.0 * sum + e as f64 * LN_2
+}
+
+/// Base-2 logarithm
+pub fn log2(x: f64) -> f64 {
+    ln(x) / LN_2
+}
+
+/// `base` raised to a non-negative integer power by repeated squaring
+pub fn powi(base: f32, exp: u32) -> f32 {
+    let (mut result, mut base, mut exp) = (1.0f32, base, exp);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exp >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_accuracy() {
+        for &x in &[1e-310, 1e-5, 0.1, 0.5, 0.75, 1.0, 1.5, 2.0, 10.0, 12345.678, 1e300] {
+            let expected: f64 = f64::ln(x);
+            assert!((ln(x) - expected).abs() <= 4.0 * f64::EPSILON * expected.abs().max(1.0), "ln({})", x);
+        }
+        assert_eq!(ln(1.0), 0.0);
+        assert_eq!(ln(0.0), f64::NEG_INFINITY);
+        assert!(ln(-1.0).is_nan());
+        assert!((log2(8.0) - 3.0).abs() < 1e-15);
+        assert_eq!(powi(0.5, 3), 0.125);
+        assert_eq!(powi(3.0, 0), 1.0);
+    }
+
+    #[test]
+    fn test_golden_bits() {
+        // Exact bit patterns; any platform producing others is a regression
+        assert_eq!(ln(0.1).to_bits(), 0xc0026bb1bbb55515);
+        assert_eq!(ln(3.0).to_bits(), 0x3ff193ea7aad030a);
+        assert_eq!(log2(0.3).to_bits(), 0xbffbca9c6f53897a);
+        assert_eq!(powi(0.9, 7).to_bits(), 0x3ef4e351);
+    }
+}
@@ -0,0 +1,934 @@
+//! Dictionary (term list) matching with an on-disk, memory-mappable automaton
+//!
+//! Terms are tokenized, normalized and compiled into a minimal acyclic
+//! automaton (DAFSA) over their key bytes, so shared prefixes and suffixes
+//! are stored once. The same bytes back both modes:
+//!
+//! - in memory: built from a Python list of terms
+//! - mapped: `DictionaryMatcher.build()` writes the automaton to disk once
+//!   and `DictionaryMatcher.open()` memory-maps it read-only, so every
+//!   worker process shares the same page-cache copy of a
+//!   multi-million-entry list
+//!
+//! Matching walks the text's word tokens and, at each position, follows the
+//! automaton token by token for up to `max_tokens` tokens, reporting the
+//! longest run that is a whole term (leftmost-longest). Each node records
+//! how many keys it accepts, which numbers the terms in sorted key order.
+//!
+//! File layout (little-endian):
+//!
+//! ```text
+//! magic       8 bytes  "OLDICT\0\x02"
+//! flags       u32      bit 0: case-insensitive keys
+//!                      bit 1: Unicode case folding (set with bit 0)
+//!                      bit 2: Turkic dotted/dotless i folding
+//! max_tokens  u32      longest term, in tokens
+//! count       u64      number of terms
+//! entity_len  u64      length of the entity type string
+//! entity      entity_len bytes, zero-padded to 8
+//! root        u64      offset of the root node within the nodes
+//! nodes       automaton nodes, each after all nodes it leads to:
+//!               count     u32  keys accepted from this node
+//!               n         u16  number of transitions
+//!               final     u8   1 if a key ends here
+//!               reserved  u8
+//!               labels    n bytes, ascending
+//!               targets   n x u32 node offsets
+//! ```
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::matcher::RawMatch;
+use crate::scratch::with_scratch;
+
+const MAGIC: &[u8; 8] = b"OLDICT\0\x02";
+const FLAG_CASE_INSENSITIVE: u32 = 1;
+const FLAG_UNICODE_FOLD: u32 = 2;
+const FLAG_TURKIC: u32 = 4;
+const HEADER_LEN: usize = 32;
+const NODE_HEADER_LEN: usize = 8;
+
+/// Word token spans: alphanumeric runs, keeping internal ' ’ and - joins
+pub fn tokens(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    tokens_into(text, &mut spans);
+    spans
+}
+
+/// `tokens` appended to a reusable buffer
+pub fn tokens_into(text: &str, spans: &mut Vec<(usize, usize)>) {
+    let mut start: Option<usize> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        if c.is_alphanumeric() {
+            if start.is_none() {
+                start = Some(idx);
+            }
+            continue;
+        }
+        if let Some(s) = start {
+            let joiner = matches!(c, '\'' | '’' | '-');
+            let next_is_word = chars.peek().is_some_and(|(_, n)| n.is_alphanumeric());
+            if joiner && next_is_word {
+                continue;
+            }
+            spans.push((s, idx));
+            start = None;
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+}
+
+/// How keys are case-folded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Folding {
+    /// Case-sensitive
+    None,
+    /// Full Unicode case folding
+    Unicode,
+    /// Unicode folding with Turkish/Azeri I -> ı and İ -> i
+    Turkic,
+}
+
+impl Folding {
+    /// Folding for the Python-facing options
+    pub fn from_options(case_insensitive: bool, locale: Option<&str>) -> PyResult<Self> {
+        let turkic = match locale.map(|l| l.to_ascii_lowercase()) {
+            None => false,
+            Some(l) if l == "tr" || l == "az" || l.starts_with("tr-") || l.starts_with("az-") => true,
+            Some(l) => {
+                return Err(PyValueError::new_err(format!(
+                    "unsupported folding locale '{}': only tr and az fold differently",
+                    l
+                )))
+            }
+        };
+        Ok(match (case_insensitive, turkic) {
+            (false, _) => Folding::None,
+            (true, false) => Folding::Unicode,
+            (true, true) => Folding::Turkic,
+        })
+    }
+
+    fn flags(self) -> u32 {
+        match self {
+            Folding::None => 0,
+            Folding::Unicode => FLAG_CASE_INSENSITIVE | FLAG_UNICODE_FOLD,
+            Folding::Turkic => FLAG_CASE_INSENSITIVE | FLAG_UNICODE_FOLD | FLAG_TURKIC,
+        }
+    }
+
+    /// Folding recorded in a header, `None` for flag combinations `flags` never writes
+    fn from_flags(flags: u32) -> Option<Self> {
+        match flags {
+            0 => Some(Folding::None),
+            f if f == Folding::Unicode.flags() => Some(Folding::Unicode),
+            f if f == Folding::Turkic.flags() => Some(Folding::Turkic),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Folding::None => "none",
+            Folding::Unicode => "unicode",
+            Folding::Turkic => "turkic",
+        }
+    }
+}
+
+/// Append the case fold of `c`
+///
+/// Lowercasing plus the full case-folding mappings lowercasing misses
+/// (ß -> ss, final sigma, Greek symbol variants, long s).
+fn fold_char(out: &mut String, c: char, folding: Folding) {
+    match folding {
+        Folding::None => out.push(c),
+        Folding::Unicode | Folding::Turkic => match c {
+            'I' if folding == Folding::Turkic => out.push('ı'),
+            'İ' if folding == Folding::Turkic => out.push('i'),
+            'ß' | 'ẞ' => out.push_str("ss"),
+            'ς' => out.push('σ'),
+            'ſ' => out.push('s'),
+            'ϐ' => out.push('β'),
+            'ϑ' => out.push('θ'),
+            'ϕ' => out.push('φ'),
+            'ϖ' => out.push('π'),
+            'ϰ' => out.push('κ'),
+            'ϱ' => out.push('ρ'),
+            'ϵ' => out.push('ε'),
+            _ => out.extend(c.to_lowercase()),
+        },
+    }
+}
+
+/// Lookup key for a token sequence: tokens joined by single spaces
+fn push_key(out: &mut String, token: &str, folding: Folding) {
+    if !out.is_empty() {
+        out.push(' ');
+    }
+    for c in token.chars() {
+        fold_char(out, c, folding);
+    }
+}
+
+/// Normalize a dictionary term into its key and token count
+fn term_key(term: &str, folding: Folding) -> Option<(String, usize)> {
+    let spans = tokens(term);
+    if spans.is_empty() {
+        return None;
+    }
+    let mut key = String::with_capacity(term.len());
+    for &(s, e) in &spans {
+        push_key(&mut key, &term[s..e], folding);
+    }
+    Some((key, spans.len()))
+}
+
+/// Serialize terms into the dictionary format
+pub fn build_table(terms: &[String], entity_type: &str, folding: Folding) -> PyResult<Vec<u8>> {
+    let mut max_tokens = 0usize;
+    let mut keys: Vec<String> = terms
+        .iter()
+        .filter_map(|t| term_key(t, folding))
+        .map(|(key, n)| {
+            max_tokens = max_tokens.max(n);
+            key
+        })
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    let (nodes, root) = DafsaBuilder::build(&keys)?;
+
+    let entity = entity_type.as_bytes();
+    let entity_padded = entity.len().div_ceil(8) * 8;
+    let mut out = Vec::with_capacity(HEADER_LEN + entity_padded + 8 + nodes.len());
+
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&folding.flags().to_le_bytes());
+    out.extend_from_slice(&(max_tokens as u32).to_le_bytes());
+    out.extend_from_slice(&(keys.len() as u64).to_le_bytes());
+    out.extend_from_slice(&(entity.len() as u64).to_le_bytes());
+    out.extend_from_slice(entity);
+    out.resize(HEADER_LEN + entity_padded, 0);
+    out.extend_from_slice(&u64::from(root).to_le_bytes());
+    out.extend_from_slice(&nodes);
+    Ok(out)
+}
+
+/// A node during construction: (final, transitions to frozen nodes)
+type OpenNode = (bool, Vec<(u8, u32)>);
+
+/// Incremental construction of a minimal automaton from sorted keys
+///
+/// The path of the previous key stays open; once a key leaves it, the
+/// abandoned part is frozen bottom-up, each node replaced by an identical
+/// frozen node where there is one (Daciuk et al., 2000). Frozen nodes are
+/// written out as they are created, so nodes only lead to earlier ones.
+#[derive(Default)]
+struct DafsaBuilder {
+    nodes: Vec<u8>,
+    /// Frozen nodes by content, for sharing identical suffixes
+    register: HashMap<OpenNode, u32>,
+    /// Open path from the root; each node's next transition is pending
+    path: Vec<OpenNode>,
+    /// Label of the pending transition out of each path node but the last
+    labels: Vec<u8>,
+}
+
+impl DafsaBuilder {
+    /// (nodes, root offset) accepting exactly `keys`, which must be sorted
+    fn build(keys: &[String]) -> PyResult<(Vec<u8>, u32)> {
+        let mut builder = Self { path: vec![(false, Vec::new())], ..Default::default() };
+        let mut prev: &[u8] = &[];
+        for key in keys {
+            let key = key.as_bytes();
+            let common = prev.iter().zip(key).take_while(|(a, b)| a == b).count();
+            builder.freeze_below(common)?;
+            for &byte in &key[common..] {
+                builder.labels.push(byte);
+                builder.path.push((false, Vec::new()));
+            }
+            if let Some(last) = builder.path.last_mut() {
+                last.0 = true;
+            }
+            prev = key;
+        }
+        builder.freeze_below(0)?;
+        let root = builder.path.pop().unwrap_or_default();
+        let root = builder.freeze(root)?;
+        Ok((builder.nodes, root))
+    }
+
+    /// Freeze the open path below `depth`
+    fn freeze_below(&mut self, depth: usize) -> PyResult<()> {
+        while self.path.len() > depth + 1 {
+            let (Some(node), Some(label)) = (self.path.pop(), self.labels.pop()) else {
+                break;
+            };
+            let target = self.freeze(node)?;
+            if let Some(parent) = self.path.last_mut() {
+                parent.1.push((label, target));
+            }
+        }
+        Ok(())
+    }
+
+    /// Offset of a frozen node identical to `node`, writing it if new
+    fn freeze(&mut self, node: OpenNode) -> PyResult<u32> {
+        if let Some(&at) = self.register.get(&node) {
+            return Ok(at);
+        }
+        let too_large = || PyValueError::new_err("dictionary automaton exceeds 4 GiB");
+        let at = u32::try_from(self.nodes.len()).map_err(|_| too_large())?;
+        let (is_final, transitions) = &node;
+        let mut count = u32::from(*is_final);
+        for &(_, target) in transitions {
+            let child = read_node(&self.nodes, target as usize).map_or(0, |c| c.count);
+            count = count.checked_add(child).ok_or_else(too_large)?;
+        }
+        self.nodes.extend_from_slice(&count.to_le_bytes());
+        self.nodes.extend_from_slice(&(transitions.len() as u16).to_le_bytes());
+        self.nodes.extend_from_slice(&[u8::from(*is_final), 0]);
+        self.nodes.extend(transitions.iter().map(|&(label, _)| label));
+        for &(_, target) in transitions {
+            self.nodes.extend_from_slice(&target.to_le_bytes());
+        }
+        self.register.insert(node, at);
+        Ok(at)
+    }
+}
+
+/// One automaton node, borrowed from the node bytes
+struct Node<'a> {
+    count: u32,
+    is_final: bool,
+    labels: &'a [u8],
+    targets: &'a [u8],
+    /// Offset just past the node
+    end: usize,
+}
+
+impl Node<'_> {
+    fn target(&self, i: usize) -> usize {
+        u32::from_le_bytes(self.targets[i * 4..i * 4 + 4].try_into().unwrap()) as usize
+    }
+}
+
+/// The node at `at`, or None if it runs past the end of `nodes`
+fn read_node(nodes: &[u8], at: usize) -> Option<Node<'_>> {
+    let header = nodes.get(at..at.checked_add(NODE_HEADER_LEN)?)?;
+    let n = u16::from_le_bytes([header[4], header[5]]) as usize;
+    let labels_at = at + NODE_HEADER_LEN;
+    let end = labels_at + n * 5;
+    Some(Node {
+        count: u32::from_le_bytes(header[..4].try_into().unwrap()),
+        is_final: header[6] != 0,
+        labels: nodes.get(labels_at..labels_at + n)?,
+        targets: nodes.get(labels_at + n..end)?,
+        end,
+    })
+}
+
+/// Check every node of an automaton; returns the root's key count
+///
+/// Nodes must tile `nodes` exactly, have ascending labels, lead only to
+/// earlier node starts (so the automaton is acyclic) and count their keys
+/// consistently. Lookups in a checked automaton stay within it and yield
+/// term indices below the root's count.
+fn check_automaton(nodes: &[u8], root: u64) -> Result<u32, &'static str> {
+    let mut starts = vec![0u64; nodes.len().div_ceil(64)];
+    let is_start = |starts: &[u64], at: usize| starts[at / 64] >> (at % 64) & 1 == 1;
+    let mut at = 0;
+    while at < nodes.len() {
+        let node = read_node(nodes, at).ok_or("truncated node")?;
+        if !node.labels.windows(2).all(|w| w[0] < w[1]) {
+            return Err("transitions out of order");
+        }
+        let mut count = u32::from(node.is_final);
+        for i in 0..node.labels.len() {
+            let target = node.target(i);
+            if target >= at || !is_start(&starts, target) {
+                return Err("transition to an invalid node");
+            }
+            let child = read_node(nodes, target).ok_or("truncated node")?;
+            count = count.checked_add(child.count).ok_or("key count overflow")?;
+        }
+        if count != node.count {
+            return Err("node key count mismatch");
+        }
+        starts[at / 64] |= 1 << (at % 64);
+        at = node.end;
+    }
+    usize::try_from(root)
+        .ok()
+        .filter(|&r| r < nodes.len() && is_start(&starts, r))
+        .and_then(|r| read_node(nodes, r))
+        .map(|r| r.count)
+        .ok_or("invalid root node")
+}
+
+/// Replace the file at `path` with `bytes` atomically
+///
+/// Open dictionaries map their file shared, so truncating it in place
+/// would make readers fault on the vanished pages (SIGBUS). The bytes go
+/// to a temporary file in the same directory, which is then renamed over
+/// the target: existing mappings keep the old inode, new opens see the
+/// new one.
+fn write_replacing(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let name = path.file_name().ok_or_else(|| std::io::Error::other("path has no file name"))?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(name);
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp = path.with_file_name(tmp_name);
+
+    let result = std::fs::File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Read-only, shared memory mapping of a dictionary file
+///
+/// The file must only ever be replaced atomically (see write_replacing),
+/// never rewritten in place, while any process has it mapped.
+#[cfg(unix)]
+struct Mapping {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+// The mapping is read-only and never mutated after creation
+#[cfg(unix)]
+unsafe impl Send for Mapping {}
+#[cfg(unix)]
+unsafe impl Sync for Mapping {}
+
+#[cfg(unix)]
+impl Mapping {
+    fn open(path: &Path) -> std::io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len == 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "empty dictionary file"));
+        }
+        // SAFETY: mapping a regular file read-only; the fd may be closed
+        // after mmap returns and the mapping stays valid until munmap
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self { ptr, len })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        // SAFETY: ptr/len describe a live PROT_READ mapping owned by self
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: unmapping exactly the region returned by mmap
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+/// Backing bytes of a dictionary
+enum Storage {
+    Owned(Vec<u8>),
+    #[cfg(unix)]
+    Mapped(Mapping),
+}
+
+impl Storage {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Storage::Owned(v) => v,
+            #[cfg(unix)]
+            Storage::Mapped(m) => m.as_slice(),
+        }
+    }
+}
+
+/// Parsed view over a dictionary file
+struct TermTable {
+    storage: Storage,
+    folding: Folding,
+    max_tokens: usize,
+    count: usize,
+    entity_type: String,
+    nodes_at: usize,
+    root: usize,
+}
+
+fn read_u32(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(b[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64(b: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(b[at..at + 8].try_into().unwrap())
+}
+
+/// Position in the automaton after part of a key
+#[derive(Clone, Copy)]
+struct State {
+    node: usize,
+    /// Number of keys sorting before any key through this state
+    index: usize,
+}
+
+impl TermTable {
+    fn parse(storage: Storage) -> PyResult<Self> {
+        let bad = |msg: &str| PyValueError::new_err(format!("invalid dictionary file: {}", msg));
+        let b = storage.bytes();
+        if b.len() < HEADER_LEN || &b[..8] != MAGIC {
+            return Err(bad("bad magic or version (rebuild with DictionaryMatcher.build)"));
+        }
+        let folding = Folding::from_flags(read_u32(b, 8)).ok_or_else(|| bad("unsupported folding flags"))?;
+        let max_tokens = read_u32(b, 12) as usize;
+        let count = read_u64(b, 16);
+        let entity_len = usize::try_from(read_u64(b, 24)).map_err(|_| bad("truncated header"))?;
+        let root_at = entity_len
+            .div_ceil(8)
+            .checked_mul(8)
+            .and_then(|n| n.checked_add(HEADER_LEN))
+            .filter(|&n| n <= b.len().saturating_sub(8))
+            .ok_or_else(|| bad("truncated header"))?;
+        let nodes_at = root_at + 8;
+        let entity_type = std::str::from_utf8(&b[HEADER_LEN..HEADER_LEN + entity_len])
+            .map_err(|_| bad("entity type is not UTF-8"))?
+            .to_string();
+        let root = read_u64(b, root_at);
+        let accepted = check_automaton(&b[nodes_at..], root).map_err(bad)?;
+        if u64::from(accepted) != count {
+            return Err(bad("term count mismatch"));
+        }
+
+        Ok(Self {
+            storage,
+            folding,
+            max_tokens,
+            count: accepted as usize,
+            entity_type,
+            nodes_at,
+            root: root as usize,
+        })
+    }
+
+    fn nodes(&self) -> &[u8] {
+        &self.storage.bytes()[self.nodes_at..]
+    }
+
+    fn start(&self) -> State {
+        State { node: self.root, index: 0 }
+    }
+
+    /// The state after reading `bytes` from `state`, if some key continues so
+    fn step(&self, mut state: State, bytes: &[u8]) -> Option<State> {
+        let nodes = self.nodes();
+        for &byte in bytes {
+            let node = read_node(nodes, state.node)?;
+            let pos = node.labels.binary_search(&byte).ok()?;
+            // A key ending here and keys through smaller labels sort first
+            state.index += usize::from(node.is_final);
+            for i in 0..pos {
+                state.index += read_node(nodes, node.target(i))?.count as usize;
+            }
+            state.node = node.target(pos);
+        }
+        Some(state)
+    }
+
+    /// Index of the term ending at `state`, if one does
+    fn term_at(&self, state: State) -> Option<usize> {
+        read_node(self.nodes(), state.node)?.is_final.then_some(state.index)
+    }
+
+    /// Index of `key` in sorted key order, if present
+    fn find(&self, key: &[u8]) -> Option<usize> {
+        self.step(self.start(), key).and_then(|state| self.term_at(state))
+    }
+
+    /// Leftmost-longest, non-overlapping term matches in text
+    fn find_matches(&self, text: &str, confidence: f32) -> Vec<RawMatch> {
+        with_scratch(|scratch| {
+            scratch.tokens.clear();
+            tokens_into(text, &mut scratch.tokens);
+            self.match_tokens(text, &scratch.tokens, &mut scratch.text, confidence)
+        })
+    }
+
+    fn match_tokens(&self, text: &str, spans: &[(usize, usize)], key: &mut String, confidence: f32) -> Vec<RawMatch> {
+        let mut matches = Vec::new();
+        let mut i = 0;
+
+        while i < spans.len() {
+            // Follow the automaton token by token, keeping the longest whole term
+            let mut state = self.start();
+            let mut longest = None;
+            key.clear();
+            for n in 1..=self.max_tokens.min(spans.len() - i) {
+                // Tokens of a multi-word term are separated by whitespace only
+                let (s, e) = spans[i + n - 1];
+                if n > 1 && !text[spans[i + n - 2].1..s].chars().all(char::is_whitespace) {
+                    break;
+                }
+                let before = key.len();
+                push_key(key, &text[s..e], self.folding);
+                let Some(next) = self.step(state, &key.as_bytes()[before..]) else {
+                    break;
+                };
+                state = next;
+                if let Some(term_idx) = self.term_at(state) {
+                    longest = Some((n, term_idx));
+                }
+            }
+
+            let Some((n, term_idx)) = longest else {
+                i += 1;
+                continue;
+            };
+            let (start, end) = (spans[i].0, spans[i + n - 1].1);
+            matches.push(RawMatch {
+                pattern_id: term_idx,
+                start,
+                end,
+                text: text[start..end].to_string(),
+                entity_type: self.entity_type.clone(),
+                confidence,
+                truncated: false,
+                context: None,
+            });
+            i += n;
+        }
+        matches
+    }
+}
+
+/// Term-list matcher backed by an in-memory or memory-mapped automaton
+#[pyclass]
+pub struct DictionaryMatcher {
+    table: TermTable,
+    confidence: f32,
+}
+
+#[pymethods]
+impl DictionaryMatcher {
+    /// Build an in-memory dictionary matcher
+    ///
+    /// Args:
+    ///     terms: Terms to match (multi-word terms match across any whitespace)
+    ///     entity_type: Entity type reported for matches
+    ///     case_insensitive: Match ignoring case (full Unicode case folding)
+    ///     confidence: Confidence reported for matches
+    ///     locale: "tr" or "az" for Turkic folding (I <-> ı, İ <-> i)
+    ///
+    /// Raises:
+    ///     ValueError: If the locale has no special folding rules
+    #[new]
+    #[pyo3(signature = (terms, entity_type="DICTIONARY", case_insensitive=true, confidence=0.85, locale=None))]
+    fn new(
+        terms: Vec<String>,
+        entity_type: &str,
+        case_insensitive: bool,
+        confidence: f32,
+        locale: Option<&str>,
+    ) -> PyResult<Self> {
+        let bytes = build_table(&terms, entity_type, Folding::from_options(case_insensitive, locale)?)?;
+        Ok(Self {
+            table: TermTable::parse(Storage::Owned(bytes))?,
+            confidence,
+        })
+    }
+
+    /// Write a dictionary file for later use with open()
+    ///
+    /// An existing file is replaced atomically (written beside it, then
+    /// renamed over it), so processes that have it open keep reading the
+    /// old version. Replace dictionary files only this way, never by
+    /// rewriting them in place.
+    ///
+    /// Args:
+    ///     terms: Terms to store
+    ///     path: Destination file path
+    ///     entity_type: Entity type reported for matches
+    ///     case_insensitive: Store case-folded keys
+    ///     locale: "tr" or "az" for Turkic folding
+    #[staticmethod]
+    #[pyo3(signature = (terms, path, entity_type="DICTIONARY", case_insensitive=true, locale=None))]
+    fn build(
+        py: Python<'_>,
+        terms: Vec<String>,
+        path: &str,
+        entity_type: &str,
+        case_insensitive: bool,
+        locale: Option<&str>,
+    ) -> PyResult<()> {
+        let folding = Folding::from_options(case_insensitive, locale)?;
+        let bytes = py.allow_threads(|| build_table(&terms, entity_type, folding))?;
+        write_replacing(Path::new(path), &bytes)
+            .map_err(|e| PyIOError::new_err(format!("failed to write dictionary '{}': {}", path, e)))
+    }
+
+    /// Open a dictionary file, memory-mapping it where supported
+    ///
+    /// The mapping is read-only and shared, so processes opening the same
+    /// file share one copy in the OS page cache. The automaton is checked
+    /// once on open.
+    ///
+    /// Args:
+    ///     path: File written by build()
+    ///     confidence: Confidence reported for matches
+    #[staticmethod]
+    #[pyo3(signature = (path, confidence=0.85))]
+    fn open(path: &str, confidence: f32) -> PyResult<Self> {
+        let io_err = |e: std::io::Error| PyIOError::new_err(format!("failed to open dictionary '{}': {}", path, e));
+
+        #[cfg(unix)]
+        let storage = Storage::Mapped(Mapping::open(Path::new(path)).map_err(io_err)?);
+        #[cfg(not(unix))]
+        let storage = Storage::Owned(std::fs::read(Path::new(path)).map_err(io_err)?);
+
+        Ok(Self {
+            table: TermTable::parse(storage)?,
+            confidence,
+        })
+    }
+
+    /// Number of distinct terms
+    #[getter]
+    fn term_count(&self) -> usize {
+        self.table.count
+    }
+
+    /// Entity type reported for matches
+    #[getter]
+    fn entity_type(&self) -> String {
+        self.table.entity_type.clone()
+    }
+
+    /// Case folding of the table: "none", "unicode" or "turkic"
+    #[getter]
+    fn folding(&self) -> &'static str {
+        self.table.folding.as_str()
+    }
+
+    /// Whether the table is memory-mapped from disk
+    #[getter]
+    fn is_mapped(&self) -> bool {
+        match &self.table.storage {
+            Storage::Owned(_) => false,
+            #[cfg(unix)]
+            Storage::Mapped(_) => true,
+        }
+    }
+
+    /// Check whether a term is in the dictionary
+    fn contains(&self, term: &str) -> bool {
+        term_key(term, self.table.folding)
+            .is_some_and(|(key, _)| self.table.find(key.as_bytes()).is_some())
+    }
+
+    /// Approximate memory held by the automaton
+    ///
+    /// Returns:
+    ///     Dict with table_bytes (private heap) and mapped_bytes (shared,
+    ///     file-backed pages)
+    fn memory_usage(&self) -> BTreeMap<&'static str, usize> {
+        let len = self.table.storage.bytes().len();
+        let mut usage = BTreeMap::new();
+        if self.is_mapped() {
+            usage.insert("table_bytes", 0);
+            usage.insert("mapped_bytes", len);
+        } else {
+            usage.insert("table_bytes", len);
+            usage.insert("mapped_bytes", 0);
+        }
+        usage
+    }
+
+    /// Find dictionary terms in text (releases the GIL)
+    ///
+    /// Returns:
+    ///     List of RawMatch objects; pattern_id is the term's index in sorted key order
+    fn find_matches(&self, py: Python<'_>, text: &str) -> Vec<RawMatch> {
+        py.allow_threads(|| self.table.find_matches(text, self.confidence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(terms: &[&str], case_insensitive: bool) -> TermTable {
+        let folding = if case_insensitive { Folding::Unicode } else { Folding::None };
+        folded_table(terms, folding)
+    }
+
+    fn folded_table(terms: &[&str], folding: Folding) -> TermTable {
+        let terms: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        TermTable::parse(Storage::Owned(build_table(&terms, "NAME", folding).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn test_tokens() {
+        let text = "O'Brien, Mary-Jane  (smith)-";
+        let toks: Vec<&str> = tokens(text).iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(toks, vec!["O'Brien", "Mary-Jane", "smith"]);
+    }
+
+    #[test]
+    fn test_table_lookup() {
+        let t = table(&["Smith", "mary jane", "Zoe", "smith"], true);
+        assert_eq!(t.count, 3);
+        assert_eq!(t.max_tokens, 2);
+        assert_eq!(t.entity_type, "NAME");
+        assert!(t.find(b"smith").is_some());
+        assert!(t.find(b"mary jane").is_some());
+        assert!(t.find(b"mary").is_none());
+    }
+
+    #[test]
+    fn test_find_matches_longest() {
+        let t = table(&["Mary", "Mary Jane", "Smith"], true);
+        let text = "Patient MARY  JANE smith and mary.";
+        let found: Vec<&str> = t.find_matches(text, 0.8).iter().map(|m| &text[m.start..m.end]).collect();
+        assert_eq!(found, vec!["MARY  JANE", "smith", "mary"]);
+
+        let cased = table(&["Mary"], false);
+        assert_eq!(cased.find_matches("mary Mary", 0.8).len(), 1);
+    }
+
+    #[test]
+    fn test_unicode_folding() {
+        let t = table(&["Ðorđe Šantić", "Straße", "ΟΔΥΣΣΕΥΣ"], true);
+        let text = "ðORĐE ŠANTIĆ, STRASSE, Οδυσσευς";
+        let found: Vec<&str> = t.find_matches(text, 0.8).iter().map(|m| &text[m.start..m.end]).collect();
+        assert_eq!(found, ["ðORĐE ŠANTIĆ", "STRASSE", "Οδυσσευς"]);
+    }
+
+    #[test]
+    fn test_turkic_folding() {
+        let text = "IŞIK ışık İSTANBUL istanbul";
+        let default = table(&["ışık", "istanbul"], true);
+        assert_eq!(default.find_matches(text, 0.8).len(), 2);
+
+        let turkic = folded_table(&["ışık", "istanbul"], Folding::Turkic);
+        assert_eq!(turkic.folding, Folding::Turkic);
+        assert_eq!(turkic.find_matches(text, 0.8).len(), 4);
+
+        assert_eq!(Folding::from_options(true, Some("tr-TR")).unwrap(), Folding::Turkic);
+        assert_eq!(Folding::from_options(false, Some("az")).unwrap(), Folding::None);
+        assert!(Folding::from_options(true, Some("de")).is_err());
+    }
+
+    #[test]
+    fn test_automaton_shares_suffixes() {
+        let t = table(&["hats", "cat", "hat", "cats"], false);
+        // root -c/h-> A -a-> B -t-> C (final) -s-> D (final)
+        let mut nodes = 0;
+        let mut at = 0;
+        while let Some(node) = read_node(t.nodes(), at) {
+            nodes += 1;
+            at = node.end;
+        }
+        assert_eq!(nodes, 5);
+        let indices: Vec<Option<usize>> =
+            ["cat", "cats", "hat", "hats", "ca", "dog"].iter().map(|k| t.find(k.as_bytes())).collect();
+        assert_eq!(indices, [Some(0), Some(1), Some(2), Some(3), None, None]);
+    }
+
+    #[test]
+    fn test_rejects_corrupt_table() {
+        let mut bytes = build_table(&["a".to_string()], "X", Folding::Unicode).unwrap();
+        bytes.pop();
+        assert!(TermTable::parse(Storage::Owned(bytes)).is_err());
+        assert!(TermTable::parse(Storage::Owned(b"garbage".to_vec())).is_err());
+
+        let mut v1 = build_table(&["a".to_string()], "X", Folding::Unicode).unwrap();
+        v1[7] = 1;
+        assert!(TermTable::parse(Storage::Owned(v1)).is_err());
+
+        // Case-insensitive without Unicode folding is never written
+        let mut ascii = build_table(&["a".to_string()], "X", Folding::Unicode).unwrap();
+        ascii[8] = FLAG_CASE_INSENSITIVE as u8;
+        assert!(TermTable::parse(Storage::Owned(ascii)).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_bytes_never_panic() {
+        let terms: Vec<String> = ["Mary Jane", "Mary", "Smith", "Smithson"].iter().map(|t| t.to_string()).collect();
+        let bytes = build_table(&terms, "NAME", Folding::Unicode).unwrap();
+        let mut rejected = 0;
+        for at in 0..bytes.len() {
+            for flip in [0x01, 0x80, 0xff] {
+                let mut corrupt = bytes.clone();
+                corrupt[at] ^= flip;
+                match TermTable::parse(Storage::Owned(corrupt)) {
+                    // Whatever still parses must stay within the table
+                    Ok(t) => {
+                        for m in t.find_matches("mary jane smithson smith mary", 0.8) {
+                            assert!(m.pattern_id < t.count);
+                        }
+                    }
+                    Err(_) => rejected += 1,
+                }
+            }
+        }
+        assert!(rejected > bytes.len());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mapped_table_matches_owned() {
+        let terms = vec!["Alice Walker".to_string(), "Bob".to_string()];
+        let path = std::env::temp_dir().join(format!("oldict-test-{}.bin", std::process::id()));
+        write_replacing(&path, &build_table(&terms, "NAME", Folding::Unicode).unwrap()).unwrap();
+
+        let mapped = TermTable::parse(Storage::Mapped(Mapping::open(&path).unwrap())).unwrap();
+        let text = "alice walker met BOB";
+        let spans = |t: &TermTable| t.find_matches(text, 0.9).iter().map(|m| (m.start, m.end)).collect::<Vec<_>>();
+        assert_eq!(spans(&mapped), vec![(0, 12), (17, 20)]);
+
+        // Rebuilding while mapped leaves the open mapping on the old file
+        write_replacing(&path, &build_table(&["Met".to_string()], "NAME", Folding::Unicode).unwrap()).unwrap();
+        assert_eq!(spans(&mapped), vec![(0, 12), (17, 20)]);
+        let reopened = TermTable::parse(Storage::Mapped(Mapping::open(&path).unwrap())).unwrap();
+        assert_eq!(spans(&reopened), vec![(13, 16)]);
+
+        drop((mapped, reopened));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
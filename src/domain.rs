@@ -0,0 +1,243 @@
+//! Domain name validation and hostname classification
+//!
+//! A dot-containing token is not necessarily a hostname: "report.final",
+//! "v1.2" and "file.txt" all look like one. Validation checks label syntax
+//! and then requires the name to sit under a known public suffix (from the
+//! embedded subset of the public suffix list), which is also what splits a
+//! host into its registrable domain ("mail.example.co.uk" -> "example.co.uk").
+
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::net::Ipv6Addr;
+
+use crate::validators;
+
+/// Maximum length of a full hostname (without trailing dot)
+const MAX_HOST_LEN: usize = 253;
+
+/// Maximum length of a single label
+const MAX_LABEL_LEN: usize = 63;
+
+/// Suffixes reserved for private networks or documentation (RFC 2606,
+/// RFC 6762, RFC 8375) plus common de facto internal zones
+const INTERNAL_SUFFIXES: &[&str] = &[
+    "local", "localhost", "internal", "intranet", "corp", "home", "lan", "localdomain",
+    "home.arpa", "test", "example", "invalid", "private",
+];
+
+/// Rules parsed from the embedded public suffix data
+struct SuffixRules {
+    /// Plain rules ("com", "co.uk")
+    exact: HashSet<&'static str>,
+    /// Wildcard rules, stored without the "*." ("ck" for "*.ck")
+    wildcard: HashSet<&'static str>,
+    /// Exception rules, stored without the "!" ("www.ck")
+    exception: HashSet<&'static str>,
+}
+
+static RULES: Lazy<SuffixRules> = Lazy::new(|| {
+    let mut rules = SuffixRules {
+        exact: HashSet::new(),
+        wildcard: HashSet::new(),
+        exception: HashSet::new(),
+    };
+    for line in include_str!("data/public_suffix.dat").lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("*.") {
+            rules.wildcard.insert(rest);
+        } else if let Some(rest) = line.strip_prefix('!') {
+            rules.exception.insert(rest);
+        } else {
+            rules.exact.insert(line);
+        }
+    }
+    rules
+});
+
+/// How a hostname relates to the public DNS
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostClass {
+    /// Registrable name under a public suffix
+    Public,
+    /// Single-label name or name under a private/reserved zone
+    Internal,
+    /// Literal IPv4 or IPv6 address
+    Ip,
+    /// Not a syntactically valid hostname, or under no known suffix
+    Invalid,
+}
+
+impl HostClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HostClass::Public => "public",
+            HostClass::Internal => "internal",
+            HostClass::Ip => "ip",
+            HostClass::Invalid => "invalid",
+        }
+    }
+}
+
+/// Lowercase the host and strip one trailing dot (fully-qualified form)
+fn canonical(host: &str) -> String {
+    let host = host.trim();
+    host.strip_suffix('.').unwrap_or(host).to_ascii_lowercase()
+}
+
+/// LDH label check: letters, digits and inner hyphens, 1-63 bytes
+///
+/// Internationalized names must be in punycode ("xn--") form.
+fn valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= MAX_LABEL_LEN
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Split a canonical host into labels if its syntax is valid
+fn labels(host: &str) -> Option<Vec<&str>> {
+    if host.is_empty() || host.len() > MAX_HOST_LEN {
+        return None;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    if !labels.iter().all(|l| valid_label(l)) {
+        return None;
+    }
+    // An all-numeric TLD is an IP fragment or version string, not a name
+    if labels.last().is_some_and(|tld| tld.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+    Some(labels)
+}
+
+/// Number of trailing labels forming the public suffix, if any rule matches
+fn suffix_len(labels: &[&str]) -> Option<usize> {
+    let rules = &*RULES;
+    let n = labels.len();
+    let mut best = None;
+    for i in (0..n).rev() {
+        let candidate = labels[i..].join(".");
+        let count = n - i;
+        if rules.exception.contains(candidate.as_str()) {
+            // An exception makes the name itself registrable
+            return Some(count - 1);
+        }
+        if rules.exact.contains(candidate.as_str()) {
+            best = Some(count);
+        }
+        if i > 0 && rules.wildcard.contains(candidate.as_str()) {
+            best = Some(count + 1);
+        }
+    }
+    best
+}
+
+fn is_ip_literal(host: &str) -> bool {
+    let unbracketed = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+    validators::ipv4_format(unbracketed) || unbracketed.parse::<Ipv6Addr>().is_ok()
+}
+
+fn is_internal(labels: &[&str]) -> bool {
+    if labels.len() == 1 {
+        return true;
+    }
+    INTERNAL_SUFFIXES.iter().any(|suffix| {
+        let parts = suffix.split('.').count();
+        parts <= labels.len() && labels[labels.len() - parts..].join(".") == *suffix
+    })
+}
+
+/// Registrable domain of a host: its public suffix plus one label
+///
+/// None if the host is invalid, is itself a public suffix, or is not under
+/// any known suffix.
+pub fn registrable_domain(host: &str) -> Option<String> {
+    let host = canonical(host);
+    let labels = labels(&host)?;
+    let suffix = suffix_len(&labels)?;
+    if suffix >= labels.len() {
+        return None;
+    }
+    Some(labels[labels.len() - suffix - 1..].join("."))
+}
+
+/// Whether `host` is a syntactically valid, registrable public domain name
+pub fn validate_domain(host: &str) -> bool {
+    registrable_domain(host).is_some()
+}
+
+/// Classify a hostname as public, internal, an IP literal or invalid
+pub fn classify_hostname(host: &str) -> HostClass {
+    let host = canonical(host);
+    if is_ip_literal(&host) {
+        return HostClass::Ip;
+    }
+    let Some(labels) = labels(&host) else {
+        return HostClass::Invalid;
+    };
+    if is_internal(&labels) {
+        return HostClass::Internal;
+    }
+    match suffix_len(&labels) {
+        Some(n) if n < labels.len() => HostClass::Public,
+        _ => HostClass::Invalid,
+    }
+}
+
+/// Validator for HOSTNAME findings: anything but an invalid name passes
+pub fn hostname_format(host: &str) -> bool {
+    classify_hostname(host) != HostClass::Invalid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrable_domain() {
+        assert_eq!(registrable_domain("mail.Example.co.uk.").as_deref(), Some("example.co.uk"));
+        assert_eq!(registrable_domain("www.example.com").as_deref(), Some("example.com"));
+        assert_eq!(registrable_domain("alice.github.io").as_deref(), Some("alice.github.io"));
+        assert_eq!(registrable_domain("co.uk"), None);
+        assert_eq!(registrable_domain("github.io"), None);
+    }
+
+    #[test]
+    fn test_wildcard_and_exception_rules() {
+        // *.ck makes every second-level name a suffix, except www.ck
+        assert_eq!(registrable_domain("shop.example.ck").as_deref(), Some("shop.example.ck"));
+        assert_eq!(registrable_domain("example.ck"), None);
+        assert_eq!(registrable_domain("www.ck").as_deref(), Some("www.ck"));
+    }
+
+    #[test]
+    fn test_validate_domain_rejects_garbage() {
+        assert!(validate_domain("example.org"));
+        assert!(validate_domain("xn--bcher-kva.de"));
+        assert!(!validate_domain("report.final"));
+        assert!(!validate_domain("file.txt"));
+        assert!(!validate_domain("v1.2"));
+        assert!(!validate_domain("-bad.example.com"));
+        assert!(!validate_domain("exa_mple.com"));
+        assert!(!validate_domain(&format!("{}.com", "a".repeat(64))));
+    }
+
+    #[test]
+    fn test_classify_hostname() {
+        assert_eq!(classify_hostname("api.example.com"), HostClass::Public);
+        assert_eq!(classify_hostname("fileserver"), HostClass::Internal);
+        assert_eq!(classify_hostname("db01.corp"), HostClass::Internal);
+        assert_eq!(classify_hostname("printer.home.arpa"), HostClass::Internal);
+        assert_eq!(classify_hostname("10.1.2.3"), HostClass::Ip);
+        assert_eq!(classify_hostname("[fe80::1]"), HostClass::Ip);
+        assert_eq!(classify_hostname("notes.txt"), HostClass::Invalid);
+        assert_eq!(classify_hostname("co.uk"), HostClass::Invalid);
+    }
+}
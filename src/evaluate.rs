@@ -0,0 +1,240 @@
+//! Precision / recall / F1 of a pattern pack against a labeled corpus
+//!
+//! Each document comes with its expected (start, end, entity_type) spans.
+//! Predictions are paired with expected spans of the same entity type,
+//! each expected span at most once:
+//!
+//! - "exact": spans must be identical
+//! - "overlap": spans must share at least one byte
+//!
+//! Documents are scored in parallel and the counts summed, so the report
+//! for a large corpus costs one pass over it.
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+
+/// How a predicted span is paired with an expected one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchMode {
+    Exact,
+    Overlap,
+}
+
+impl MatchMode {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "exact" => Some(Self::Exact),
+            "overlap" => Some(Self::Overlap),
+            _ => None,
+        }
+    }
+
+    fn pairs(self, a: &(usize, usize), b: &(usize, usize)) -> bool {
+        match self {
+            Self::Exact => a == b,
+            Self::Overlap => a.0 < b.1 && b.0 < a.1,
+        }
+    }
+}
+
+/// (start, end, entity_type)
+pub type LabeledSpan = (usize, usize, String);
+
+/// Counts and scores for one entity type
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntityScore {
+    #[pyo3(get)]
+    pub true_positives: usize,
+    #[pyo3(get)]
+    pub false_positives: usize,
+    #[pyo3(get)]
+    pub false_negatives: usize,
+}
+
+impl EntityScore {
+    fn add(&mut self, other: &EntityScore) {
+        self.true_positives += other.true_positives;
+        self.false_positives += other.false_positives;
+        self.false_negatives += other.false_negatives;
+    }
+
+    fn ratio(num: usize, den: usize) -> f64 {
+        if den == 0 {
+            0.0
+        } else {
+            num as f64 / den as f64
+        }
+    }
+}
+
+#[pymethods]
+impl EntityScore {
+    /// TP / (TP + FP); 0.0 with no predictions
+    #[getter]
+    pub fn precision(&self) -> f64 {
+        Self::ratio(self.true_positives, self.true_positives + self.false_positives)
+    }
+
+    /// TP / (TP + FN); 0.0 with no expected spans
+    #[getter]
+    pub fn recall(&self) -> f64 {
+        Self::ratio(self.true_positives, self.true_positives + self.false_negatives)
+    }
+
+    /// Harmonic mean of precision and recall
+    #[getter]
+    pub fn f1(&self) -> f64 {
+        let (p, r) = (self.precision(), self.recall());
+        if p + r == 0.0 {
+            0.0
+        } else {
+            2.0 * p * r / (p + r)
+        }
+    }
+
+    /// Number of expected spans (TP + FN)
+    #[getter]
+    fn support(&self) -> usize {
+        self.true_positives + self.false_negatives
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EntityScore(precision={:.3}, recall={:.3}, f1={:.3}, support={})",
+            self.precision(),
+            self.recall(),
+            self.f1(),
+            self.support()
+        )
+    }
+}
+
+/// Corpus-level evaluation result
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct EvaluationReport {
+    #[pyo3(get)]
+    pub documents: usize,
+    /// Per entity type, sorted by name
+    #[pyo3(get)]
+    pub entities: BTreeMap<String, EntityScore>,
+    /// Counts summed over all entity types (micro average)
+    #[pyo3(get)]
+    pub overall: EntityScore,
+}
+
+#[pymethods]
+impl EvaluationReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "EvaluationReport(documents={}, entities={}, precision={:.3}, recall={:.3}, f1={:.3})",
+            self.documents,
+            self.entities.len(),
+            self.overall.precision(),
+            self.overall.recall(),
+            self.overall.f1()
+        )
+    }
+}
+
+/// Score one document's predictions against its expected spans
+pub fn score_document(
+    predicted: &[LabeledSpan],
+    expected: &[LabeledSpan],
+    mode: MatchMode,
+) -> BTreeMap<String, EntityScore> {
+    let mut scores: BTreeMap<String, EntityScore> = BTreeMap::new();
+    // Identical predictions from several patterns count once
+    let mut seen = HashSet::new();
+    let mut used = vec![false; expected.len()];
+    for (start, end, entity_type) in predicted {
+        if !seen.insert((start, end, entity_type)) {
+            continue;
+        }
+        let hit = expected.iter().enumerate().position(|(i, (s, e, t))| {
+            !used[i] && t == entity_type && mode.pairs(&(*start, *end), &(*s, *e))
+        });
+        let score = scores.entry(entity_type.clone()).or_default();
+        match hit {
+            Some(i) => {
+                used[i] = true;
+                score.true_positives += 1;
+            }
+            None => score.false_positives += 1,
+        }
+    }
+    for ((_, _, entity_type), used) in expected.iter().zip(used) {
+        if !used {
+            scores.entry(entity_type.clone()).or_default().false_negatives += 1;
+        }
+    }
+    scores
+}
+
+/// Evaluate `predict` over a labeled corpus
+pub fn evaluate<F>(corpus: &[(String, Vec<LabeledSpan>)], mode: MatchMode, predict: F) -> EvaluationReport
+where
+    F: Fn(&str) -> Vec<LabeledSpan> + Sync,
+{
+    let entities = corpus
+        .par_iter()
+        .map(|(text, expected)| score_document(&predict(text), expected, mode))
+        .reduce(BTreeMap::new, |mut acc, doc| {
+            for (entity_type, score) in doc {
+                acc.entry(entity_type).or_default().add(&score);
+            }
+            acc
+        });
+    let mut overall = EntityScore::default();
+    for score in entities.values() {
+        overall.add(score);
+    }
+    EvaluationReport { documents: corpus.len(), entities, overall }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize, entity_type: &str) -> LabeledSpan {
+        (start, end, entity_type.to_string())
+    }
+
+    #[test]
+    fn test_score_document_modes() {
+        let expected = vec![span(0, 11, "SSN"), span(20, 30, "PHONE")];
+        let predicted = vec![span(0, 11, "SSN"), span(0, 11, "SSN"), span(19, 30, "PHONE"), span(40, 45, "EMAIL")];
+
+        let exact = score_document(&predicted, &expected, MatchMode::Exact);
+        assert_eq!(exact["SSN"], EntityScore { true_positives: 1, false_positives: 0, false_negatives: 0 });
+        assert_eq!(exact["PHONE"], EntityScore { true_positives: 0, false_positives: 1, false_negatives: 1 });
+        assert_eq!(exact["EMAIL"].false_positives, 1);
+
+        let overlap = score_document(&predicted, &expected, MatchMode::Overlap);
+        assert_eq!(overlap["PHONE"].true_positives, 1);
+    }
+
+    #[test]
+    fn test_evaluate_sums_documents() {
+        let corpus = vec![
+            ("a".to_string(), vec![span(0, 1, "X")]),
+            ("b".to_string(), vec![span(0, 1, "X")]),
+            ("c".to_string(), vec![]),
+        ];
+        // Predicts X on "a" and "c" only
+        let report = evaluate(&corpus, MatchMode::Exact, |text| {
+            if text == "b" {
+                vec![]
+            } else {
+                vec![span(0, 1, "X")]
+            }
+        });
+        assert_eq!(report.documents, 3);
+        let x = &report.entities["X"];
+        assert_eq!((x.true_positives, x.false_positives, x.false_negatives), (1, 1, 1));
+        assert!((x.precision() - 0.5).abs() < 1e-9);
+        assert!((report.overall.f1() - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,116 @@
+//! Analyst feedback on sampled findings
+//!
+//! Analysts review a sample of findings and mark values as true or false
+//! positives. Verdicts are keyed by a hash of the entity type and the
+//! normalized value, so the same value is recognized in every later scan
+//! without storing it in the clear. Values with false-positive votes have
+//! their score demoted in proportion to the share of such votes.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Score multiplier for a value every vote marked as a false positive
+pub const FALSE_POSITIVE_FLOOR: f32 = 0.1;
+
+/// Analyst verdict on a value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    TruePositive,
+    FalsePositive,
+}
+
+impl Verdict {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "true_positive" | "tp" => Some(Self::TruePositive),
+            "false_positive" | "fp" => Some(Self::FalsePositive),
+            _ => None,
+        }
+    }
+}
+
+/// Feedback key: hex SHA-256 of "ENTITY_TYPE\0normalized"
+pub fn feedback_hash(entity_type: &str, normalized: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(entity_type.to_ascii_uppercase().as_bytes())
+        .chain_update([0])
+        .chain_update(normalized.as_bytes())
+        .finalize();
+    format!("{:x}", digest)
+}
+
+/// Whether `hash` looks like a feedback key (64 hex characters)
+pub fn is_feedback_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Accumulated verdicts per value hash
+#[derive(Clone, Debug, Default)]
+pub struct FeedbackStore {
+    /// hash -> (true-positive votes, false-positive votes)
+    votes: HashMap<String, (u32, u32)>,
+}
+
+impl FeedbackStore {
+    pub fn is_empty(&self) -> bool {
+        self.votes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.votes.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.votes.clear();
+    }
+
+    /// Record one verdict; `hash` must satisfy `is_feedback_hash`
+    pub fn add(&mut self, hash: &str, verdict: Verdict) {
+        let entry = self.votes.entry(hash.to_ascii_lowercase()).or_default();
+        match verdict {
+            Verdict::TruePositive => entry.0 += 1,
+            Verdict::FalsePositive => entry.1 += 1,
+        }
+    }
+
+    /// Score multiplier for a hash: 1.0 without false-positive votes,
+    /// down to FALSE_POSITIVE_FLOOR when every vote is a false positive
+    pub fn weight(&self, hash: &str) -> f32 {
+        match self.votes.get(hash) {
+            Some(&(tp, fp)) if fp > 0 => {
+                let fp_share = fp as f32 / (tp + fp) as f32;
+                1.0 - fp_share * (1.0 - FALSE_POSITIVE_FLOOR)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_hash() {
+        let h = feedback_hash("ssn", "123456789");
+        assert!(is_feedback_hash(&h));
+        assert_eq!(h, feedback_hash("SSN", "123456789"));
+        assert_ne!(h, feedback_hash("PHONE", "123456789"));
+        assert!(!is_feedback_hash("abc"));
+    }
+
+    #[test]
+    fn test_weight_follows_vote_share() {
+        let h = feedback_hash("SSN", "123456789");
+        let mut store = FeedbackStore::default();
+        assert_eq!(store.weight(&h), 1.0);
+        store.add(&h, Verdict::TruePositive);
+        assert_eq!(store.weight(&h), 1.0);
+        store.add(&h.to_ascii_uppercase(), Verdict::FalsePositive);
+        assert!((store.weight(&h) - 0.55).abs() < 1e-6);
+        store.add(&h, Verdict::FalsePositive);
+        store.add(&h, Verdict::FalsePositive);
+        assert!((store.weight(&h) - 0.325).abs() < 1e-6);
+        assert_eq!(store.len(), 1);
+    }
+}
@@ -0,0 +1,632 @@
+//! Findings: matches combined with normalization, validation and scoring
+//!
+//! `RawMatch` is the low-level output of the regex engine. A `Finding`
+//! adds what every consumer computes next: a normalized value, the verdict
+//! of each native validator that applies to the entity type, and a score
+//! derived from the pattern confidence and those verdicts.
+//!
+//! `GroupedFinding` collects the findings of one document that share an
+//! entity type and normalized value, so a value repeated many times is
+//! reported once with all of its locations.
+
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::checkdigit;
+use crate::domain;
+use crate::feedback::{self, FeedbackStore};
+use crate::matcher::{text_preview, RawMatch};
+use crate::national;
+use crate::network;
+use crate::ocr::OcrConfidence;
+use crate::postprocess::PostProcessors;
+use crate::repair::Repairs;
+use crate::segment;
+use crate::serial;
+use crate::taxonomy::{self, IdentifierClass};
+use crate::validators;
+
+/// Score multiplier applied when any validator rejects the value
+pub const FAILED_VALIDATION_WEIGHT: f32 = 0.25;
+
+/// A native validator: name reported in verdicts plus the check itself
+type Validator = (&'static str, fn(&str) -> bool);
+
+const CARD_VALIDATORS: &[Validator] = &[("luhn", validators::luhn)];
+// 9xx areas fail ssn_format whatever area_9xx policy validate_ssn_format is
+// given; they pass only as ITIN matches
+const SSN_VALIDATORS: &[Validator] = &[("ssn_format", validators::ssn_format)];
+const ITIN_VALIDATORS: &[Validator] = &[("itin_format", validators::itin_format)];
+const PHONE_VALIDATORS: &[Validator] = &[("phone_format", validators::phone_format)];
+const IP_VALIDATORS: &[Validator] = &[("ipv4_format", validators::ipv4_format)];
+const IBAN_VALIDATORS: &[Validator] = &[("iban_mod97", checkdigit::iban_valid)];
+const AADHAAR_VALIDATORS: &[Validator] = &[("verhoeff", checkdigit::verhoeff_valid)];
+const CIDR_VALIDATORS: &[Validator] = &[("cidr", network::cidr_format)];
+const HOST_PORT_VALIDATORS: &[Validator] = &[("host_port", network::host_port_format)];
+const DOMAIN_VALIDATORS: &[Validator] = &[("domain", domain::validate_domain)];
+const HOSTNAME_VALIDATORS: &[Validator] = &[("hostname", domain::hostname_format)];
+const VIN_VALIDATORS: &[Validator] = &[("vin_check", serial::vin_check)];
+const TEUDAT_ZEHUT_VALIDATORS: &[Validator] = &[("teudat_zehut", national::teudat_zehut)];
+const AMKA_VALIDATORS: &[Validator] = &[("amka", national::amka)];
+const NHS_VALIDATORS: &[Validator] = &[("nhs_mod11", national::nhs_number)];
+const NIR_VALIDATORS: &[Validator] = &[("nir_key", national::nir)];
+
+/// Native validators that apply to an entity type
+pub fn validators_for(entity_type: &str) -> &'static [Validator] {
+    match entity_type {
+        "CREDIT_CARD" => CARD_VALIDATORS,
+        "SSN" => SSN_VALIDATORS,
+        "ITIN" => ITIN_VALIDATORS,
+        "PHONE" | "PHONE_MOBILE" | "PHONE_HOME" | "PHONE_WORK" | "FAX" => PHONE_VALIDATORS,
+        "IBAN" => IBAN_VALIDATORS,
+        "AADHAAR" | "AADHAAR_IN" => AADHAAR_VALIDATORS,
+        "IP_ADDRESS" => IP_VALIDATORS,
+        "IP_CIDR" => CIDR_VALIDATORS,
+        "HOST_PORT" => HOST_PORT_VALIDATORS,
+        "DOMAIN" => DOMAIN_VALIDATORS,
+        "HOSTNAME" => HOSTNAME_VALIDATORS,
+        "VIN" => VIN_VALIDATORS,
+        "TEUDAT_ZEHUT_IL" => TEUDAT_ZEHUT_VALIDATORS,
+        "AMKA_GR" => AMKA_VALIDATORS,
+        "NHS_NUMBER" => NHS_VALIDATORS,
+        "INSEE_FR" => NIR_VALIDATORS,
+        _ => &[],
+    }
+}
+
+/// Canonical form of a matched value, used for dedup and comparison
+pub fn normalize_value(entity_type: &str, text: &str) -> String {
+    match entity_type {
+        "CREDIT_CARD" | "SSN" | "PHONE" | "PHONE_MOBILE" | "PHONE_HOME" | "PHONE_WORK"
+        | "FAX" => text.chars().filter(|c| c.is_ascii_digit()).collect(),
+        "EMAIL" => text.trim().to_lowercase(),
+        "DOMAIN" | "HOSTNAME" => {
+            let host = text.trim();
+            host.strip_suffix('.').unwrap_or(host).to_ascii_lowercase()
+        }
+        _ => {
+            let mut collapsed = String::with_capacity(text.len());
+            for word in text.split_whitespace() {
+                if !collapsed.is_empty() {
+                    collapsed.push(' ');
+                }
+                collapsed.push_str(word);
+            }
+            collapsed
+        }
+    }
+}
+
+/// A validated, scored detection
+#[pyclass]
+#[derive(Clone)]
+pub struct Finding {
+    #[pyo3(get)]
+    pub entity_type: String,
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+    /// Matched text; with normalization stages (see PatternMatcher's
+    /// normalize argument) it is the normalized form of input[start:end]
+    #[pyo3(get)]
+    pub text: String,
+    /// Whether `text` is a preview of a longer match (see max_text_bytes);
+    /// normalization and validation still see the whole match
+    #[pyo3(get)]
+    pub truncated: bool,
+    /// Canonical value (digits only, lowercased, whitespace collapsed...)
+    #[pyo3(get)]
+    pub normalized: String,
+    /// Value validated after repair stripped labels, brackets or trailing
+    /// punctuation from the match (None when no repair applied)
+    #[pyo3(get)]
+    pub repaired: Option<String>,
+    /// Validator name -> verdict for every validator that ran
+    #[pyo3(get)]
+    pub verdicts: BTreeMap<String, bool>,
+    /// Pattern confidence before validation
+    #[pyo3(get)]
+    pub confidence: f32,
+    /// Final score after validation
+    #[pyo3(get)]
+    pub score: f32,
+    /// Lowest OCR confidence under the span (None without OCR input)
+    #[pyo3(get)]
+    pub ocr_confidence: Option<f32>,
+    #[pyo3(get)]
+    pub pattern_id: usize,
+    /// Re-identification class under the scanning matcher's taxonomy
+    pub class: IdentifierClass,
+    /// Start of the enclosing sentence (context scope for the finding)
+    #[pyo3(get)]
+    pub context_start: usize,
+    /// End of the enclosing sentence
+    #[pyo3(get)]
+    pub context_end: usize,
+    /// Caller-supplied scan context, shared by every finding from one scan
+    pub context: Option<Arc<PyObject>>,
+}
+
+#[pymethods]
+impl Finding {
+    /// True unless a validator rejected the value
+    #[getter]
+    fn valid(&self) -> bool {
+        self.verdicts.values().all(|v| *v)
+    }
+
+    /// Identifier class under the matcher's taxonomy (the built-in one
+    /// unless the matcher was given another): "direct", "quasi",
+    /// "sensitive" or "other"
+    #[getter]
+    fn identifier_class(&self) -> &'static str {
+        self.class.as_str()
+    }
+
+    /// Key for analyst feedback on this value (see PatternMatcher.load_feedback)
+    #[getter]
+    fn feedback_hash(&self) -> String {
+        feedback::feedback_hash(&self.entity_type, &self.normalized)
+    }
+
+    /// Opaque metadata passed to detect() (None if not given)
+    #[getter]
+    fn metadata(&self, py: Python<'_>) -> PyObject {
+        match &self.context {
+            Some(ctx) => ctx.clone_ref(py),
+            None => py.None(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Finding(type={}, text='{}', pos={}:{}, score={:.2})",
+            self.entity_type, self.text, self.start, self.end, self.score
+        )
+    }
+}
+
+impl Finding {
+    /// Build a finding from a raw match: normalize, validate and score
+    pub fn from_raw(raw: RawMatch) -> Self {
+        Self::from_raw_repaired(raw, None, &Repairs::default())
+    }
+
+    /// Like `from_raw`, but normalize and validate the value left after
+    /// the entity's repair rules strip decorations from the match
+    ///
+    /// `full` is the whole matched value when `raw.text` is a truncated
+    /// preview; it is validated and normalized in place of the preview, and
+    /// the stored repaired value is cut to the preview's length.
+    pub fn from_raw_repaired(raw: RawMatch, full: Option<&str>, repairs: &Repairs) -> Self {
+        let source = full.unwrap_or(&raw.text);
+        let repaired = repairs.apply(&raw.entity_type, source).map(|(s, e)| &source[s..e]);
+        let value = repaired.unwrap_or(source);
+        let normalized = normalize_value(&raw.entity_type, value);
+        let mut verdicts = BTreeMap::new();
+        for (name, check) in validators_for(&raw.entity_type) {
+            verdicts.insert((*name).to_string(), check(value));
+        }
+
+        let score = if verdicts.values().all(|v| *v) {
+            raw.confidence
+        } else {
+            raw.confidence * FAILED_VALIDATION_WEIGHT
+        };
+        let repaired = repaired.map(|r| match full {
+            Some(_) => text_preview(r, raw.text.len()).to_string(),
+            None => r.to_string(),
+        });
+
+        Self {
+            class: taxonomy::default_class(&raw.entity_type),
+            entity_type: raw.entity_type,
+            start: raw.start,
+            end: raw.end,
+            text: raw.text,
+            truncated: raw.truncated,
+            normalized,
+            repaired,
+            verdicts,
+            confidence: raw.confidence,
+            score,
+            ocr_confidence: None,
+            pattern_id: raw.pattern_id,
+            context_start: raw.start,
+            context_end: raw.end,
+            context: raw.context,
+        }
+    }
+}
+
+/// Turn raw matches into findings, dropping those scoring below `min_score`
+///
+/// Each finding's context span is the sentence(s) of `text` containing it.
+/// Values are validated after the entity's repair rules and the normalized
+/// value has the entity's post-processing rules applied. Truncated matches
+/// are validated and normalized from their whole value: the entry of
+/// `full` at their index (see find_matches_full) or else their span of
+/// `text`.
+/// Scores are scaled by the lowest OCR confidence under the span and
+/// demoted for values with analyst false-positive votes before the
+/// `min_score` cut.
+#[allow(clippy::too_many_arguments)]
+pub fn build_findings(
+    text: &str,
+    raw: Vec<RawMatch>,
+    full: Vec<Option<String>>,
+    min_score: f32,
+    post: &PostProcessors,
+    repairs: &Repairs,
+    feedback: &FeedbackStore,
+    ocr: Option<&OcrConfidence>,
+) -> Vec<Finding> {
+    let mut full = full.into_iter();
+    let mut findings: Vec<Finding> = raw
+        .into_iter()
+        .map(|m| {
+            let value = full.next().flatten();
+            let whole = match &value {
+                Some(value) => Some(value.as_str()),
+                None if m.truncated => text.get(m.start..m.end),
+                None => None,
+            };
+            Finding::from_raw_repaired(m, whole, repairs)
+        })
+        .collect();
+
+    if let Some(ocr) = ocr {
+        for f in findings.iter_mut() {
+            f.ocr_confidence = ocr.min_confidence(f.start, f.end);
+            f.score *= f.ocr_confidence.unwrap_or(1.0);
+        }
+    }
+
+    if !post.is_empty() {
+        for f in findings.iter_mut() {
+            f.normalized = post.apply(&f.entity_type, std::mem::take(&mut f.normalized));
+        }
+    }
+    if !feedback.is_empty() {
+        for f in findings.iter_mut() {
+            f.score *= feedback.weight(&feedback::feedback_hash(&f.entity_type, &f.normalized));
+        }
+    }
+    findings.retain(|f| f.score >= min_score);
+
+    if !findings.is_empty() {
+        let sentences = segment::sentences(text);
+        for f in findings.iter_mut() {
+            (f.context_start, f.context_end) = segment::enclosing_span(&sentences, f.start, f.end);
+        }
+    }
+    findings
+}
+
+/// All findings of one document with the same entity type and normalized value
+#[pyclass]
+#[derive(Clone)]
+pub struct GroupedFinding {
+    #[pyo3(get)]
+    pub entity_type: String,
+    #[pyo3(get)]
+    pub normalized: String,
+    /// Highest score among the occurrences
+    #[pyo3(get)]
+    pub score: f32,
+    /// The individual findings, in text order
+    #[pyo3(get)]
+    pub findings: Vec<Finding>,
+}
+
+#[pymethods]
+impl GroupedFinding {
+    /// Number of occurrences
+    #[getter]
+    fn count(&self) -> usize {
+        self.findings.len()
+    }
+
+    /// (start, end) of every occurrence, in text order
+    #[getter]
+    fn locations(&self) -> Vec<(usize, usize)> {
+        self.findings.iter().map(|f| (f.start, f.end)).collect()
+    }
+
+    /// Distinct matched texts, in order of first appearance
+    #[getter]
+    fn texts(&self) -> Vec<String> {
+        let mut texts: Vec<String> = Vec::new();
+        for f in &self.findings {
+            if !texts.contains(&f.text) {
+                texts.push(f.text.clone());
+            }
+        }
+        texts
+    }
+
+    /// True unless a validator rejected every occurrence
+    #[getter]
+    fn valid(&self) -> bool {
+        self.findings.iter().any(|f| f.valid())
+    }
+
+    /// Key for analyst feedback on this value (see PatternMatcher.load_feedback)
+    #[getter]
+    fn feedback_hash(&self) -> String {
+        feedback::feedback_hash(&self.entity_type, &self.normalized)
+    }
+
+    fn __len__(&self) -> usize {
+        self.findings.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GroupedFinding(type={}, normalized='{}', count={}, score={:.2})",
+            self.entity_type,
+            self.normalized,
+            self.findings.len(),
+            self.score
+        )
+    }
+}
+
+/// Group findings by (entity type, normalized value)
+///
+/// Groups are ordered by their first occurrence; occurrences within a
+/// group are in text order.
+pub fn group_findings(mut findings: Vec<Finding>) -> Vec<GroupedFinding> {
+    findings.sort_by_key(|f| (f.start, f.end));
+    let mut index: HashMap<(String, String), usize> = HashMap::new();
+    let mut groups: Vec<GroupedFinding> = Vec::new();
+    for f in findings {
+        let key = (f.entity_type.clone(), f.normalized.clone());
+        match index.get(&key) {
+            Some(&i) => {
+                let group = &mut groups[i];
+                group.score = group.score.max(f.score);
+                group.findings.push(f);
+            }
+            None => {
+                index.insert(key, groups.len());
+                groups.push(GroupedFinding {
+                    entity_type: f.entity_type.clone(),
+                    normalized: f.normalized.clone(),
+                    score: f.score,
+                    findings: vec![f],
+                });
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feedback::Verdict;
+
+    fn raw(entity_type: &str, text: &str, confidence: f32) -> RawMatch {
+        RawMatch {
+            pattern_id: 0,
+            start: 0,
+            end: text.len(),
+            text: text.to_string(),
+            entity_type: entity_type.to_string(),
+            confidence,
+            truncated: false,
+            context: None,
+        }
+    }
+
+    #[test]
+    fn test_group_findings_by_normalized_value() {
+        let text = "SSN 123-45-6789, again 123 45 6789, other 456-78-9012, mail A@x.io and a@x.io";
+        let at = |needle: &str, entity_type: &str, confidence: f32| {
+            let start = text.find(needle).unwrap();
+            RawMatch { start, end: start + needle.len(), ..raw(entity_type, needle, confidence) }
+        };
+        let raws = vec![
+            at("a@x.io", "EMAIL", 0.7),
+            at("123-45-6789", "SSN", 0.6),
+            at("123 45 6789", "SSN", 0.9),
+            at("456-78-9012", "SSN", 0.9),
+            at("A@x.io", "EMAIL", 0.8),
+        ];
+        let findings = build_findings(
+            text,
+            raws,
+            Vec::new(),
+            0.0,
+            &PostProcessors::default(),
+            &Repairs::default(),
+            &FeedbackStore::default(),
+            None,
+        );
+        let groups = group_findings(findings);
+
+        let summary: Vec<(&str, &str, usize, f32)> =
+            groups.iter().map(|g| (g.entity_type.as_str(), g.normalized.as_str(), g.count(), g.score)).collect();
+        assert_eq!(summary, [("SSN", "123456789", 2, 0.9), ("SSN", "456789012", 1, 0.9), ("EMAIL", "a@x.io", 2, 0.8)]);
+        assert_eq!(groups[0].locations(), [(4, 15), (23, 34)]);
+        assert_eq!(groups[2].texts(), ["A@x.io", "a@x.io"]);
+    }
+
+    #[test]
+    fn test_normalize_value() {
+        assert_eq!(normalize_value("CREDIT_CARD", "4111-1111 1111-1111"), "4111111111111111");
+        assert_eq!(normalize_value("EMAIL", " John@Example.COM"), "john@example.com");
+        assert_eq!(normalize_value("NAME", "John \n  Smith"), "John Smith");
+    }
+
+    #[test]
+    fn test_finding_validation_and_score() {
+        let good = Finding::from_raw(raw("CREDIT_CARD", "4111 1111 1111 1111", 0.9));
+        assert_eq!(good.verdicts.get("luhn"), Some(&true));
+        assert_eq!(good.score, 0.9);
+
+        let bad = Finding::from_raw(raw("SSN", "666-45-6789", 0.8));
+        assert_eq!(bad.verdicts.get("ssn_format"), Some(&false));
+        assert!((bad.score - 0.8 * FAILED_VALIDATION_WEIGHT).abs() < 1e-6);
+
+        // An ITIN is only valid when reported as one
+        let itin_as_ssn = Finding::from_raw(raw("SSN", "912-70-1234", 0.8));
+        assert_eq!(itin_as_ssn.verdicts.get("ssn_format"), Some(&false));
+        let itin = Finding::from_raw(raw("ITIN", "912-70-1234", 0.8));
+        assert_eq!(itin.verdicts.get("itin_format"), Some(&true));
+        assert_eq!(itin.score, 0.8);
+
+        let unvalidated = Finding::from_raw(raw("NAME", "John Smith", 0.5));
+        assert!(unvalidated.verdicts.is_empty());
+        assert_eq!(unvalidated.score, 0.5);
+        assert_eq!((good.class, unvalidated.class), (IdentifierClass::Direct, IdentifierClass::Quasi));
+    }
+
+    #[test]
+    fn test_repair_before_validation() {
+        let decorated = raw("IBAN", "(IBAN: GB82 WEST 1234 5698 7654 32).", 0.8);
+        assert!(!Finding::from_raw(decorated.clone()).valid());
+
+        let steps = ["labels", "brackets", "trailing_punct"].map(String::from).to_vec();
+        let repairs = Repairs::from_config(HashMap::from([("IBAN".to_string(), steps)])).unwrap();
+        let f = Finding::from_raw_repaired(decorated, None, &repairs);
+        assert_eq!(f.repaired.as_deref(), Some("GB82 WEST 1234 5698 7654 32"));
+        assert_eq!(f.text, "(IBAN: GB82 WEST 1234 5698 7654 32).");
+        assert!(f.valid());
+        assert_eq!(f.score, 0.8);
+
+        let clean = Finding::from_raw_repaired(raw("IBAN", "GB82 WEST 1234 5698 7654 32", 0.8), None, &repairs);
+        assert_eq!(clean.repaired, None);
+    }
+
+    #[test]
+    fn test_truncated_match_validated_whole() {
+        let text = "cards 4111 1111 1111 1111 and 4111 1111 1111 1129";
+        let preview = |start: usize| RawMatch {
+            start,
+            end: start + 19,
+            truncated: true,
+            ..raw("CREDIT_CARD", "4111 1111", 0.9)
+        };
+        let findings = build_findings(
+            text,
+            vec![preview(6), preview(30)],
+            Vec::new(),
+            0.5,
+            &PostProcessors::default(),
+            &Repairs::default(),
+            &FeedbackStore::default(),
+            None,
+        );
+        let values: Vec<(&str, &str, bool)> =
+            findings.iter().map(|f| (f.text.as_str(), f.normalized.as_str(), f.valid())).collect();
+        assert_eq!(
+            values,
+            [("4111 1111", "4111111111111111", true), ("4111 1111", "4111111111111129", true)]
+        );
+    }
+
+    #[test]
+    fn test_truncated_normalized_match_validated_as_normalized() {
+        let patterns = vec![(r"\d{4}(?: \d{4}){3}".to_string(), "CREDIT_CARD".to_string(), 0.9, 0)];
+        let mut compiled = crate::matcher::compile_patterns(&patterns, false);
+        compiled.normalizer = crate::normalize::Pipeline::from_names(&["decode_percent".to_string()]).unwrap();
+        let text = "card 4111%201111%201111%201111 and 4111 1111 1111 1111";
+
+        // Short and truncated, the same value validates the same way
+        for max_text_bytes in [None, Some(9)] {
+            compiled.max_text_bytes = max_text_bytes;
+            let (raws, full) = crate::matcher::find_matches_full(&compiled, text);
+            let findings = build_findings(
+                text,
+                raws,
+                full,
+                0.0,
+                &PostProcessors::default(),
+                &Repairs::default(),
+                &FeedbackStore::default(),
+                None,
+            );
+            let values: Vec<(&str, bool)> = findings.iter().map(|f| (f.normalized.as_str(), f.valid())).collect();
+            assert_eq!(values, [("4111111111111111", true), ("4111111111111111", true)], "{:?}", max_text_bytes);
+        }
+    }
+
+    #[test]
+    fn test_build_findings_min_score() {
+        let findings = build_findings(
+            "",
+            vec![raw("SSN", "123-45-6789", 0.9), raw("SSN", "000-12-3456", 0.9)],
+            Vec::new(),
+            0.5,
+            &PostProcessors::default(),
+            &Repairs::default(),
+            &FeedbackStore::default(),
+            None,
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].normalized, "123456789");
+    }
+
+    #[test]
+    fn test_finding_context_is_sentence() {
+        let text = "Unrelated intro. Patient SSN is 123-45-6789 on file. Next sentence.";
+        let start = text.find("123").unwrap();
+        let mut m = raw("SSN", "123-45-6789", 0.9);
+        m.start = start;
+        m.end = start + 11;
+
+        let findings = build_findings(
+            text,
+            vec![m],
+            Vec::new(),
+            0.0,
+            &PostProcessors::default(),
+            &Repairs::default(),
+            &FeedbackStore::default(),
+            None,
+        );
+        let f = &findings[0];
+        assert_eq!(&text[f.context_start..f.context_end], "Patient SSN is 123-45-6789 on file.");
+    }
+
+    #[test]
+    fn test_feedback_demotes_before_min_score() {
+        let mut feedback = FeedbackStore::default();
+        feedback.add(&feedback::feedback_hash("SSN", "123456789"), Verdict::FalsePositive);
+        let raws = || vec![raw("SSN", "123-45-6789", 0.9), raw("SSN", "234-56-7890", 0.9)];
+        let (post, repairs) = (PostProcessors::default(), Repairs::default());
+
+        let kept = build_findings("", raws(), Vec::new(), 0.5, &post, &repairs, &feedback, None);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].normalized, "234567890");
+
+        let all = build_findings("", raws(), Vec::new(), 0.0, &post, &repairs, &feedback, None);
+        assert!((all[0].score - 0.09).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ocr_confidence_scales_score() {
+        let text = "SSN 123-45-6789";
+        let mut m = raw("SSN", "123-45-6789", 0.9);
+        m.start = 4;
+        m.end = 15;
+        let ocr = OcrConfidence::from_spans(text, vec![(0, 3, 1.0), (4, 15, 0.5)]).unwrap();
+
+        let findings = build_findings(
+            text,
+            vec![m],
+            Vec::new(),
+            0.0,
+            &PostProcessors::default(),
+            &Repairs::default(),
+            &FeedbackStore::default(),
+            Some(&ocr),
+        );
+        assert_eq!(findings[0].ocr_confidence, Some(0.5));
+        assert!((findings[0].score - 0.45).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,229 @@
+//! Fixed-width record scanning with caller-supplied column layouts
+//!
+//! Mainframe-era exports have no delimiters, so a flat scan happily
+//! matches "digits" that straddle two columns and reports offsets nobody
+//! can map back to a field. `FixedWidthScanner` cuts each record into its
+//! columns, scans every column on its own and reports the column name.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::layout::{self, FieldMatch};
+use crate::matcher::{find_matches_impl, CompiledPatterns, PatternMatcher};
+
+/// One column of a fixed-width layout
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub name: String,
+    /// Zero-based byte offset within the record
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Check a layout for empty or duplicate columns
+pub fn validate_layout(columns: &[Column]) -> Result<(), String> {
+    if columns.is_empty() {
+        return Err("layout has no columns".to_string());
+    }
+    let mut names = HashSet::new();
+    for column in columns {
+        if column.len == 0 {
+            return Err(format!("column '{}' has zero length", column.name));
+        }
+        if !names.insert(column.name.as_str()) {
+            return Err(format!("duplicate column '{}'", column.name));
+        }
+    }
+    Ok(())
+}
+
+/// Records of the input with their byte offsets
+///
+/// Records are lines; with `record_length` each line is further cut into
+/// records of that many bytes (packed files have no newlines at all).
+fn records(text: &str, record_length: Option<usize>) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    for (offset, line) in layout::lines(text) {
+        match record_length {
+            Some(len) => {
+                let mut pos = 0;
+                while pos < line.len() {
+                    let end = (pos + len).min(line.len());
+                    if let Some(record) = line.get(pos..end) {
+                        out.push((offset + pos, record));
+                    }
+                    pos = end;
+                }
+            }
+            None if !line.is_empty() => out.push((offset, line)),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Scan each column of each record with the compiled patterns
+pub fn scan_fixed_width(
+    compiled: &CompiledPatterns,
+    columns: &[Column],
+    text: &str,
+    record_length: Option<usize>,
+) -> Vec<FieldMatch> {
+    let mut found = Vec::new();
+    for (index, (offset, record)) in records(text, record_length).into_iter().enumerate() {
+        for column in columns {
+            let Some((s, e)) = layout::trimmed_span(record, column.start, column.len) else {
+                continue;
+            };
+            let base = offset + s;
+            for m in find_matches_impl(compiled, &record[s..e]) {
+                found.push(FieldMatch {
+                    record: index,
+                    field: column.name.clone(),
+                    entity_type: m.entity_type,
+                    start: base + m.start,
+                    end: base + m.end,
+                    text: m.text,
+                    confidence: m.confidence,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Pattern scanner for fixed-width records
+#[pyclass]
+pub struct FixedWidthScanner {
+    compiled: Arc<CompiledPatterns>,
+    columns: Vec<Column>,
+    /// Bytes per record for packed input (None: one record per line)
+    #[pyo3(get)]
+    record_length: Option<usize>,
+}
+
+#[pymethods]
+impl FixedWidthScanner {
+    /// Create a scanner for a column layout
+    ///
+    /// Args:
+    ///     matcher: PatternMatcher whose compiled patterns are shared
+    ///     layout: List of (name, start, length), byte offsets within a record
+    ///     record_length: Bytes per record when records are packed without
+    ///         newlines; None for one record per line
+    ///
+    /// Raises:
+    ///     ValueError: If the layout is empty, has a zero-length or
+    ///         duplicate column, or a column extends past record_length
+    #[new]
+    #[pyo3(signature = (matcher, layout, record_length=None))]
+    fn new(
+        matcher: PyRef<PatternMatcher>,
+        layout: Vec<(String, usize, usize)>,
+        record_length: Option<usize>,
+    ) -> PyResult<Self> {
+        let columns: Vec<Column> = layout
+            .into_iter()
+            .map(|(name, start, len)| Column { name, start, len })
+            .collect();
+        validate_layout(&columns).map_err(PyValueError::new_err)?;
+        if let Some(record_length) = record_length {
+            if let Some(column) = columns.iter().find(|c| c.start + c.len > record_length) {
+                return Err(PyValueError::new_err(format!(
+                    "column '{}' extends past the {}-byte record",
+                    column.name, record_length
+                )));
+            }
+        }
+        Ok(Self {
+            compiled: matcher.compiled(),
+            columns,
+            record_length,
+        })
+    }
+
+    /// Column names in layout order
+    #[getter]
+    fn fields(&self) -> Vec<String> {
+        self.columns.iter().map(|c| c.name.clone()).collect()
+    }
+
+    /// Scan every column of every record
+    ///
+    /// Padding around each column value is ignored. Releases the GIL.
+    ///
+    /// Returns:
+    ///     List of FieldMatch with offsets into the whole text
+    fn scan(&self, py: Python<'_>, text: &str) -> Vec<FieldMatch> {
+        py.allow_threads(|| scan_fixed_width(&self.compiled, &self.columns, text, self.record_length))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("FixedWidthScanner(fields={}, record_length={:?})", self.columns.len(), self.record_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::compile_patterns;
+
+    fn columns() -> Vec<Column> {
+        [("id", 0, 6), ("ssn", 6, 11), ("phone", 17, 10)]
+            .iter()
+            .map(|&(name, start, len)| Column { name: name.to_string(), start, len })
+            .collect()
+    }
+
+    fn compiled() -> CompiledPatterns {
+        let patterns = vec![
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0),
+            (r"\d{10}".to_string(), "PHONE".to_string(), 0.6, 0),
+        ];
+        compile_patterns(&patterns, false)
+    }
+
+    #[test]
+    fn test_matches_attributed_to_columns() {
+        let text = "000001123-45-67895551234567\n000002           5559876543\n";
+        let found = scan_fixed_width(&compiled(), &columns(), text, None);
+        let got: Vec<(usize, &str, &str)> =
+            found.iter().map(|m| (m.record, m.field.as_str(), &text[m.start..m.end])).collect();
+        assert_eq!(
+            got,
+            [(0, "ssn", "123-45-6789"), (0, "phone", "5551234567"), (1, "phone", "5559876543")]
+        );
+    }
+
+    #[test]
+    fn test_no_match_across_columns() {
+        // A flat scan would see "0000011234" as a ten-digit phone number
+        let text = "00000112345";
+        let cols = vec![
+            Column { name: "id".to_string(), start: 0, len: 6 },
+            Column { name: "code".to_string(), start: 6, len: 5 },
+        ];
+        assert!(scan_fixed_width(&compiled(), &cols, text, None).is_empty());
+    }
+
+    #[test]
+    fn test_packed_records() {
+        let record = "000001123-45-67895551234567";
+        let text = record.repeat(3);
+        let found = scan_fixed_width(&compiled(), &columns(), &text, Some(record.len()));
+        assert_eq!(found.len(), 6);
+        assert_eq!(found[5].record, 2);
+        assert_eq!(found[5].start, 2 * record.len() + 17);
+    }
+
+    #[test]
+    fn test_layout_validation() {
+        assert!(validate_layout(&columns()).is_ok());
+        assert!(validate_layout(&[]).is_err());
+        let mut dup = columns();
+        dup[1].name = "id".to_string();
+        assert!(validate_layout(&dup).is_err());
+    }
+}
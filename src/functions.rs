@@ -0,0 +1,564 @@
+//! Module-level Python functions
+//!
+//! Every `#[pyfunction]` the extension registers lives here rather than in
+//! the crate root, so the clippy allow below stays scoped to this module.
+
+// pyo3 wraps each PyResult return as `.map_err(Into::<PyErr>::into)` in a
+// generated sibling function, out of reach of an item-level #[allow]
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::benchmark::BenchmarkResult;
+use crate::debug::MatchTrace;
+use crate::envfile::EnvSecret;
+use crate::hashed::HashedPii;
+use crate::layout::FieldMatch;
+use crate::{
+    annotate, benchmark, capabilities, checkdigit, debug, domain, envfile, grapheme, hashed, html, logformat, metro2,
+    national, network, normalize, overlap, postal, regions, segment, serial, signing, validators,
+};
+
+/// Validate credit card number using Luhn algorithm
+#[pyfunction]
+pub(crate) fn validate_luhn(number: &str) -> bool {
+    validators::luhn(number)
+}
+
+/// Validate SSN format (not context)
+///
+/// Args:
+///     ssn: Candidate number, separators ignored
+///     area_9xx: "reject" (default) treats every 9xx area as invalid;
+///         "itin" also accepts 9xx numbers that are valid ITINs. Detection
+///         does not use this: SSN findings always reject 9xx areas and only
+///         ITIN findings accept them
+#[pyfunction]
+#[pyo3(signature = (ssn, area_9xx="reject"))]
+pub(crate) fn validate_ssn_format(ssn: &str, area_9xx: &str) -> PyResult<bool> {
+    let policy = area_9xx_arg(area_9xx)?;
+    Ok(validators::classify_ssn(ssn, policy) != validators::SsnClass::Invalid)
+}
+
+/// Classify a nine-digit taxpayer number as "ssn", "itin" or "invalid"
+///
+/// Args:
+///     ssn: Candidate number, separators ignored
+///     area_9xx: "itin" (default) classifies 9xx numbers with an ITIN group
+///         as ITINs; "reject" treats every 9xx area as invalid
+#[pyfunction]
+#[pyo3(signature = (ssn, area_9xx="itin"))]
+pub(crate) fn classify_ssn(ssn: &str, area_9xx: &str) -> PyResult<&'static str> {
+    let policy = area_9xx_arg(area_9xx)?;
+    Ok(validators::classify_ssn(ssn, policy).as_str())
+}
+
+fn area_9xx_arg(area_9xx: &str) -> PyResult<validators::Area9xxPolicy> {
+    validators::Area9xxPolicy::parse(area_9xx).ok_or_else(|| {
+        PyValueError::new_err(format!("unknown area_9xx policy '{}': expected 'reject' or 'itin'", area_9xx))
+    })
+}
+
+/// Validate US phone number format
+#[pyfunction]
+pub(crate) fn validate_phone_format(phone: &str) -> bool {
+    validators::phone_format(phone)
+}
+
+/// Validate IPv4 address format
+#[pyfunction]
+pub(crate) fn validate_ipv4_format(ip: &str) -> bool {
+    validators::ipv4_format(ip)
+}
+
+/// Check if IP is private/reserved (likely false positive)
+#[pyfunction]
+pub(crate) fn is_private_ip(ip: &str) -> bool {
+    validators::is_private_ip(ip)
+}
+
+/// Validate CIDR notation (IPv4 prefix 0-32, IPv6 prefix 0-128)
+#[pyfunction]
+pub(crate) fn validate_cidr(cidr: &str) -> bool {
+    network::cidr_format(cidr)
+}
+
+/// Validate host:port (IPv4, [IPv6] or hostname; port 1-65535)
+#[pyfunction]
+pub(crate) fn validate_host_port(value: &str) -> bool {
+    network::host_port_format(value)
+}
+
+/// Validate a domain name against the embedded public suffix list
+///
+/// True only for syntactically valid names under a known public suffix
+/// that are not themselves a suffix ("example.co.uk", not "co.uk").
+#[pyfunction]
+pub(crate) fn validate_domain(host: &str) -> bool {
+    domain::validate_domain(host)
+}
+
+/// Classify a hostname as "public", "internal", "ip" or "invalid"
+#[pyfunction]
+pub(crate) fn classify_hostname(host: &str) -> &'static str {
+    domain::classify_hostname(host).as_str()
+}
+
+/// Registrable domain of a host ("mail.example.co.uk" -> "example.co.uk")
+#[pyfunction]
+pub(crate) fn registrable_domain(host: &str) -> Option<String> {
+    domain::registrable_domain(host)
+}
+
+/// Validate a postal code for a country (ISO 3166 alpha-2, "UK" accepted)
+///
+/// Raises ValueError for countries without a format check.
+#[pyfunction]
+pub(crate) fn validate_postal(code: &str, country: &str) -> PyResult<bool> {
+    postal::validate_postal(code, country).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "no postal code format for country '{}' (supported: {})",
+            country,
+            postal::SUPPORTED_COUNTRIES.join(", ")
+        ))
+    })
+}
+
+/// Structural plausibility (0.0-1.0) that a value is a serial number
+///
+/// Args:
+///     value: Candidate serial number
+///     excluded_prefixes: Prefixes that score 0 (defaults to SKU, PO, INV, ...)
+///     min_length: Minimum alphanumeric length
+///     max_length: Maximum alphanumeric length
+#[pyfunction]
+#[pyo3(signature = (value, excluded_prefixes=None, min_length=6, max_length=24))]
+pub(crate) fn score_serial(value: &str, excluded_prefixes: Option<Vec<String>>, min_length: usize, max_length: usize) -> f32 {
+    let mut rules = serial::SerialRules {
+        min_len: min_length,
+        max_len: max_length,
+        ..Default::default()
+    };
+    if let Some(prefixes) = excluded_prefixes {
+        rules.excluded_prefixes = prefixes.iter().map(|p| p.to_ascii_uppercase()).collect();
+    }
+    serial::score_serial(value, &rules)
+}
+
+/// Validate an Israeli Teudat Zehut (up to 9 digits, weighted check digit)
+#[pyfunction]
+pub(crate) fn validate_teudat_zehut(value: &str) -> bool {
+    national::teudat_zehut(value)
+}
+
+/// Validate a Greek AMKA (DDMMYY birth date + serial + Luhn check digit)
+#[pyfunction]
+pub(crate) fn validate_amka(value: &str) -> bool {
+    national::amka(value)
+}
+
+/// Validate a UK NHS number (10 digits, mod-11 check digit)
+#[pyfunction]
+pub(crate) fn validate_nhs_number(value: &str) -> bool {
+    national::nhs_number(value)
+}
+
+/// Validate a French NIR / INSEE number including its 2-digit key (2A/2B allowed)
+#[pyfunction]
+pub(crate) fn validate_nir(value: &str) -> bool {
+    national::nir(value)
+}
+
+/// Luhn check digit to append to a partial number
+///
+/// Spaces and hyphens are ignored; raises ValueError on other characters.
+#[pyfunction]
+pub(crate) fn luhn_checksum_digit(partial: &str) -> PyResult<char> {
+    checkdigit::luhn_digit(partial).ok_or_else(|| invalid_partial(partial, "digits"))
+}
+
+/// Two ISO 7064 MOD 97-10 check digits to append to a partial value
+#[pyfunction]
+pub(crate) fn mod97_checksum_digits(partial: &str) -> PyResult<String> {
+    checkdigit::mod97_digits(partial).ok_or_else(|| invalid_partial(partial, "letters and digits"))
+}
+
+/// Verhoeff check digit to append to a partial number
+#[pyfunction]
+pub(crate) fn verhoeff_checksum_digit(partial: &str) -> PyResult<char> {
+    checkdigit::verhoeff_digit(partial).ok_or_else(|| invalid_partial(partial, "digits"))
+}
+
+/// IBAN check digits for a country code and BBAN ("GB", "WEST12345698765432" -> "82")
+#[pyfunction]
+pub(crate) fn iban_check_digits(country: &str, bban: &str) -> PyResult<String> {
+    checkdigit::iban_check_digits(country, bban)
+        .ok_or_else(|| PyValueError::new_err(format!("invalid IBAN country '{}' or BBAN '{}'", country, bban)))
+}
+
+/// Validate a value ending in ISO 7064 MOD 97-10 check digits
+#[pyfunction]
+pub(crate) fn validate_mod97(value: &str) -> bool {
+    checkdigit::mod97_valid(value)
+}
+
+/// Validate an IBAN (spaces allowed)
+#[pyfunction]
+pub(crate) fn validate_iban(iban: &str) -> bool {
+    checkdigit::iban_valid(iban)
+}
+
+/// Validate a number ending in a Verhoeff check digit
+#[pyfunction]
+pub(crate) fn validate_verhoeff(number: &str) -> bool {
+    checkdigit::verhoeff_valid(number)
+}
+
+fn invalid_partial(partial: &str, allowed: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "cannot compute check digit for '{}': expected {} (spaces and hyphens allowed)",
+        partial, allowed
+    ))
+}
+
+/// Find hex digests (MD5/SHA-*) assigned to PII-named fields
+///
+/// Detects values like email_md5=... or "ssnHash": "..." and reports the
+/// PII kind from the field name and the digest type from its length.
+/// Releases the GIL while scanning.
+#[pyfunction]
+#[pyo3(name = "find_hashed_pii")]
+pub(crate) fn find_hashed_pii(py: Python<'_>, text: &str) -> Vec<HashedPii> {
+    py.allow_threads(|| hashed::find_hashed_pii(text))
+}
+
+/// Find secrets in .env files and KEY=value environment dumps
+///
+/// Classifies each assignment by its key name (TOKEN, SECRET, KEY,
+/// PASSWORD, ...), provider token formats (AWS, GitHub, Slack, Stripe,
+/// JWT, PEM keys, ...) and value entropy. Placeholders and ${VAR}
+/// references under secret-bearing keys are not reported.
+/// Releases the GIL while scanning.
+///
+/// Args:
+///     text: Contents of a .env file or `env` output
+///     min_confidence: Drop secrets below this confidence
+#[pyfunction]
+#[pyo3(signature = (text, min_confidence=0.5))]
+pub(crate) fn find_env_secrets(py: Python<'_>, text: &str, min_confidence: f32) -> Vec<EnvSecret> {
+    py.allow_threads(|| envfile::find_env_secrets(text, min_confidence))
+}
+
+/// Find consumer fields in Metro 2 credit-report base segments
+///
+/// Recognizes the fixed-width record structure (newline-delimited or
+/// packed) and reports each populated consumer field (name, SSN, date of
+/// birth, address, ...) with its record index and field name.
+/// Releases the GIL while scanning.
+#[pyfunction]
+#[pyo3(name = "find_metro2")]
+pub(crate) fn find_metro2(py: Python<'_>, text: &str) -> Vec<FieldMatch> {
+    py.allow_threads(|| metro2::find_metro2(text))
+}
+
+/// Split text into sentence or paragraph spans
+///
+/// Args:
+///     text: The text to segment
+///     level: "sentence" or "paragraph"
+///
+/// Returns:
+///     List of (start, end) byte offsets, whitespace trimmed
+#[pyfunction]
+#[pyo3(name = "segment", signature = (text, level="sentence"))]
+pub(crate) fn segment_text(py: Python<'_>, text: &str, level: &str) -> PyResult<Vec<(usize, usize)>> {
+    match level {
+        "sentence" => Ok(py.allow_threads(|| segment::sentences(text))),
+        "paragraph" => Ok(py.allow_threads(|| segment::paragraphs(text))),
+        other => Err(PyValueError::new_err(format!(
+            "unknown segmentation level '{}', expected 'sentence' or 'paragraph'",
+            other
+        ))),
+    }
+}
+
+/// Split a document into prose, table, code, base64, binary and hexdump regions
+///
+/// Consecutive lines of one kind form a region and blank lines join the
+/// region before them, so the regions cover the whole text. These are the
+/// regions PatternMatcher(regions=...) restricts patterns to.
+///
+/// Returns:
+///     List of (start, end, kind) byte offsets in document order
+#[pyfunction]
+pub(crate) fn classify_regions(py: Python<'_>, text: &str) -> Vec<(usize, usize, &'static str)> {
+    py.allow_threads(|| regions::classify_regions(text))
+        .into_iter()
+        .map(|r| (r.start, r.end, r.kind.as_str()))
+        .collect()
+}
+
+/// Context window around a span, safe for RTL and combining-mark text
+///
+/// Args:
+///     text: The source text
+///     start: Span start (byte offset)
+///     end: Span end (byte offset)
+///     radius: Characters of context on each side
+///     grapheme_safe: Widen edges so no combining mark is split from its base
+///
+/// Returns:
+///     (start, end) byte offsets of the window
+#[pyfunction]
+#[pyo3(signature = (text, start, end, radius=40, grapheme_safe=true))]
+pub(crate) fn context_window(text: &str, start: usize, end: usize, radius: usize, grapheme_safe: bool) -> PyResult<(usize, usize)> {
+    if start > end || end > text.len() || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+        return Err(PyValueError::new_err(format!(
+            "invalid span {}:{} for text of {} bytes",
+            start,
+            end,
+            text.len()
+        )));
+    }
+    Ok(grapheme::context_window(text, start, end, radius, grapheme_safe))
+}
+
+/// Text with entity markers inserted around matches
+///
+/// Args:
+///     text: The scanned text
+///     matches: Objects with start, end (byte offsets) and entity_type,
+///         e.g. RawMatch, Finding or FieldMatch
+///     start_marker: Opening marker; "{entity}" is replaced by the entity type
+///     end_marker: Closing marker, same substitution
+///
+/// Overlapping matches keep the earliest-starting (then longest) one.
+///
+/// Raises:
+///     ValueError: If a span is out of range or not on a character boundary
+#[pyfunction]
+#[pyo3(
+    name = "annotate",
+    signature = (text, matches, start_marker=annotate::DEFAULT_START_MARKER, end_marker=annotate::DEFAULT_END_MARKER)
+)]
+pub(crate) fn annotate_text(
+    py: Python<'_>,
+    text: &str,
+    matches: Vec<Bound<'_, PyAny>>,
+    start_marker: &str,
+    end_marker: &str,
+) -> PyResult<String> {
+    let spans = overlap::spans_from_py(text, &matches)?;
+    Ok(py.allow_threads(|| annotate::annotate(text, spans, start_marker, end_marker)))
+}
+
+/// HTML preview with a <mark data-entity="..."> around each match
+///
+/// The document text and entity types are HTML-escaped; only the marks
+/// themselves are markup.
+///
+/// Args:
+///     text: The scanned text
+///     matches: Objects with start, end (byte offsets) and entity_type
+///     mark_class: Optional class attribute for every mark
+///
+/// Overlapping matches keep the earliest-starting (then longest) one.
+///
+/// Raises:
+///     ValueError: If a span is out of range or not on a character boundary
+#[pyfunction]
+#[pyo3(signature = (text, matches, mark_class=None))]
+pub(crate) fn render_html(py: Python<'_>, text: &str, matches: Vec<Bound<'_, PyAny>>, mark_class: Option<&str>) -> PyResult<String> {
+    let spans = overlap::spans_from_py(text, &matches)?;
+    Ok(py.allow_threads(|| html::render_html(text, spans, mark_class)))
+}
+
+/// Combine results from several matchers with the native overlap rule
+///
+/// Args:
+///     results_list: One list of matches per matcher, in precedence order;
+///         items need start, end and entity_type, plus score (Finding) or
+///         confidence (RawMatch) for "highest_score"
+///     overlap_policy: "earliest" (earliest start, then longest, as in
+///         annotate and render_html), "highest_score" (highest score, then
+///         earliest/longest) or "keep_all" (no overlap resolution)
+///
+/// Exact duplicates (same start, end and entity_type) keep the item from
+/// the earliest list.
+///
+/// Returns:
+///     The kept match objects, sorted by start (then longest first)
+///
+/// Raises:
+///     ValueError: On an unknown policy or a span with end before start
+#[pyfunction]
+#[pyo3(signature = (results_list, overlap_policy="earliest"))]
+pub(crate) fn merge_results<'py>(
+    py: Python<'py>,
+    results_list: Vec<Vec<Bound<'py, PyAny>>>,
+    overlap_policy: &str,
+) -> PyResult<Vec<Bound<'py, PyAny>>> {
+    let policy = overlap::OverlapPolicy::parse(overlap_policy).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "unknown overlap_policy '{}': expected earliest, highest_score or keep_all",
+            overlap_policy
+        ))
+    })?;
+    let items: Vec<Bound<'py, PyAny>> = results_list.into_iter().flatten().collect();
+    let spans = items.iter().map(overlap::scored_span_from_py).collect::<PyResult<Vec<_>>>()?;
+    let kept = py.allow_threads(|| overlap::merge(&spans, policy));
+    Ok(kept.into_iter().map(|i| items[i].clone()).collect())
+}
+
+/// Trace one pattern over a text, recording why candidates were kept or dropped
+///
+/// Meant for interactive pattern development: runs the pattern the way a
+/// PatternMatcher would (same engine choice, same span rules) followed by
+/// detect()'s validation and scoring, and records every step.
+///
+/// Args:
+///     pattern: Regex to test
+///     text: Sample text
+///     entity_type: Entity type, which selects the native validators
+///     group: Capture group reported as the match (0 = whole match)
+///     confidence: Pattern confidence used for scoring
+///     min_score: Score cut applied as in detect()
+///     limit: Most engine matches to trace
+///
+/// Returns:
+///     MatchTrace with the engine, two-pass triggers, steps and findings;
+///     print(trace.report()) for a readable summary. Compile errors are
+///     reported as a rejected "compile" step rather than raised.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (pattern, text, entity_type="DEBUG", group=0, confidence=1.0, min_score=0.0, limit=1000))]
+pub(crate) fn match_debug(
+    py: Python<'_>,
+    pattern: &str,
+    text: &str,
+    entity_type: &str,
+    group: usize,
+    confidence: f32,
+    min_score: f32,
+    limit: usize,
+) -> MatchTrace {
+    py.allow_threads(|| debug::trace(pattern, text, entity_type, group, confidence, min_score, limit))
+}
+
+/// (key, start, end) of a log line field
+type LogFieldSpan = (String, usize, usize);
+
+/// Split one syslog / CEF / LEEF line into named fields
+///
+/// Args:
+///     line: A single log line
+///     format: "auto", "syslog", "cef" or "leef"
+///
+/// Returns:
+///     (format, [(key, start, end), ...]) with byte offsets into the line,
+///     or None if the line is not in a supported format
+#[pyfunction]
+#[pyo3(signature = (line, format="auto"))]
+pub(crate) fn parse_log_line(line: &str, format: &str) -> PyResult<Option<(&'static str, Vec<LogFieldSpan>)>> {
+    let format = logformat::format_arg(format)?;
+    Ok(logformat::parse_line(line, format)
+        .map(|(fmt, fields)| (fmt.as_str(), fields.into_iter().map(|f| (f.key, f.start, f.end)).collect())))
+}
+
+/// Measure scan throughput on this machine with the built-in workload
+///
+/// Scans the embedded benchmark corpus, repeated to `megabytes`, with the
+/// first N patterns of the embedded PII pattern set for each N in
+/// `pattern_counts`, on one thread. Each matcher is warmed before timing
+/// and the fastest of `rounds` scans is reported. Releases the GIL.
+///
+/// Args:
+///     pattern_counts: Pattern counts to measure (default 1, 8, 16, 32)
+///     megabytes: Text size per round in MB (10^6 bytes)
+///     rounds: Timed scans per pattern count
+///     two_pass: Compile the matchers with two-pass scanning
+///
+/// Returns:
+///     List of BenchmarkResult, one per pattern count
+///
+/// Raises:
+///     ValueError: For a pattern count outside 1..=32, a non-positive
+///         size or zero rounds
+#[pyfunction]
+#[pyo3(signature = (pattern_counts=None, megabytes=4.0, rounds=3, two_pass=false))]
+pub(crate) fn benchmark_self(
+    py: Python<'_>,
+    pattern_counts: Option<Vec<usize>>,
+    megabytes: f64,
+    rounds: usize,
+    two_pass: bool,
+) -> PyResult<Vec<BenchmarkResult>> {
+    let counts = benchmark::check_args(pattern_counts, megabytes, rounds).map_err(PyValueError::new_err)?;
+    Ok(py.allow_threads(|| {
+        let text = benchmark::corpus_text((megabytes * 1e6) as usize);
+        counts.into_iter().map(|count| benchmark::run(count, &text, rounds, two_pass)).collect()
+    }))
+}
+
+/// The synthetic corpus benchmark_self() scans, for comparing other tools
+#[pyfunction]
+pub(crate) fn benchmark_corpus() -> &'static str {
+    benchmark::CORPUS
+}
+
+/// Check a stored PatternMatcher.detect_signed() payload against its signature
+///
+/// Args:
+///     payload: SignedResult.payload exactly as stored
+///     signature: SignedResult.signature (hex)
+///     key: The key the result was signed with
+///
+/// Returns:
+///     True if the payload is unmodified and was signed with `key`
+#[pyfunction]
+pub(crate) fn verify_signed_result(payload: &str, signature: &str, key: &[u8]) -> bool {
+    signing::verify(key, payload, signature)
+}
+
+/// Run normalization stages over a text, as PatternMatcher(normalize=...) does
+///
+/// Args:
+///     text: Text to normalize
+///     stages: Stage names in the order to run them ("unicode_fold",
+///         "collapse_whitespace", "decode_entities", "decode_percent",
+///         "ocr_fold")
+///
+/// Raises:
+///     ValueError: For an unknown stage name
+#[pyfunction]
+pub(crate) fn normalize_text(text: &str, stages: Vec<String>) -> PyResult<String> {
+    let pipeline = normalize::Pipeline::from_names(&stages).map_err(PyValueError::new_err)?;
+    Ok(pipeline.apply(text).text)
+}
+
+/// Check if native extension is working
+#[pyfunction]
+pub(crate) fn is_native_available() -> bool {
+    true
+}
+
+/// Optional features available in this build
+///
+/// Returns:
+///     Dict of capability name -> bool for archive, mmap, parallel,
+///     parquet, pdf, simd and two_pass; every name is always present
+#[pyfunction]
+#[pyo3(name = "capabilities")]
+pub(crate) fn native_capabilities() -> std::collections::BTreeMap<&'static str, bool> {
+    capabilities::capabilities()
+}
+
+/// Vector instruction set used for byte searching on this CPU
+///
+/// Returns:
+///     "avx2", "sse2", "neon" or "none"
+#[pyfunction]
+pub(crate) fn simd_tier() -> &'static str {
+    capabilities::simd_tier()
+}
@@ -0,0 +1,176 @@
+//! Cluster-safe span and context handling for RTL and combining-mark text
+//!
+//! Offsets everywhere are in logical (storage) order, which is what
+//! Arabic and Hebrew text uses regardless of display direction. The
+//! remaining hazards are at span edges:
+//!
+//! - combining marks (niqqud, harakat, accents) belong to the preceding
+//!   base character, so a span must never start on one or stop just
+//!   before one
+//! - invisible bidi controls (LRM/RLM, embeddings, isolates) wrapping a
+//!   value are formatting, not content, and are trimmed from span edges
+
+/// Combining marks and joiners that attach to the preceding character
+pub fn is_combining(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'   // Combining diacritical marks
+        | '\u{0483}'..='\u{0489}' // Cyrillic
+        | '\u{0591}'..='\u{05BD}' // Hebrew cantillation and points
+        | '\u{05BF}'
+        | '\u{05C1}'..='\u{05C2}'
+        | '\u{05C4}'..='\u{05C5}'
+        | '\u{05C7}'
+        | '\u{0610}'..='\u{061A}' // Arabic signs
+        | '\u{064B}'..='\u{065F}' // Arabic harakat
+        | '\u{0670}'
+        | '\u{06D6}'..='\u{06DC}'
+        | '\u{06DF}'..='\u{06E4}'
+        | '\u{06E7}'..='\u{06E8}'
+        | '\u{06EA}'..='\u{06ED}'
+        | '\u{0900}'..='\u{0903}' // Devanagari signs
+        | '\u{093A}'..='\u{094F}'
+        | '\u{1AB0}'..='\u{1AFF}' // Combining diacritical marks extended
+        | '\u{1DC0}'..='\u{1DFF}' // Combining diacritical marks supplement
+        | '\u{200C}'..='\u{200D}' // ZWNJ / ZWJ
+        | '\u{20D0}'..='\u{20FF}' // Combining marks for symbols
+        | '\u{FE00}'..='\u{FE0F}' // Variation selectors
+        | '\u{FE20}'..='\u{FE2F}' // Combining half marks
+    )
+}
+
+/// Invisible bidirectional formatting characters
+pub fn is_bidi_control(c: char) -> bool {
+    matches!(c,
+        '\u{061C}'                // Arabic letter mark
+        | '\u{200E}'..='\u{200F}' // LRM / RLM
+        | '\u{202A}'..='\u{202E}' // Embeddings and overrides
+        | '\u{2066}'..='\u{2069}' // Isolates
+    )
+}
+
+/// Move `pos` back to the base character of the cluster containing it
+pub fn cluster_start(text: &str, mut pos: usize) -> usize {
+    while pos > 0 {
+        match text[pos..].chars().next() {
+            Some(c) if is_combining(c) => {
+                pos = text[..pos].char_indices().next_back().map_or(0, |(i, _)| i);
+            }
+            _ => break,
+        }
+    }
+    pos
+}
+
+/// Move `pos` forward past combining marks that belong to the previous cluster
+pub fn cluster_end(text: &str, mut pos: usize) -> usize {
+    for c in text[pos..].chars() {
+        if !is_combining(c) {
+            break;
+        }
+        pos += c.len_utf8();
+    }
+    pos
+}
+
+/// Adjust a match span so it covers whole clusters and no edge bidi controls
+///
+/// Leading combining marks belong to the character before the span and are
+/// dropped; trailing marks after the span belong to its last character and
+/// are included. Returns None if nothing but formatting remains.
+pub fn adjust_span(text: &str, start: usize, end: usize) -> Option<(usize, usize)> {
+    let mut s = start;
+    for c in text[start..end].chars() {
+        if !(is_combining(c) || is_bidi_control(c)) {
+            break;
+        }
+        s += c.len_utf8();
+    }
+
+    let mut e = end;
+    for c in text[s..end].chars().rev() {
+        if !is_bidi_control(c) {
+            break;
+        }
+        e -= c.len_utf8();
+    }
+
+    if s >= e {
+        return None;
+    }
+    Some((s, cluster_end(text, e)))
+}
+
+/// Context window of `radius` characters around [start, end)
+///
+/// With `grapheme_safe`, window edges are widened to cluster boundaries so
+/// the snippet never begins on an orphaned mark or cuts a mark off its base.
+pub fn context_window(text: &str, start: usize, end: usize, radius: usize, grapheme_safe: bool) -> (usize, usize) {
+    let mut ws = start;
+    for (i, _) in text[..start].char_indices().rev().take(radius) {
+        ws = i;
+    }
+    let mut we = end;
+    for c in text[end..].chars().take(radius) {
+        we += c.len_utf8();
+    }
+    if grapheme_safe {
+        (cluster_start(text, ws), cluster_end(text, we))
+    } else {
+        (ws, we)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "shalom" with niqqud: shin+qamats+shin-dot, lamed, vav+holam, final mem
+    const SHALOM: &str = "\u{05E9}\u{05B8}\u{05C1}\u{05DC}\u{05D5}\u{05B9}\u{05DD}";
+
+    #[test]
+    fn test_adjust_span_includes_trailing_marks() {
+        // A letters-only pattern stops before the points on the first letter
+        let (s, e) = adjust_span(SHALOM, 0, 2).unwrap();
+        assert_eq!(&SHALOM[s..e], "\u{05E9}\u{05B8}\u{05C1}");
+    }
+
+    #[test]
+    fn test_adjust_span_drops_leading_marks_and_bidi() {
+        // Arabic "muhammad" with harakat, wrapped in RLM
+        let text = "\u{200F}\u{0645}\u{064F}\u{062D}\u{064E}\u{0645}\u{0651}\u{064E}\u{062F}\u{200F}";
+        let (s, e) = adjust_span(text, 0, text.len()).unwrap();
+        assert_eq!(&text[s..e], "\u{0645}\u{064F}\u{062D}\u{064E}\u{0645}\u{0651}\u{064E}\u{062F}");
+
+        // A span starting on a mark gives the mark back to its base
+        let (s, _) = adjust_span(text, 5, text.len()).unwrap();
+        assert_eq!(text[s..].chars().next(), Some('\u{062D}'));
+
+        assert!(adjust_span(text, 0, 3).is_none());
+    }
+
+    #[test]
+    fn test_rtl_number_spans() {
+        // Hebrew sentence with an RLM-isolated ID number
+        let text = "\u{05DE}\u{05E1}\u{05E4}\u{05E8} \u{2067}123-45-6789\u{2069} \u{05EA}\u{05D5}\u{05D3}\u{05D4}";
+        let start = text.find('\u{2067}').unwrap();
+        let end = text.find('\u{2069}').unwrap() + '\u{2069}'.len_utf8();
+        let (s, e) = adjust_span(text, start, end).unwrap();
+        assert_eq!(&text[s..e], "123-45-6789");
+    }
+
+    #[test]
+    fn test_context_window_grapheme_safe() {
+        let text = format!("{} 123 {}", SHALOM, SHALOM);
+        let start = text.find("123").unwrap();
+        let end = start + 3;
+
+        // Three characters back lands on a vowel point inside the first word
+        let (ws, we) = context_window(&text, start, end, 3, false);
+        assert_eq!(text[ws..].chars().next(), Some('\u{05B9}'));
+        assert_eq!(text[..we].chars().last(), Some('\u{05B8}'));
+
+        let (ws, we) = context_window(&text, start, end, 3, true);
+        assert_eq!(text[ws..].chars().next(), Some('\u{05D5}'));
+        assert_eq!(&text[ws..we], "\u{05D5}\u{05B9}\u{05DD} 123 \u{05E9}\u{05B8}\u{05C1}");
+    }
+}
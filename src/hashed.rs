@@ -0,0 +1,205 @@
+//! Detection of hashed PII: hex digests stored under PII field names
+//!
+//! `email_md5=5d41402abc4b2a76b9719d911017c592` is still an identifier
+//! under our policy (digests of low-entropy values are reversible by
+//! lookup), but no value pattern sees it. A digest is reported when it is
+//! assigned to a field whose name indicates a PII kind; the digest type is
+//! inferred from its length.
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use regex::Regex;
+
+/// `field = digest` / `"field": "digest"` assignments
+static ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b([A-Za-z_][A-Za-z0-9_.\-]{0,63})["']?\s*[:=]\s*["']?([0-9A-Fa-f]{32,128})\b"#)
+        .expect("hashed PII assignment regex")
+});
+
+/// Confidence when the field names both a PII kind and a hash
+pub const CONFIDENCE_EXPLICIT: f32 = 0.9;
+/// Confidence when the field names only the PII kind
+pub const CONFIDENCE_IMPLIED: f32 = 0.75;
+
+/// Field-name tokens that indicate the value was hashed
+const HASH_TOKENS: &[&str] = &[
+    "hash", "hashed", "md5", "sha", "sha1", "sha224", "sha256", "sha384", "sha512", "digest", "hmac",
+];
+
+/// Field-name token -> PII kind of the hashed value
+const PII_TOKENS: &[(&str, &str)] = &[
+    ("email", "EMAIL"),
+    ("mail", "EMAIL"),
+    ("ssn", "SSN"),
+    ("phone", "PHONE"),
+    ("mobile", "PHONE"),
+    ("msisdn", "PHONE"),
+    ("dob", "DATE_DOB"),
+    ("birthdate", "DATE_DOB"),
+    ("name", "NAME"),
+    ("firstname", "NAME"),
+    ("lastname", "NAME"),
+    ("card", "CREDIT_CARD"),
+    ("pan", "CREDIT_CARD"),
+    ("ip", "IP_ADDRESS"),
+    ("passport", "PASSPORT"),
+    ("address", "ADDRESS"),
+    ("account", "ACCOUNT_NUMBER"),
+    ("acct", "ACCOUNT_NUMBER"),
+    ("username", "USERNAME"),
+    ("login", "USERNAME"),
+    ("idfa", "DEVICE_ID"),
+    ("gaid", "DEVICE_ID"),
+    ("maid", "DEVICE_ID"),
+];
+
+/// A hex digest stored under a PII field name
+#[pyclass]
+#[derive(Clone)]
+pub struct HashedPii {
+    /// Digest span (the hex value only)
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+    #[pyo3(get)]
+    pub text: String,
+    /// Field name the digest was assigned to
+    #[pyo3(get)]
+    pub field: String,
+    /// PII kind indicated by the field name ("EMAIL", "SSN", ...)
+    #[pyo3(get)]
+    pub pii_type: String,
+    /// Digest algorithm inferred from length ("md5", "sha1", "sha256", ...)
+    #[pyo3(get)]
+    pub digest: String,
+    #[pyo3(get)]
+    pub confidence: f32,
+}
+
+#[pymethods]
+impl HashedPii {
+    /// Always "HASHED_PII"; the hashed value's kind is in pii_type
+    #[getter]
+    fn entity_type(&self) -> &'static str {
+        "HASHED_PII"
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "HashedPii(field={}, pii_type={}, digest={}, pos={}:{})",
+            self.field, self.pii_type, self.digest, self.start, self.end
+        )
+    }
+}
+
+/// Digest algorithm for a hex digest length
+pub fn digest_type(hex_len: usize) -> Option<&'static str> {
+    match hex_len {
+        32 => Some("md5"),
+        40 => Some("sha1"),
+        56 => Some("sha224"),
+        64 => Some("sha256"),
+        96 => Some("sha384"),
+        128 => Some("sha512"),
+        _ => None,
+    }
+}
+
+/// Lowercase tokens of a field name, split on punctuation and camelCase
+pub(crate) fn field_tokens(field: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in field.chars() {
+        if !c.is_ascii_alphanumeric() {
+            prev_lower = false;
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower && !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// PII kind and whether the field also names a hash
+fn classify_field(field: &str) -> Option<(&'static str, bool)> {
+    let tokens = field_tokens(field);
+    let pii = tokens
+        .iter()
+        .find_map(|t| PII_TOKENS.iter().find(|(name, _)| name == t).map(|(_, kind)| *kind))?;
+    let hashed = tokens.iter().any(|t| HASH_TOKENS.contains(&t.as_str()));
+    Some((pii, hashed))
+}
+
+/// Find digests assigned to PII-named fields
+pub fn find_hashed_pii(text: &str) -> Vec<HashedPii> {
+    let mut found = Vec::new();
+    for caps in ASSIGNMENT.captures_iter(text) {
+        let (Some(field), Some(value)) = (caps.get(1), caps.get(2)) else {
+            continue;
+        };
+        let Some(digest) = digest_type(value.len()) else {
+            continue;
+        };
+        let Some((pii_type, hashed)) = classify_field(field.as_str()) else {
+            continue;
+        };
+        found.push(HashedPii {
+            start: value.start(),
+            end: value.end(),
+            text: value.as_str().to_string(),
+            field: field.as_str().to_string(),
+            pii_type: pii_type.to_string(),
+            digest: digest.to_string(),
+            confidence: if hashed { CONFIDENCE_EXPLICIT } else { CONFIDENCE_IMPLIED },
+        });
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MD5: &str = "5d41402abc4b2a76b9719d911017c592";
+    const SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_field_tokens() {
+        assert_eq!(field_tokens("email_md5"), vec!["email", "md5"]);
+        assert_eq!(field_tokens("hashedEmail"), vec!["hashed", "email"]);
+        assert_eq!(field_tokens("user.ssn-sha256"), vec!["user", "ssn", "sha256"]);
+    }
+
+    #[test]
+    fn test_detects_hashed_fields() {
+        let text = format!("email_md5={} \"ssnHash\": \"{}\"", MD5, SHA256);
+        let found = find_hashed_pii(&text);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].pii_type.as_str(), found[0].digest.as_str()), ("EMAIL", "md5"));
+        assert_eq!(found[0].confidence, CONFIDENCE_EXPLICIT);
+        assert_eq!(&text[found[0].start..found[0].end], MD5);
+        assert_eq!((found[1].field.as_str(), found[1].digest.as_str()), ("ssnHash", "sha256"));
+    }
+
+    #[test]
+    fn test_ignores_non_pii_fields_and_odd_lengths() {
+        let text = format!("commit={} etag: {} email_md5={}ab", SHA256, MD5, MD5);
+        assert!(find_hashed_pii(&text).is_empty());
+        // "zip" and "shipping" must not read as "ip"
+        assert!(find_hashed_pii(&format!("shipping_hash={}", MD5)).is_empty());
+
+        let found = find_hashed_pii(&format!("phone = {}", MD5));
+        assert_eq!(found[0].confidence, CONFIDENCE_IMPLIED);
+    }
+}
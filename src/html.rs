@@ -0,0 +1,89 @@
+//! HTML preview rendering with highlighted matches
+//!
+//! Everything that comes from the scanned document is escaped — the text
+//! between and inside marks, and the entity type in the attribute — so a
+//! document containing markup renders as text. Overlapping matches are
+//! reduced with `overlap::select_non_overlapping`, so marks never nest or
+//! interleave.
+
+use crate::overlap::{self, Span};
+
+/// Append `s` to `out` with HTML special characters escaped
+///
+/// Safe for both element content and double- or single-quoted attributes.
+pub fn escape_into(out: &mut String, s: &str) {
+    let mut last = 0;
+    for (i, b) in s.bytes().enumerate() {
+        let rep = match b {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            b'\'' => "&#x27;",
+            _ => continue,
+        };
+        out.push_str(&s[last..i]);
+        out.push_str(rep);
+        last = i + 1;
+    }
+    out.push_str(&s[last..]);
+}
+
+/// Render `text` as HTML with a `<mark data-entity=...>` around each span
+///
+/// Spans must be valid for `text` (see `overlap::in_bounds`). With
+/// `mark_class` every mark also gets that class attribute.
+pub fn render_html(text: &str, spans: Vec<Span>, mark_class: Option<&str>) -> String {
+    let spans = overlap::select_non_overlapping(spans);
+    let mut out = String::with_capacity(text.len() + text.len() / 8 + spans.len() * 48);
+    let mut last = 0;
+    for span in spans {
+        escape_into(&mut out, &text[last..span.start]);
+        out.push_str("<mark data-entity=\"");
+        escape_into(&mut out, &span.label);
+        out.push('"');
+        if let Some(class) = mark_class {
+            out.push_str(" class=\"");
+            escape_into(&mut out, class);
+            out.push('"');
+        }
+        out.push('>');
+        escape_into(&mut out, &text[span.start..span.end]);
+        out.push_str("</mark>");
+        last = span.end;
+    }
+    escape_into(&mut out, &text[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize, label: &str) -> Span {
+        Span { start, end, label: label.to_string() }
+    }
+
+    #[test]
+    fn test_escapes_everything_from_the_document() {
+        let text = "<b>x</b> & \"a@b.io\" <script>";
+        let email = text.find("a@b.io").unwrap();
+        let out = render_html(text, vec![span(email, email + 6, "EMAIL\"><script>")], None);
+        assert_eq!(
+            out,
+            "&lt;b&gt;x&lt;/b&gt; &amp; &quot;<mark data-entity=\"EMAIL&quot;&gt;&lt;script&gt;\">a@b.io</mark>&quot; &lt;script&gt;"
+        );
+    }
+
+    #[test]
+    fn test_overlaps_and_class() {
+        let text = "Zoë <zoe@example.com>";
+        let start = text.find("zoe@").unwrap();
+        let spans = vec![span(start, start + 15, "EMAIL"), span(start, start + 3, "NAME"), span(0, 4, "NAME")];
+        let out = render_html(text, spans, Some("pii"));
+        assert_eq!(
+            out,
+            "<mark data-entity=\"NAME\" class=\"pii\">Zoë</mark> &lt;<mark data-entity=\"EMAIL\" class=\"pii\">zoe@example.com</mark>&gt;"
+        );
+    }
+}
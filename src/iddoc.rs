@@ -0,0 +1,344 @@
+//! Identity document recognition for scanned forms
+//!
+//! No single value says "this is a W-2": the form is recognized by its
+//! layout. Each template lists the printed field labels that co-occur on
+//! one document type (W-2, I-9, driver's license) and the entity types its
+//! values carry. Labels are found with one case-insensitive Aho-Corasick
+//! pass over the text with Unicode quotes and dashes folded and whitespace
+//! collapsed, so OCR spacing doesn't hide them. A document is reported
+//! once enough distinct field labels are present; its confidence grows
+//! with a form title, more fields and validated values of the expected
+//! types, and it lists every label and finding that contributed.
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::finding::Finding;
+use crate::normalize::Pipeline;
+
+/// Confidence contributed by a form title ("Wage and Tax Statement")
+const TITLE_WEIGHT: f32 = 0.4;
+
+/// Confidence contributed by field labels, at FULL_FIELDS distinct labels
+const FIELD_WEIGHT: f32 = 0.4;
+
+/// Confidence contributed by validated values, at every expected type
+const VALUE_WEIGHT: f32 = 0.2;
+
+/// Distinct field labels for the full FIELD_WEIGHT
+const FULL_FIELDS: usize = 6;
+
+/// Layout of one identity document type
+struct Template {
+    doc_type: &'static str,
+    /// Form titles, lowercase
+    titles: &'static [&'static str],
+    /// Printed field labels, lowercase
+    fields: &'static [&'static str],
+    /// Entity types of the values the form carries
+    entity_types: &'static [&'static str],
+    /// Distinct field labels needed before the document is reported
+    min_fields: usize,
+}
+
+const TEMPLATES: &[Template] = &[
+    Template {
+        doc_type: "W2",
+        titles: &["wage and tax statement", "form w-2", "w-2"],
+        fields: &[
+            "employee's social security number",
+            "employer identification number",
+            "employer's name",
+            "employee's first name",
+            "employee's name",
+            "wages, tips, other compensation",
+            "federal income tax withheld",
+            "social security wages",
+            "social security tax withheld",
+            "medicare wages and tips",
+            "medicare tax withheld",
+            "social security tips",
+            "allocated tips",
+            "dependent care benefits",
+            "nonqualified plans",
+            "control number",
+        ],
+        entity_types: &["SSN", "TAX_ID", "NAME", "EMPLOYER", "ADDRESS"],
+        min_fields: 3,
+    },
+    Template {
+        doc_type: "I9",
+        titles: &["employment eligibility verification", "form i-9", "i-9"],
+        fields: &[
+            "u.s. citizenship and immigration services",
+            "uscis number",
+            "alien registration number",
+            "a-number",
+            "a citizen of the united states",
+            "noncitizen national of the united states",
+            "lawful permanent resident",
+            "noncitizen authorized to work",
+            "employee's social security number",
+            "date of birth",
+            "signature of employee",
+            "first day of employment",
+            "list a",
+            "list b",
+            "list c",
+            "document title",
+            "issuing authority",
+        ],
+        entity_types: &["SSN", "A_NUMBER", "PASSPORT", "GREEN_CARD_NUMBER", "DRIVERS_LICENSE", "NAME", "DOB"],
+        min_fields: 3,
+    },
+    Template {
+        doc_type: "DRIVERS_LICENSE",
+        titles: &[
+            "driver license",
+            "driver's license",
+            "drivers license",
+            "driving licence",
+            "commercial driver license",
+        ],
+        fields: &[
+            "dl", "dln", "lic no", "license no", "class", "exp", "expires", "iss", "issued", "dob", "date of birth",
+            "sex", "hgt", "height", "wgt", "weight", "eyes", "hair", "rstr", "restrictions", "end", "endorsements",
+            "donor", "veteran", "dd",
+        ],
+        entity_types: &["DRIVERS_LICENSE", "NAME", "DOB", "DATE_DOB", "ADDRESS"],
+        // Short labels like "EXP" and "SEX" are common words on their own
+        min_fields: 4,
+    },
+];
+
+/// A label of some template: (template index, is a title)
+type LabelUse = (usize, bool);
+
+/// One automaton over every template label
+struct LabelIndex {
+    automaton: AhoCorasick,
+    /// Per automaton pattern, the templates using that label
+    uses: Vec<Vec<LabelUse>>,
+    /// Folds quotes and dashes and collapses whitespace before labels are searched
+    normalizer: Pipeline,
+}
+
+static LABELS: Lazy<LabelIndex> = Lazy::new(|| {
+    let mut by_label: BTreeMap<&str, Vec<LabelUse>> = BTreeMap::new();
+    for (idx, template) in TEMPLATES.iter().enumerate() {
+        for &title in template.titles {
+            by_label.entry(title).or_default().push((idx, true));
+        }
+        for &field in template.fields {
+            by_label.entry(field).or_default().push((idx, false));
+        }
+    }
+    let automaton = AhoCorasickBuilder::new()
+        .ascii_case_insensitive(true)
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(by_label.keys())
+        .expect("built-in document labels compile");
+    let stages = ["unicode_fold", "collapse_whitespace"].map(String::from);
+    LabelIndex {
+        automaton,
+        uses: by_label.into_values().collect(),
+        normalizer: Pipeline::from_names(&stages).expect("built-in normalization stages"),
+    }
+});
+
+/// A field label found in the input
+#[pyclass]
+#[derive(Clone)]
+pub struct DocumentLabel {
+    /// Label text as it appears in the input
+    #[pyo3(get)]
+    pub text: String,
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+    /// Whether the label is a form title rather than a field
+    #[pyo3(get)]
+    pub title: bool,
+}
+
+#[pymethods]
+impl DocumentLabel {
+    fn __repr__(&self) -> String {
+        format!("DocumentLabel(text='{}', pos={}:{}, title={})", self.text, self.start, self.end, self.title)
+    }
+}
+
+/// A recognized identity document and the evidence for it
+#[pyclass]
+#[derive(Clone)]
+pub struct IdentityDocument {
+    /// "W2", "I9" or "DRIVERS_LICENSE"
+    #[pyo3(get)]
+    pub doc_type: String,
+    /// Title, field label and value evidence combined, in [0, 1]
+    #[pyo3(get)]
+    pub confidence: f32,
+    /// Start of the first piece of evidence
+    #[pyo3(get)]
+    pub start: usize,
+    /// End of the last piece of evidence
+    #[pyo3(get)]
+    pub end: usize,
+    /// Titles and field labels of this document type, in text order
+    #[pyo3(get)]
+    pub labels: Vec<DocumentLabel>,
+    /// Validated findings of the entity types the document carries
+    #[pyo3(get)]
+    pub findings: Vec<Finding>,
+}
+
+#[pymethods]
+impl IdentityDocument {
+    /// Entity type for policy rules keyed on findings: "IDENTITY_DOCUMENT"
+    #[getter]
+    fn entity_type(&self) -> &'static str {
+        "IDENTITY_DOCUMENT"
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "IdentityDocument(type={}, confidence={:.2}, labels={}, findings={})",
+            self.doc_type,
+            self.confidence,
+            self.labels.len(),
+            self.findings.len()
+        )
+    }
+}
+
+/// Label hits on word boundaries as (start, end, pattern index), in input offsets
+fn find_labels(text: &str) -> Vec<(usize, usize, usize)> {
+    let normalized = LABELS.normalizer.apply(text);
+    let bytes = normalized.text.as_bytes();
+    let is_word = |i: usize| bytes.get(i).is_some_and(|b| b.is_ascii_alphanumeric());
+    LABELS
+        .automaton
+        .find_iter(&normalized.text)
+        .filter(|m| !is_word(m.end()) && (m.start() == 0 || !is_word(m.start() - 1)))
+        .map(|m| {
+            let (start, end) = normalized.to_original(m.start(), m.end());
+            (start, end, m.pattern().as_usize())
+        })
+        .collect()
+}
+
+/// Recognize identity documents from the text and its findings
+///
+/// Returns documents reaching `min_confidence`, ordered by start offset.
+pub fn recognize(text: &str, findings: &[Finding], min_confidence: f32) -> Vec<IdentityDocument> {
+    let hits = find_labels(text);
+    let mut documents = Vec::new();
+    for (idx, template) in TEMPLATES.iter().enumerate() {
+        let mut labels = Vec::new();
+        let mut distinct_fields = BTreeSet::new();
+        let mut has_title = false;
+        for &(start, end, pattern) in &hits {
+            let Some(&(_, title)) = LABELS.uses[pattern].iter().find(|(t, _)| *t == idx) else {
+                continue;
+            };
+            if title {
+                has_title = true;
+            } else {
+                distinct_fields.insert(pattern);
+            }
+            labels.push(DocumentLabel { text: text[start..end].to_string(), start, end, title });
+        }
+        if distinct_fields.len() < template.min_fields {
+            continue;
+        }
+
+        let values: Vec<Finding> = findings
+            .iter()
+            .filter(|f| template.entity_types.contains(&f.entity_type.as_str()) && f.verdicts.values().all(|v| *v))
+            .cloned()
+            .collect();
+        let value_types: BTreeSet<&str> = values.iter().map(|f| f.entity_type.as_str()).collect();
+
+        let title_part = if has_title { TITLE_WEIGHT } else { 0.0 };
+        let field_part = FIELD_WEIGHT * (distinct_fields.len().min(FULL_FIELDS) as f32 / FULL_FIELDS as f32);
+        let value_part = VALUE_WEIGHT * (value_types.len() as f32 / template.entity_types.len() as f32);
+        let confidence = (title_part + field_part + value_part).min(1.0);
+        if confidence < min_confidence {
+            continue;
+        }
+
+        let spans = labels.iter().map(|l| (l.start, l.end)).chain(values.iter().map(|f| (f.start, f.end)));
+        let start = spans.clone().map(|(s, _)| s).min().unwrap_or(0);
+        let end = spans.map(|(_, e)| e).max().unwrap_or(0);
+        documents.push(IdentityDocument {
+            doc_type: template.doc_type.to_string(),
+            confidence,
+            start,
+            end,
+            labels,
+            findings: values,
+        });
+    }
+    documents.sort_by(|a, b| (a.start, &a.doc_type).cmp(&(b.start, &b.doc_type)));
+    documents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::RawMatch;
+
+    fn finding(text: &str, entity_type: &str, value: &str) -> Finding {
+        let start = text.find(value).unwrap();
+        Finding::from_raw(RawMatch {
+            pattern_id: 0,
+            start,
+            end: start + value.len(),
+            text: value.to_string(),
+            entity_type: entity_type.to_string(),
+            confidence: 0.9,
+            truncated: false,
+            context: None,
+        })
+    }
+
+    const W2: &str = "Form W-2  Wage and Tax Statement 2023\n\
+                      a Employee\u{2019}s social  security number 536-22-8745\n\
+                      b Employer identification number (EIN) 12-3456789\n\
+                      1 Wages, tips, other compensation 48500.00  2 Federal income tax withheld 5320.11\n\
+                      3 Social security wages 48500.00  5 Medicare wages and tips 48500.00";
+
+    #[test]
+    fn test_recognizes_w2() {
+        let findings = vec![finding(W2, "SSN", "536-22-8745"), finding(W2, "TAX_ID", "12-3456789")];
+        let docs = recognize(W2, &findings, 0.0);
+        assert_eq!(docs.len(), 1);
+        let doc = &docs[0];
+        assert_eq!(doc.doc_type, "W2");
+        assert!(doc.labels.iter().any(|l| l.title && l.text == "Form W-2"));
+        // Curly apostrophe and doubled space are folded before matching
+        assert!(doc.labels.iter().any(|l| l.text == "Employee\u{2019}s social  security number"));
+        assert_eq!(doc.findings.len(), 2);
+        // Title, all six fields, two of five value types
+        assert!((doc.confidence - (TITLE_WEIGHT + FIELD_WEIGHT + VALUE_WEIGHT * 0.4)).abs() < 1e-6);
+        let last_label = W2.find("Medicare wages and tips").unwrap() + "Medicare wages and tips".len();
+        assert_eq!((doc.start, doc.end), (0, last_label));
+    }
+
+    #[test]
+    fn test_needs_co_occurring_fields() {
+        let prose = "Please mail your W-2 and a copy of your driver's license; the class starts soon.";
+        assert!(recognize(prose, &[], 0.0).is_empty());
+
+        let license = "DRIVER LICENSE  DL A1234567  CLASS C  DOB 01/02/1980  EXP 01/02/2030  SEX F  HGT 5-06  EYES BRN";
+        let docs = recognize(license, &[], 0.5);
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].doc_type, "DRIVERS_LICENSE");
+        assert!(docs[0].findings.is_empty());
+        // A higher bar drops the same document
+        assert!(recognize(license, &[], 0.9).is_empty());
+    }
+}
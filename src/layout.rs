@@ -0,0 +1,87 @@
+//! Shared pieces for record-structured (fixed-width) detectors
+//!
+//! Offsets alone are unactionable in fixed-width exports: the useful
+//! answer is "record 812, field ssn". Structural detectors report a
+//! `FieldMatch` carrying the record index and field name alongside the
+//! usual byte span.
+
+use pyo3::prelude::*;
+
+/// A finding attributed to a named field of a structured record
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct FieldMatch {
+    /// Zero-based index of the record in the input
+    #[pyo3(get)]
+    pub record: usize,
+    /// Field name from the record layout
+    #[pyo3(get)]
+    pub field: String,
+    #[pyo3(get)]
+    pub entity_type: String,
+    /// Byte span of the value in the whole input (padding excluded)
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+    #[pyo3(get)]
+    pub text: String,
+    #[pyo3(get)]
+    pub confidence: f32,
+}
+
+#[pymethods]
+impl FieldMatch {
+    fn __repr__(&self) -> String {
+        format!(
+            "FieldMatch(record={}, field={}, entity_type={}, pos={}:{})",
+            self.record, self.field, self.entity_type, self.start, self.end
+        )
+    }
+}
+
+/// Span of a fixed-width field with space padding trimmed
+///
+/// `start` and `len` are relative to `record`; the returned span is too.
+/// None if the field lies past the end of the record or is blank.
+pub fn trimmed_span(record: &str, start: usize, len: usize) -> Option<(usize, usize)> {
+    let end = (start + len).min(record.len());
+    let raw = record.get(start..end)?;
+    let lead = raw.len() - raw.trim_start_matches(' ').len();
+    let trimmed = raw.trim_matches(' ');
+    (!trimmed.is_empty()).then(|| (start + lead, start + lead + trimmed.len()))
+}
+
+/// Records of a line-delimited input with their byte offsets
+///
+/// Line terminators ("\n" or "\r\n") are excluded from each record.
+pub fn lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+    text.split_inclusive('\n').map(move |line| {
+        let start = offset;
+        offset += line.len();
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        (start, line.strip_suffix('\r').unwrap_or(line))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trimmed_span() {
+        let record = "AB  JOHN    X";
+        assert_eq!(trimmed_span(record, 2, 8), Some((4, 8)));
+        assert_eq!(trimmed_span(record, 8, 2), None);
+        assert_eq!(trimmed_span(record, 12, 5), Some((12, 13)));
+        assert_eq!(trimmed_span(record, 20, 5), None);
+    }
+
+    #[test]
+    fn test_lines_keep_offsets() {
+        let text = "one\r\ntwo\nthree";
+        let got: Vec<(usize, &str)> = lines(text).collect();
+        assert_eq!(got, [(0, "one"), (5, "two"), (9, "three")]);
+    }
+}
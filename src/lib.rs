@@ -6,61 +6,171 @@
 
 use pyo3::prelude::*;
 
+mod allowlist;
+mod annotate;
+mod benchmark;
+mod bytescan;
+mod capabilities;
+mod checkdigit;
+mod config;
+mod cursor;
+mod debug;
+mod detmath;
+mod dictionary;
+mod domain;
+mod envfile;
+mod evaluate;
+mod feedback;
+mod finding;
+mod fixedwidth;
+mod functions;
+mod grapheme;
+mod hashed;
+mod html;
+mod iddoc;
+mod layout;
+mod literal;
+mod logformat;
 mod matcher;
+mod metro2;
+mod national;
+mod network;
+mod noise;
+mod normalize;
+mod ocr;
+mod overlap;
+mod pairs;
+mod pool;
+mod postal;
+mod postprocess;
+mod pseudonym;
+mod redact;
+mod regions;
+mod repair;
+mod scratch;
+mod segment;
+mod serial;
+mod signing;
+mod stats;
+mod stream;
+mod summary;
+mod tail;
+mod taxonomy;
+mod twopass;
 mod validators;
+mod warmup;
 
+use benchmark::BenchmarkResult;
+use bytescan::ByteScanResult;
+use cursor::ScanCursor;
+use debug::{MatchTrace, TraceStep};
+use dictionary::DictionaryMatcher;
+use envfile::EnvSecret;
+use evaluate::{EntityScore, EvaluationReport};
+use finding::{Finding, GroupedFinding};
+use fixedwidth::FixedWidthScanner;
+use functions::*;
+use hashed::HashedPii;
+use iddoc::{DocumentLabel, IdentityDocument};
+use layout::FieldMatch;
+use logformat::LogScanner;
 use matcher::{PatternMatcher, RawMatch};
+use pairs::{LabeledPair, PairExtractor};
+use pool::ScannerPool;
+use pseudonym::Pseudonymizer;
+use redact::Redactor;
+use signing::SignedResult;
+use stats::SlowPattern;
+use stream::StreamScanner;
+use summary::EntitySummary;
+use tail::TailScanner;
+use taxonomy::{DocumentRisk, Taxonomy};
 
 /// OpenLabels native extension module
 #[pymodule]
 fn _rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PatternMatcher>()?;
     m.add_class::<RawMatch>()?;
+    m.add_class::<ByteScanResult>()?;
+    m.add_class::<ScanCursor>()?;
+    m.add_class::<SlowPattern>()?;
+    m.add_class::<StreamScanner>()?;
+    m.add_class::<TailScanner>()?;
+    m.add_class::<ScannerPool>()?;
+    m.add_class::<FixedWidthScanner>()?;
+    m.add_class::<LogScanner>()?;
+    m.add_class::<Finding>()?;
+    m.add_class::<GroupedFinding>()?;
+    m.add_class::<EntitySummary>()?;
+    m.add_class::<SignedResult>()?;
+    m.add_class::<IdentityDocument>()?;
+    m.add_class::<DocumentLabel>()?;
+    m.add_class::<EvaluationReport>()?;
+    m.add_class::<EntityScore>()?;
+    m.add_class::<Taxonomy>()?;
+    m.add_class::<DocumentRisk>()?;
+    m.add_class::<DictionaryMatcher>()?;
+    m.add_class::<HashedPii>()?;
+    m.add_class::<EnvSecret>()?;
+    m.add_class::<FieldMatch>()?;
+    m.add_class::<Pseudonymizer>()?;
+    m.add_class::<Redactor>()?;
+    m.add_class::<PairExtractor>()?;
+    m.add_class::<LabeledPair>()?;
+    m.add_class::<MatchTrace>()?;
+    m.add_class::<TraceStep>()?;
+    m.add_class::<BenchmarkResult>()?;
 
     // Validation functions
     m.add_function(wrap_pyfunction!(validate_luhn, m)?)?;
     m.add_function(wrap_pyfunction!(validate_ssn_format, m)?)?;
+    m.add_function(wrap_pyfunction!(classify_ssn, m)?)?;
     m.add_function(wrap_pyfunction!(validate_phone_format, m)?)?;
     m.add_function(wrap_pyfunction!(validate_ipv4_format, m)?)?;
     m.add_function(wrap_pyfunction!(is_private_ip, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_cidr, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_host_port, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_domain, m)?)?;
+    m.add_function(wrap_pyfunction!(classify_hostname, m)?)?;
+    m.add_function(wrap_pyfunction!(registrable_domain, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_postal, m)?)?;
+    m.add_function(wrap_pyfunction!(score_serial, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_teudat_zehut, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_amka, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_nhs_number, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_nir, m)?)?;
 
-    // Utility
-    m.add_function(wrap_pyfunction!(is_native_available, m)?)?;
-    Ok(())
-}
+    // Check-digit computation
+    m.add_function(wrap_pyfunction!(luhn_checksum_digit, m)?)?;
+    m.add_function(wrap_pyfunction!(mod97_checksum_digits, m)?)?;
+    m.add_function(wrap_pyfunction!(verhoeff_checksum_digit, m)?)?;
+    m.add_function(wrap_pyfunction!(iban_check_digits, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_mod97, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_iban, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_verhoeff, m)?)?;
 
-/// Validate credit card number using Luhn algorithm
-#[pyfunction]
-fn validate_luhn(number: &str) -> bool {
-    validators::luhn(number)
-}
-
-/// Validate SSN format (not context)
-#[pyfunction]
-fn validate_ssn_format(ssn: &str) -> bool {
-    validators::ssn_format(ssn)
-}
+    // Detectors
+    m.add_function(wrap_pyfunction!(find_hashed_pii, m)?)?;
+    m.add_function(wrap_pyfunction!(find_metro2, m)?)?;
+    m.add_function(wrap_pyfunction!(find_env_secrets, m)?)?;
 
-/// Validate US phone number format
-#[pyfunction]
-fn validate_phone_format(phone: &str) -> bool {
-    validators::phone_format(phone)
-}
+    // Text utilities
+    m.add_function(wrap_pyfunction!(segment_text, m)?)?;
+    m.add_function(wrap_pyfunction!(classify_regions, m)?)?;
+    m.add_function(wrap_pyfunction!(context_window, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_text, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_log_line, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_text, m)?)?;
+    m.add_function(wrap_pyfunction!(render_html, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_results, m)?)?;
+    m.add_function(wrap_pyfunction!(match_debug, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_signed_result, m)?)?;
 
-/// Validate IPv4 address format
-#[pyfunction]
-fn validate_ipv4_format(ip: &str) -> bool {
-    validators::ipv4_format(ip)
-}
-
-/// Check if IP is private/reserved (likely false positive)
-#[pyfunction]
-fn is_private_ip(ip: &str) -> bool {
-    validators::is_private_ip(ip)
-}
-
-/// Check if native extension is working
-#[pyfunction]
-fn is_native_available() -> bool {
-    true
+    // Utility
+    m.add_function(wrap_pyfunction!(is_native_available, m)?)?;
+    m.add_function(wrap_pyfunction!(native_capabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(simd_tier, m)?)?;
+    m.add_function(wrap_pyfunction!(benchmark_self, m)?)?;
+    m.add_function(wrap_pyfunction!(benchmark_corpus, m)?)?;
+    Ok(())
 }
@@ -0,0 +1,202 @@
+//! Large literal alternations compiled to Aho-Corasick
+//!
+//! Keyword packs often arrive as one huge alternation of plain terms,
+//! e.g. `\b(?:acme|globex|initech|...)\b` with thousands of entries. The
+//! regex engines either hit their size limits compiling these or scan
+//! them slowly. A pattern that is nothing but literal terms (optionally in
+//! one group and between `\b` assertions) is matched with an Aho-Corasick
+//! automaton instead, reproducing the regex's leftmost-first matches.
+//!
+//! Anything else (classes, repetitions, flags such as `(?i)`) keeps the
+//! regex engine, as do alternations too small to benefit.
+
+use aho_corasick::{AhoCorasick, Input, MatchKind};
+use regex_syntax::ast::{parse::Parser, Ast, AssertionKind, GroupKind};
+
+/// Fewest terms for which an alternation leaves the regex engine
+pub const MIN_LITERAL_TERMS: usize = 100;
+
+/// A pure literal alternation pattern
+pub struct LiteralAlternation {
+    automaton: AhoCorasick,
+    /// `\b` before the terms
+    word_start: bool,
+    /// `\b` after the terms
+    word_end: bool,
+}
+
+impl LiteralAlternation {
+    /// Compile `pattern` if it is a literal alternation of at least
+    /// MIN_LITERAL_TERMS terms whose capture group `group_idx` (if not 0)
+    /// is the whole match
+    pub fn parse(pattern: &str, group_idx: usize) -> Option<Self> {
+        let ast = Parser::new().parse(pattern).ok()?;
+
+        // Optional \b on either side
+        let (word_start, body, word_end) = match &ast {
+            Ast::Concat(concat) => match concat.asts.as_slice() {
+                [a, body, b] if is_word_boundary(a) && is_word_boundary(b) => (true, body, true),
+                [a, body] if is_word_boundary(a) => (true, body, false),
+                [body, b] if is_word_boundary(b) => (false, body, true),
+                _ => (false, &ast, false),
+            },
+            _ => (false, &ast, false),
+        };
+
+        // Optional group around the alternation; \b is zero-width, so a
+        // capture here spans exactly the match
+        let (group, body) = match body {
+            Ast::Group(group) => match &group.kind {
+                GroupKind::CaptureIndex(idx) => (*idx as usize, &*group.ast),
+                GroupKind::CaptureName { name, .. } => (name.index as usize, &*group.ast),
+                GroupKind::NonCapturing(flags) if flags.items.is_empty() => (0, &*group.ast),
+                GroupKind::NonCapturing(_) => return None,
+            },
+            body => (0, body),
+        };
+        if group_idx != 0 && group_idx != group {
+            return None;
+        }
+
+        let Ast::Alternation(alternation) = body else {
+            return None;
+        };
+        if alternation.asts.len() < MIN_LITERAL_TERMS {
+            return None;
+        }
+        let terms: Vec<String> = alternation.asts.iter().map(literal_term).collect::<Option<_>>()?;
+
+        let kind = if word_start || word_end {
+            // Boundaries can reject the preferred term at a position, so
+            // every overlapping candidate is needed (see find_iter)
+            MatchKind::Standard
+        } else {
+            MatchKind::LeftmostFirst
+        };
+        let automaton = AhoCorasick::builder().match_kind(kind).build(&terms).ok()?;
+        Some(Self {
+            automaton,
+            word_start,
+            word_end,
+        })
+    }
+
+    /// Leftmost-first, non-overlapping matches within text[start..end]
+    ///
+    /// Word boundaries are checked against the whole text, like regex
+    /// look-around on a ranged search.
+    pub fn find_iter(&self, text: &str, start: usize, end: usize, mut found: impl FnMut(usize, usize)) {
+        let input = Input::new(text).span(start..end);
+        if !self.word_start && !self.word_end {
+            for m in self.automaton.find_iter(input) {
+                found(m.start(), m.end());
+            }
+            return;
+        }
+
+        // At the leftmost start with a valid term, the regex picks the
+        // earliest-listed term: sort bounded candidates by (start, term)
+        let mut candidates: Vec<(usize, usize, usize)> = self
+            .automaton
+            .find_overlapping_iter(input)
+            .filter(|m| {
+                (!self.word_start || at_word_boundary(text, m.start()))
+                    && (!self.word_end || at_word_boundary(text, m.end()))
+            })
+            .map(|m| (m.start(), m.pattern().as_usize(), m.end()))
+            .collect();
+        candidates.sort_unstable();
+
+        let mut last_end = start;
+        for (s, _, e) in candidates {
+            if s >= last_end {
+                found(s, e);
+                last_end = e;
+            }
+        }
+    }
+
+    /// Number of alternation terms
+    pub fn term_count(&self) -> usize {
+        self.automaton.patterns_len()
+    }
+
+    /// Heap size of the automaton
+    pub fn memory_usage(&self) -> usize {
+        self.automaton.memory_usage()
+    }
+}
+
+fn is_word_boundary(ast: &Ast) -> bool {
+    matches!(ast, Ast::Assertion(a) if a.kind == AssertionKind::WordBoundary)
+}
+
+/// The text of an alternation branch made only of literal characters
+fn literal_term(ast: &Ast) -> Option<String> {
+    match ast {
+        Ast::Literal(lit) => Some(lit.c.to_string()),
+        Ast::Concat(concat) => concat
+            .asts
+            .iter()
+            .map(|a| match a {
+                Ast::Literal(lit) => Some(lit.c),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Unicode `\b`: word character on exactly one side of `pos`
+fn at_word_boundary(text: &str, pos: usize) -> bool {
+    let before = text[..pos].chars().next_back().is_some_and(regex_syntax::is_word_character);
+    let after = text[pos..].chars().next().is_some_and(regex_syntax::is_word_character);
+    before != after
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex_automata::meta::Regex;
+
+    fn terms(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("term{}", i)).collect()
+    }
+
+    /// The automaton finds exactly the regex engine's spans
+    fn assert_same_as_regex(pattern: &str, text: &str) {
+        let alt = LiteralAlternation::parse(pattern, 0).expect("literal alternation");
+        let mut ac = Vec::new();
+        alt.find_iter(text, 0, text.len(), |s, e| ac.push((s, e)));
+        let re: Vec<(usize, usize)> =
+            Regex::new(pattern).unwrap().find_iter(text).map(|m| (m.start(), m.end())).collect();
+        assert_eq!(ac, re, "pattern ...{}", &pattern[pattern.len() - 30..]);
+    }
+
+    #[test]
+    fn test_recognizes_literal_alternations() {
+        let body = terms(MIN_LITERAL_TERMS).join("|");
+        assert!(LiteralAlternation::parse(&body, 0).is_some());
+        assert!(LiteralAlternation::parse(&format!(r"\b(?:{})\b", body), 0).is_some());
+        assert!(LiteralAlternation::parse(&format!(r"\b({})\b", body), 1).is_some());
+
+        // Too few terms, flags, classes and other groups stay regex
+        assert!(LiteralAlternation::parse(&terms(3).join("|"), 0).is_none());
+        assert!(LiteralAlternation::parse(&format!("(?i){}", body), 0).is_none());
+        assert!(LiteralAlternation::parse(&format!(r"{}|\d+", body), 0).is_none());
+        assert!(LiteralAlternation::parse(&format!(r"\b({})\b", body), 2).is_none());
+        assert!(LiteralAlternation::parse(&format!("{}|", body), 0).is_none());
+    }
+
+    #[test]
+    fn test_matches_agree_with_regex() {
+        let mut words = terms(MIN_LITERAL_TERMS);
+        words.extend(["foo".to_string(), "foobar".to_string(), "a.b".to_string()]);
+        let body = words.join("|").replace("a.b", r"a\.b");
+        let text = "foobar foo xfoo term12 term1x a.b term99_ term7";
+
+        for pattern in [body.clone(), format!(r"\b(?:{})\b", body), format!(r"\b(?:{})", body)] {
+            assert_same_as_regex(&pattern, text);
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! Syslog, CEF and LEEF aware scanning
+//!
+//! SIEM exports pack structured fields into one line: a syslog header,
+//! then a CEF or LEEF payload with pipe-delimited header fields and
+//! key=value extensions. Scanning the raw line gives offsets into a blob;
+//! splitting the fields first lets each match report its field key
+//! (`suser`, `dst`, `device_vendor`, ...).
+//!
+//! Lines that match no supported format are scanned whole as "message".
+
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use regex::Regex;
+use std::sync::Arc;
+
+use crate::layout::{self, FieldMatch};
+use crate::matcher::{find_matches_impl, CompiledPatterns, PatternMatcher};
+
+/// CEF extension keys: `key=` at the start or after whitespace
+static CEF_KEY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|[ \t])([A-Za-z0-9_.\[\]-]+)=").expect("CEF key regex"));
+
+/// CEF header fields after the version, in order
+const CEF_HEADER: &[&str] = &["device_vendor", "device_product", "device_version", "signature_id", "name", "severity"];
+
+/// LEEF header fields after the version, in order
+const LEEF_HEADER: &[&str] = &["vendor", "product", "product_version", "event_id"];
+
+/// RFC 5424 header fields after the version, in order ("-" is nil)
+const SYSLOG_HEADER: &[&str] = &["timestamp", "hostname", "app_name", "procid", "msgid"];
+
+/// RFC 3164 timestamp month abbreviations
+const MONTHS: &[&str] = &["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Log line format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Auto,
+    Syslog,
+    Cef,
+    Leef,
+}
+
+impl LogFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "syslog" => Some(Self::Syslog),
+            "cef" => Some(Self::Cef),
+            "leef" => Some(Self::Leef),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Syslog => "syslog",
+            Self::Cef => "cef",
+            Self::Leef => "leef",
+        }
+    }
+}
+
+/// A named field of a log line; offsets are relative to the line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogField {
+    pub key: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+fn push_field(fields: &mut Vec<LogField>, key: &str, start: usize, end: usize) {
+    if end > start {
+        fields.push(LogField { key: key.to_string(), start, end });
+    }
+}
+
+/// Parse a syslog header (RFC 5424 or RFC 3164)
+///
+/// Relays often drop the "<PRI>" of RFC 3164 lines, so it is optional
+/// there. Returns the header fields and the offset where the message starts.
+fn parse_syslog(line: &str) -> Option<(Vec<LogField>, usize)> {
+    let mut pos = 0;
+    let mut has_pri = false;
+    if let Some(rest) = line.strip_prefix('<') {
+        let close = rest.find('>')?;
+        if close == 0 || close > 3 || !rest[..close].bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        pos = close + 2;
+        has_pri = true;
+    }
+    let mut fields = Vec::new();
+    let bytes = line.as_bytes();
+
+    if has_pri && bytes.get(pos).is_some_and(|b| b.is_ascii_digit()) && bytes.get(pos + 1) == Some(&b' ') {
+        // RFC 5424: VERSION SP TIMESTAMP SP HOSTNAME SP APP-NAME SP PROCID SP MSGID SP SD [SP MSG]
+        pos += 2;
+        for key in SYSLOG_HEADER {
+            let end = line[pos..].find(' ').map_or(line.len(), |i| pos + i);
+            if &line[pos..end] != "-" {
+                push_field(&mut fields, key, pos, end);
+            }
+            pos = (end + 1).min(line.len());
+        }
+        pos = parse_structured_data(line, pos, &mut fields);
+        if bytes.get(pos) == Some(&b' ') {
+            pos += 1;
+        }
+        return Some((fields, pos));
+    }
+
+    // RFC 3164: "Mmm dd hh:mm:ss HOSTNAME TAG: MSG"
+    let timestamp = line.get(pos..pos + 15)?;
+    let ts = timestamp.as_bytes();
+    if !timestamp.is_ascii() || !MONTHS.contains(&&timestamp[..3]) || ts[3] != b' ' || ts[9] != b':' || ts[12] != b':' {
+        return None;
+    }
+    push_field(&mut fields, "timestamp", pos, pos + 15);
+    pos += 16;
+    if pos >= line.len() {
+        return Some((fields, line.len()));
+    }
+    let host_end = line[pos..].find(' ').map_or(line.len(), |i| pos + i);
+    push_field(&mut fields, "hostname", pos, host_end);
+    pos = (host_end + 1).min(line.len());
+    if let Some(colon) = line[pos..].find(": ").filter(|&i| i <= 48) {
+        let tag = &line[pos..pos + colon];
+        if tag.bytes().all(|b| b.is_ascii_alphanumeric() || b"_-./[]".contains(&b)) {
+            let app_end = tag.find('[').map_or(pos + colon, |i| pos + i);
+            push_field(&mut fields, "app_name", pos, app_end);
+            pos += colon + 2;
+        }
+    }
+    Some((fields, pos))
+}
+
+/// Parse RFC 5424 structured data starting at `pos`; returns the offset after it
+///
+/// Each parameter becomes a field keyed "sd_id.param".
+fn parse_structured_data(line: &str, mut pos: usize, fields: &mut Vec<LogField>) -> usize {
+    let bytes = line.as_bytes();
+    if bytes.get(pos) == Some(&b'-') {
+        return pos + 1;
+    }
+    while bytes.get(pos) == Some(&b'[') {
+        let id_start = pos + 1;
+        let Some(id_len) = line[id_start..].find([' ', ']']) else {
+            return line.len();
+        };
+        let sd_id = &line[id_start..id_start + id_len];
+        pos = id_start + id_len;
+        loop {
+            while bytes.get(pos) == Some(&b' ') {
+                pos += 1;
+            }
+            match bytes.get(pos) {
+                Some(b']') => {
+                    pos += 1;
+                    break;
+                }
+                None => return line.len(),
+                _ => {}
+            }
+            let Some(eq) = line[pos..].find("=\"") else {
+                return line.len();
+            };
+            let name = &line[pos..pos + eq];
+            let value_start = pos + eq + 2;
+            // Values escape '"', '\' and ']' with a backslash
+            let mut i = value_start;
+            while i < bytes.len() && bytes[i] != b'"' {
+                if bytes[i] == b'\\' {
+                    i += 1;
+                }
+                i += line[i.min(line.len())..].chars().next().map_or(1, char::len_utf8);
+            }
+            let value_end = i.min(line.len());
+            push_field(fields, &format!("{}.{}", sd_id, name), value_start, value_end);
+            pos = (value_end + 1).min(line.len());
+        }
+    }
+    pos
+}
+
+/// Offsets of '|' separators in a CEF header, honouring "\|" escapes
+fn cef_separators(s: &str, count: usize) -> Vec<usize> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(count);
+    let mut i = 0;
+    while i < bytes.len() && out.len() < count {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'|' => out.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Parse a CEF payload starting at `start` ("CEF:0|...")
+fn parse_cef(line: &str, start: usize, fields: &mut Vec<LogField>) -> bool {
+    let body = &line[start + 4..];
+    let seps = cef_separators(body, CEF_HEADER.len() + 1);
+    if seps.len() != CEF_HEADER.len() + 1 {
+        return false;
+    }
+    let base = start + 4;
+    for (i, key) in CEF_HEADER.iter().enumerate() {
+        push_field(fields, key, base + seps[i] + 1, base + seps[i + 1]);
+    }
+    let ext_start = base + seps[CEF_HEADER.len()] + 1;
+    let extension = &line[ext_start..];
+    let keys: Vec<(&str, usize, usize)> = CEF_KEY
+        .captures_iter(extension)
+        .filter_map(|c| {
+            let key = c.get(1)?;
+            Some((key.as_str(), key.start(), c.get(0)?.end()))
+        })
+        .collect();
+    for (i, &(key, _, value_start)) in keys.iter().enumerate() {
+        let value_end = keys.get(i + 1).map_or(extension.len(), |&(_, next, _)| next);
+        let value = extension[value_start..value_end].trim_end();
+        push_field(fields, key, ext_start + value_start, ext_start + value_start + value.len());
+    }
+    true
+}
+
+/// Attribute delimiter declared in a LEEF 2.0 header ("^", "x09", "0x5E")
+fn leef_delimiter(spec: &str) -> Option<char> {
+    let hex = spec.strip_prefix("0x").or_else(|| spec.strip_prefix('x'));
+    match hex {
+        Some(hex) if !hex.is_empty() => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+        _ => {
+            let mut chars = spec.chars();
+            match (chars.next(), chars.next()) {
+                (None, _) => Some('\t'),
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Parse a LEEF payload starting at `start` ("LEEF:1.0|..." or "LEEF:2.0|...")
+fn parse_leef(line: &str, start: usize, fields: &mut Vec<LogField>) -> bool {
+    let base = start + 5;
+    let body = &line[base..];
+    let v2 = body.starts_with('2');
+    let header_count = LEEF_HEADER.len() + 1 + usize::from(v2);
+    let seps: Vec<usize> = body.match_indices('|').map(|(i, _)| i).take(header_count).collect();
+    if seps.len() != header_count {
+        return false;
+    }
+    for (i, key) in LEEF_HEADER.iter().enumerate() {
+        push_field(fields, key, base + seps[i] + 1, base + seps[i + 1]);
+    }
+    let delimiter = if v2 {
+        let spec = &body[seps[header_count - 2] + 1..seps[header_count - 1]];
+        match leef_delimiter(spec) {
+            Some(d) => d,
+            None => return false,
+        }
+    } else {
+        '\t'
+    };
+    let mut pos = base + seps[header_count - 1] + 1;
+    for attr in line[pos..].split(delimiter) {
+        if let Some(eq) = attr.find('=') {
+            push_field(fields, &attr[..eq], pos + eq + 1, pos + attr.len());
+        }
+        pos += attr.len() + delimiter.len_utf8();
+    }
+    true
+}
+
+/// Start of a "CEF:<digit>|" payload
+fn find_cef(line: &str) -> Option<usize> {
+    line.match_indices("CEF:").map(|(i, _)| i).find(|&i| {
+        let b = &line.as_bytes()[i + 4..];
+        b.len() > 1 && b[0].is_ascii_digit() && b[1] == b'|'
+    })
+}
+
+/// Start of a "LEEF:<version>|" payload
+fn find_leef(line: &str) -> Option<usize> {
+    line.match_indices("LEEF:").map(|(i, _)| i).find(|&i| {
+        line[i + 5..].split('|').next().is_some_and(|v| v == "1.0" || v == "2.0")
+    })
+}
+
+/// Split a log line into named fields
+///
+/// Returns the detected format and the fields (offsets relative to the
+/// line), or None if the line is not in the requested format.
+pub fn parse_line(line: &str, format: LogFormat) -> Option<(LogFormat, Vec<LogField>)> {
+    let mut fields = Vec::new();
+    let payload = |fmt: LogFormat| match fmt {
+        LogFormat::Cef => find_cef(line),
+        LogFormat::Leef => find_leef(line),
+        _ => None,
+    };
+    let detected = match format {
+        LogFormat::Auto => [LogFormat::Cef, LogFormat::Leef]
+            .into_iter()
+            .find_map(|f| payload(f).map(|start| (f, Some(start))))
+            .or_else(|| parse_syslog(line).map(|_| (LogFormat::Syslog, None)))?,
+        LogFormat::Syslog => (LogFormat::Syslog, None),
+        f => (f, Some(payload(f)?)),
+    };
+
+    match detected {
+        (LogFormat::Syslog, _) => {
+            let (header, msg_start) = parse_syslog(line)?;
+            fields.extend(header);
+            push_field(&mut fields, "msg", msg_start, line.len());
+        }
+        (fmt, Some(start)) => {
+            // A syslog header in front of the payload contributes its fields
+            if let Some((header, _)) = parse_syslog(&line[..start]) {
+                fields.extend(header);
+            }
+            let parsed = if fmt == LogFormat::Cef {
+                parse_cef(line, start, &mut fields)
+            } else {
+                parse_leef(line, start, &mut fields)
+            };
+            if !parsed {
+                return None;
+            }
+        }
+        _ => return None,
+    }
+    Some((detected.0, fields))
+}
+
+/// Scan each field of each log line with the compiled patterns
+pub fn scan_log(compiled: &CompiledPatterns, text: &str, format: LogFormat) -> Vec<FieldMatch> {
+    let mut found = Vec::new();
+    for (index, (offset, line)) in layout::lines(text).filter(|(_, l)| !l.is_empty()).enumerate() {
+        let fields = match parse_line(line, format) {
+            Some((_, fields)) => fields,
+            None => vec![LogField { key: "message".to_string(), start: 0, end: line.len() }],
+        };
+        for field in fields {
+            let base = offset + field.start;
+            for m in find_matches_impl(compiled, &line[field.start..field.end]) {
+                found.push(FieldMatch {
+                    record: index,
+                    field: field.key.clone(),
+                    entity_type: m.entity_type,
+                    start: base + m.start,
+                    end: base + m.end,
+                    text: m.text,
+                    confidence: m.confidence,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Parse a Python format argument
+pub fn format_arg(format: &str) -> PyResult<LogFormat> {
+    LogFormat::parse(format).ok_or_else(|| {
+        PyValueError::new_err(format!("unknown log format '{}': expected auto, syslog, cef or leef", format))
+    })
+}
+
+/// Pattern scanner for syslog / CEF / LEEF log lines
+#[pyclass]
+pub struct LogScanner {
+    compiled: Arc<CompiledPatterns>,
+    log_format: LogFormat,
+}
+
+#[pymethods]
+impl LogScanner {
+    /// Create a log scanner
+    ///
+    /// Args:
+    ///     matcher: PatternMatcher whose compiled patterns are shared
+    ///     format: "auto" (detect per line), "syslog", "cef" or "leef"
+    ///
+    /// Raises:
+    ///     ValueError: If the format is unknown
+    #[new]
+    #[pyo3(signature = (matcher, format="auto"))]
+    fn new(matcher: PyRef<PatternMatcher>, format: &str) -> PyResult<Self> {
+        Ok(Self {
+            compiled: matcher.compiled(),
+            log_format: format_arg(format)?,
+        })
+    }
+
+    /// Configured format name
+    #[getter]
+    fn format(&self) -> &'static str {
+        self.log_format.as_str()
+    }
+
+    /// Scan every field of every line
+    ///
+    /// Each FieldMatch has the line index as `record` and the field key
+    /// as `field`. Releases the GIL.
+    fn scan(&self, py: Python<'_>, text: &str) -> Vec<FieldMatch> {
+        py.allow_threads(|| scan_log(&self.compiled, text, self.log_format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::compile_patterns;
+
+    fn keyed<'a>(line: &'a str, fields: &'a [LogField]) -> Vec<(&'a str, &'a str)> {
+        fields.iter().map(|f| (f.key.as_str(), &line[f.start..f.end])).collect()
+    }
+
+    #[test]
+    fn test_cef_with_syslog_prefix() {
+        let line = "Sep 19 08:26:10 host1 CEF:0|Acme|Gate|1.0|100|Login|5|suser=jdoe src=10.0.0.5 msg=user jdoe logged in";
+        let (fmt, fields) = parse_line(line, LogFormat::Auto).unwrap();
+        assert_eq!(fmt, LogFormat::Cef);
+        let got = keyed(line, &fields);
+        assert!(got.contains(&("hostname", "host1")));
+        assert!(got.contains(&("device_vendor", "Acme")));
+        assert!(got.contains(&("suser", "jdoe")));
+        assert!(got.contains(&("src", "10.0.0.5")));
+        assert!(got.contains(&("msg", "user jdoe logged in")));
+    }
+
+    #[test]
+    fn test_cef_escaped_pipe_in_header() {
+        let line = r"CEF:0|Ac\|me|Gate|1.0|100|Login|5|dst=1.2.3.4";
+        let (_, fields) = parse_line(line, LogFormat::Cef).unwrap();
+        let got = keyed(line, &fields);
+        assert_eq!(got[0], ("device_vendor", r"Ac\|me"));
+        assert_eq!(got.last(), Some(&("dst", "1.2.3.4")));
+    }
+
+    #[test]
+    fn test_leef_versions() {
+        let v1 = "LEEF:1.0|Acme|IDS|2.0|4000|usrName=jdoe\tsrc=10.1.1.1";
+        let (fmt, fields) = parse_line(v1, LogFormat::Auto).unwrap();
+        assert_eq!(fmt, LogFormat::Leef);
+        assert_eq!(keyed(v1, &fields)[4..], [("usrName", "jdoe"), ("src", "10.1.1.1")]);
+
+        let v2 = "LEEF:2.0|Acme|IDS|2.0|4000|^|usrName=jdoe^email=j@x.org";
+        let (_, fields) = parse_line(v2, LogFormat::Leef).unwrap();
+        assert_eq!(keyed(v2, &fields)[4..], [("usrName", "jdoe"), ("email", "j@x.org")]);
+    }
+
+    #[test]
+    fn test_syslog_5424_structured_data() {
+        let line = r#"<165>1 2024-01-15T12:00:00Z web01 app - ID47 [meta user="jdoe" ip="10.0.0.9"] ssn 123-45-6789"#;
+        let (fmt, fields) = parse_line(line, LogFormat::Auto).unwrap();
+        assert_eq!(fmt, LogFormat::Syslog);
+        let got = keyed(line, &fields);
+        assert!(got.contains(&("hostname", "web01")));
+        assert!(got.contains(&("meta.user", "jdoe")));
+        assert!(got.contains(&("meta.ip", "10.0.0.9")));
+        assert_eq!(got.last(), Some(&("msg", "ssn 123-45-6789")));
+    }
+
+    #[test]
+    fn test_scan_reports_field_keys() {
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0)];
+        let compiled = compile_patterns(&patterns, false);
+        let text = "<13>Jan 15 12:00:00 db01 audit: ok\nCEF:0|A|B|1|2|N|3|cs1=123-45-6789 act=x\nplain 234-56-7890\n";
+        let found = scan_log(&compiled, text, LogFormat::Auto);
+        let got: Vec<(usize, &str, &str)> =
+            found.iter().map(|m| (m.record, m.field.as_str(), &text[m.start..m.end])).collect();
+        assert_eq!(got, [(1, "cs1", "123-45-6789"), (2, "message", "234-56-7890")]);
+    }
+}
@@ -1,25 +1,67 @@
 //! Pattern matching engine using Rust's regex crate
 //!
-//! Compiles all patterns once and reuses them across scans.
-//! Uses RegexSet for efficient multi-pattern matching.
+//! Compiles all patterns once per matcher and reuses them across scans.
+//! Uses a multi-pattern regex set for efficient "which patterns match?" checks.
 //! Supports batch processing with parallel execution via Rayon.
+//! Optionally runs a two-pass scan (see `twopass`) where only patterns
+//! triggered by a cheap literal pass are verified.
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
 
 use aho_corasick::AhoCorasick;
-use once_cell::sync::OnceCell;
+use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
 use rayon::prelude::*;
-use regex::{Regex, RegexSet, RegexSetBuilder};
-use std::collections::HashMap;
+use regex_automata::{meta, Anchored, Input, MatchKind};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::allowlist;
+use crate::bytescan::{match_spans, scan_bytes, ByteScanResult, Utf8Mode};
+use crate::config::{self, Value};
+use crate::cursor::ScanCursor;
+use crate::evaluate::{self, EvaluationReport, LabeledSpan, MatchMode};
+use crate::feedback::{self, FeedbackStore, Verdict};
+use crate::finding::{build_findings, group_findings, Finding, GroupedFinding};
+use crate::grapheme;
+use crate::iddoc::{self, IdentityDocument};
+use crate::literal::LiteralAlternation;
+use crate::noise::{self, NoiseSource};
+use crate::ocr;
+use crate::pairs::{LabeledPair, PairExtractor};
+use crate::postprocess::PostProcessors;
+use crate::redact;
+use crate::regions::{self, Region, RegionMask, ALL_REGIONS};
+use crate::repair::Repairs;
+use crate::scratch::{with_scratch, Scratch};
+use crate::stats::{ScanStats, SlowPattern, Watchdog};
+use crate::normalize::Pipeline;
+use crate::signing::{self, SignedResult};
+use crate::stream::{self, Chunking, OverlapDedup};
+use crate::summary::{self, EntitySummary};
+use crate::taxonomy::Taxonomy;
+use crate::twopass::{TwoPassPlan, DENSE_CANDIDATE_RATIO};
 
-/// Global compiled patterns (initialized once, reused forever)
-static COMPILED_PATTERNS: OnceCell<CompiledPatterns> = OnceCell::new();
+/// Stand-in for literal alternations in the RegexSet; matches nothing
+const NEVER_MATCHES: &str = r"[^\s\S]";
+
+/// How one pattern finds match positions
+enum Engine {
+    Regex(meta::Regex),
+    /// Large literal alternation run as Aho-Corasick (see `literal`)
+    Literals(LiteralAlternation),
+}
 
 /// Holds compiled regex patterns and metadata
-struct CompiledPatterns {
-    /// RegexSet for fast "which patterns match?" check
-    regex_set: RegexSet,
-    /// Individual compiled regexes for position extraction
-    individual_regexes: Vec<Regex>,
+pub(crate) struct CompiledPatterns {
+    /// Multi-pattern regex for fast "which patterns match?" check
+    regex_set: meta::Regex,
+    /// Individual compiled engines for position extraction
+    engines: Vec<Engine>,
+    /// Patterns run as literal automata, always swept (not in the RegexSet)
+    literal_patterns: Vec<usize>,
     /// Pattern metadata (entity_type, confidence, group_idx)
     metadata: Vec<PatternMetadata>,
     /// Map from pattern index to metadata index (for failed compilations)
@@ -27,10 +69,189 @@ struct CompiledPatterns {
     /// Aho-Corasick automaton for fast literal pre-filtering
     /// Contains common literals like "@", ".", "-" that most patterns need
     prefilter: Option<AhoCorasick>,
+    /// Literal triggers for two-pass scanning (None = single pass)
+    two_pass: Option<TwoPassPlan>,
+    /// Scan counters and the optional slow-pattern watchdog
+    pub(crate) stats: ScanStats,
+    /// Region kinds each pattern may match in (None = no pattern is restricted)
+    region_masks: Option<Vec<RegionMask>>,
+    /// Drop numeric matches inside hex dump regions
+    pub(crate) suppress_hexdump: bool,
+    /// Scan oversized texts as overlapping chunks (None = always whole)
+    pub(crate) chunking: Option<Chunking>,
+    /// Normalization stages run before matching (empty = scan the text as given)
+    pub(crate) normalizer: Pipeline,
+    /// Longest matched text copied into a result (None = always the whole match)
+    pub(crate) max_text_bytes: Option<usize>,
+}
+
+impl CompiledPatterns {
+    /// Restrict entity types to region kinds; unlisted types match anywhere
+    pub(crate) fn restrict_regions(&mut self, masks: &HashMap<String, RegionMask>) {
+        let per_pattern: Vec<RegionMask> = self
+            .metadata
+            .iter()
+            .map(|m| masks.get(&m.entity_type).copied().unwrap_or(ALL_REGIONS))
+            .collect();
+        self.region_masks = per_pattern.iter().any(|&m| m != ALL_REGIONS).then_some(per_pattern);
+    }
+
+    /// Whether a pattern only runs in some region kinds
+    fn is_region_restricted(&self, set_idx: usize) -> bool {
+        self.region_masks.as_ref().is_some_and(|masks| masks[set_idx] != ALL_REGIONS)
+    }
+
+    /// Which patterns match anywhere in text, as sorted indices in scratch.hits
+    ///
+    /// Literal alternations are always included; their sweep is the check.
+    fn set_matches(&self, text: &str, scratch: &mut Scratch) {
+        scratch.collect_hits(self.regex_set.pattern_len(), |set| {
+            self.regex_set.which_overlapping_matches(&Input::new(text), set)
+        });
+        if !self.literal_patterns.is_empty() {
+            scratch.hits.extend_from_slice(&self.literal_patterns);
+            scratch.hits.sort_unstable();
+        }
+    }
+
+    /// Run every engine over `text` without recording stats or returning matches
+    ///
+    /// Primes the calling thread's regex caches and scratch buffers. Every
+    /// pattern is swept, not only the ones the RegexSet reports.
+    pub(crate) fn warm(&self, text: &str) {
+        if let Some(prefilter) = &self.prefilter {
+            let _ = prefilter.find_iter(text).count();
+        }
+        if self.region_masks.is_some() {
+            let _ = regions::classify_regions(text);
+        }
+        let mut matches = Vec::new();
+        with_scratch(|scratch| {
+            self.set_matches(text, scratch);
+            if let Some(plan) = &self.two_pass {
+                plan.candidates(text, &mut scratch.candidates);
+            }
+        });
+        for set_idx in 0..self.engines.len() {
+            sweep_range(self, set_idx, text, 0, text.len(), self.max_text_bytes, &mut matches);
+            matches.clear();
+        }
+    }
+
+    /// Approximate heap usage of the compiled structures, in bytes
+    pub(crate) fn memory_usage(&self) -> BTreeMap<&'static str, usize> {
+        let (mut regexes, mut literals) = (0, 0);
+        for engine in &self.engines {
+            match engine {
+                Engine::Regex(regex) => regexes += regex.memory_usage(),
+                Engine::Literals(alt) => literals += alt.memory_usage(),
+            }
+        }
+        let regex_set = self.regex_set.memory_usage();
+        let prefilter = self.prefilter.as_ref().map_or(0, |ac| ac.memory_usage());
+        let two_pass = self.two_pass.as_ref().map_or(0, |plan| plan.memory_usage());
+        let metadata: usize = self
+            .metadata
+            .iter()
+            .map(|m| std::mem::size_of::<PatternMetadata>() + m.pattern.capacity() + m.entity_type.capacity())
+            .sum::<usize>()
+            + self.index_map.capacity() * 2 * std::mem::size_of::<usize>();
+
+        let mut usage = BTreeMap::new();
+        usage.insert("regex_bytes", regexes);
+        usage.insert("regex_set_bytes", regex_set);
+        usage.insert("literal_bytes", literals);
+        usage.insert("prefilter_bytes", prefilter);
+        usage.insert("two_pass_bytes", two_pass);
+        usage.insert("metadata_bytes", metadata);
+        usage.insert("total_bytes", regexes + regex_set + literals + prefilter + two_pass + metadata);
+        usage
+    }
+
+    /// Effective configuration as an export tree (see `config`)
+    ///
+    /// `submitted` is the number of patterns passed in; those missing from
+    /// the compiled set are listed as failed by their input index.
+    pub(crate) fn effective_config(
+        &self,
+        submitted: usize,
+        postprocess: &PostProcessors,
+        repair: &Repairs,
+    ) -> BTreeMap<String, Value> {
+        let mut original_idx = vec![0; self.metadata.len()];
+        for (&original, &set_idx) in &self.index_map {
+            original_idx[set_idx] = original;
+        }
+        let failed: Vec<Value> = (0..submitted)
+            .filter(|i| !self.index_map.contains_key(i))
+            .map(|i| Value::Int(i as i64))
+            .collect();
+
+        let patterns = self
+            .metadata
+            .iter()
+            .enumerate()
+            .map(|(set_idx, meta)| {
+                let engine = match self.engines[set_idx] {
+                    Engine::Regex(_) => "regex",
+                    Engine::Literals(_) => "literal",
+                };
+                let mut p = BTreeMap::new();
+                p.insert("pattern_id".to_string(), Value::Int(set_idx as i64));
+                p.insert("input_index".to_string(), Value::Int(original_idx[set_idx] as i64));
+                p.insert("entity_type".to_string(), Value::Str(meta.entity_type.clone()));
+                p.insert("regex".to_string(), Value::Str(meta.pattern.clone()));
+                p.insert("confidence".to_string(), Value::float(meta.confidence));
+                p.insert("group".to_string(), Value::Int(meta.group_idx as i64));
+                p.insert("engine".to_string(), Value::Str(engine.to_string()));
+                if let Some(masks) = self.region_masks.as_ref().filter(|masks| masks[set_idx] != ALL_REGIONS) {
+                    p.insert("regions".to_string(), Value::strings(&regions::mask_names(masks[set_idx])));
+                }
+                Value::Table(p)
+            })
+            .collect();
+
+        let postprocess = postprocess
+            .to_config()
+            .into_iter()
+            .map(|(entity_type, steps)| (entity_type, Value::strings(&steps)))
+            .collect();
+        let repair = repair
+            .to_config()
+            .into_iter()
+            .map(|(entity_type, steps)| (entity_type, Value::strings(&steps)))
+            .collect();
+
+        let mut root = BTreeMap::new();
+        root.insert("format_version".to_string(), Value::Int(config::FORMAT_VERSION));
+        root.insert("engine_version".to_string(), Value::Str(env!("CARGO_PKG_VERSION").to_string()));
+        root.insert("two_pass".to_string(), Value::Bool(self.two_pass.is_some()));
+        root.insert("suppress_hexdump".to_string(), Value::Bool(self.suppress_hexdump));
+        if !self.normalizer.is_empty() {
+            root.insert("normalize".to_string(), Value::strings(&self.normalizer.names()));
+        }
+        if let Some(chunking) = &self.chunking {
+            root.insert("chunk_threshold".to_string(), Value::Int(chunking.threshold as i64));
+            root.insert("chunk_overlap".to_string(), Value::Int(chunking.overlap as i64));
+        }
+        if let Some(max) = self.max_text_bytes {
+            root.insert("max_text_bytes".to_string(), Value::Int(max as i64));
+        }
+        if let Some(watchdog) = &self.stats.watchdog {
+            root.insert("watchdog_ms".to_string(), Value::float(watchdog.threshold_ms()));
+        }
+        root.insert("failed_patterns".to_string(), Value::List(failed));
+        root.insert("postprocess".to_string(), Value::Table(postprocess));
+        root.insert("repair".to_string(), Value::Table(repair));
+        root.insert("patterns".to_string(), Value::List(patterns));
+        root
+    }
 }
 
 #[derive(Clone)]
 struct PatternMetadata {
+    /// Pattern source as given, for the configuration export
+    pattern: String,
     entity_type: String,
     confidence: f32,
     group_idx: usize,
@@ -52,10 +273,25 @@ pub struct RawMatch {
     pub entity_type: String,
     #[pyo3(get)]
     pub confidence: f32,
+    /// Whether `text` is a preview cut at the matcher's max_text_bytes
+    /// (start and end still span the whole match)
+    #[pyo3(get)]
+    pub truncated: bool,
+    /// Caller-supplied scan context, shared by every match from one scan
+    pub context: Option<Arc<PyObject>>,
 }
 
 #[pymethods]
 impl RawMatch {
+    /// Opaque context object passed to the scan call (None if not given)
+    #[getter]
+    fn context(&self, py: Python<'_>) -> PyObject {
+        match &self.context {
+            Some(ctx) => ctx.clone_ref(py),
+            None => py.None(),
+        }
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "RawMatch(type={}, text='{}', pos={}:{})",
@@ -67,28 +303,294 @@ impl RawMatch {
 /// High-performance pattern matcher using Rust regex
 #[pyclass]
 pub struct PatternMatcher {
+    /// Compiled patterns owned by this matcher
+    compiled: Arc<CompiledPatterns>,
+    /// Per-entity rules applied to Finding.normalized
+    postprocess: PostProcessors,
+    /// Per-entity decoration stripping before validation
+    repair: Repairs,
+    /// Drop findings with values on the built-in allowlist (see `allowlist`)
+    builtin_allowlist: bool,
+    /// Identifier classes given to findings
+    taxonomy: Taxonomy,
+    /// Analyst verdicts used to demote recurring false positives in detect()
+    feedback: RwLock<FeedbackStore>,
     /// Number of successfully compiled patterns
     pattern_count: usize,
     /// Number of patterns that failed to compile
     failed_count: usize,
 }
 
+impl PatternMatcher {
+    /// Shared handle to the compiled patterns
+    pub(crate) fn compiled(&self) -> Arc<CompiledPatterns> {
+        Arc::clone(&self.compiled)
+    }
+
+    /// Findings for one scan's matches with this matcher's rules applied
+    fn build_findings(
+        &self,
+        text: &str,
+        matches: Vec<RawMatch>,
+        full: Vec<Option<String>>,
+        min_score: f32,
+        feedback: &FeedbackStore,
+        ocr: Option<&ocr::OcrConfidence>,
+    ) -> Vec<Finding> {
+        let mut findings =
+            build_findings(text, matches, full, min_score, &self.postprocess, &self.repair, feedback, ocr);
+        if self.builtin_allowlist {
+            allowlist::drop_allowlisted(&mut findings);
+        }
+        for f in findings.iter_mut() {
+            f.class = self.taxonomy.classify_type(&f.entity_type);
+        }
+        findings
+    }
+}
+
 #[pymethods]
 impl PatternMatcher {
-    /// Create a new matcher, compiling patterns if not already done
+    /// Create a new matcher, compiling the given patterns
     ///
     /// Args:
-    ///     patterns: List of (regex_str, entity_type, confidence, group_idx) tuples
+    ///     patterns: List of (regex_str, entity_type, confidence, group_idx) tuples;
+    ///         pure literal alternations of 100+ terms are compiled to an
+    ///         Aho-Corasick automaton instead of a regex
+    ///     two_pass: Verify patterns only at candidate positions found by a
+    ///         cheap literal pre-pass (faster on mostly-clean documents)
+    ///     postprocess: Optional {entity_type: [step, ...]} rules applied to
+    ///         Finding.normalized; steps are "upper", "lower", "strip_spaces",
+    ///         "strip_separators", "digits_only" and "truncate:N"
+    ///     watchdog_ms: Time each pattern's pass and record passes slower
+    ///         than this many milliseconds (see slow_patterns())
+    ///     regions: Optional {entity_type: [kind, ...]} restricting patterns
+    ///         of that type to document regions of those kinds ("prose",
+    ///         "table", "code", "base64", "binary", "hexdump"; see classify_regions())
+    ///     suppress_hexdump: Drop numeric matches (digits and hex digits
+    ///         only, plus separators) inside hex dump regions such as xxd or
+    ///         hexdump -C output and packet captures
+    ///     chunk_threshold: Texts longer than this many bytes are scanned as
+    ///         overlapping chunks of this size, bounding per-scan memory;
+    ///         results are the same as a whole scan for matches shorter than
+    ///         chunk_overlap. None scans every text whole. With normalize
+    ///         stages the normalized copy of the whole text is built first
+    ///         and chunked, so that copy is not bounded by the threshold
+    ///     chunk_overlap: Bytes adjacent chunks share; at most half of
+    ///         chunk_threshold
+    ///     normalize: Optional list of normalization stages run in order
+    ///         before matching: "unicode_fold", "collapse_whitespace",
+    ///         "decode_entities", "decode_percent", "ocr_fold". Offsets
+    ///         still refer to the input; RawMatch.text is the normalized form
+    ///     repair: Optional {entity_type: [step, ...]} rules stripping
+    ///         decorations from a match before validation; steps are
+    ///         "labels" ("SSN#", "Tel.", "No.", "№"), "brackets" and
+    ///         "trailing_punct". The validated value is Finding.repaired
+    ///     max_text_bytes: Copy at most this many bytes of each match into
+    ///         its text (cut at a character boundary) and set truncated;
+    ///         start and end still span the whole match. None copies every
+    ///         match whole
+    ///     builtin_allowlist: Drop findings whose value is a well-known
+    ///         non-sensitive one: processor test card numbers, SSNs from
+    ///         documentation and advertising, reserved example domains
+    ///         (and emails at them) and documentation IP ranges
+    ///     taxonomy: Taxonomy giving Finding.identifier_class; None uses
+    ///         the built-in classes
     ///
     /// Returns:
     ///     PatternMatcher instance
+    ///
+    /// Raises:
+    ///     ValueError: For invalid postprocess, watchdog, regions, chunking,
+    ///         normalize, repair or max_text_bytes arguments
     #[new]
-    fn new(patterns: Vec<(String, String, f32, usize)>) -> PyResult<Self> {
-        let compiled = COMPILED_PATTERNS.get_or_init(|| compile_patterns(&patterns));
+    #[pyo3(signature = (
+        patterns,
+        two_pass=false,
+        postprocess=None,
+        watchdog_ms=None,
+        regions=None,
+        suppress_hexdump=false,
+        chunk_threshold=Some(stream::DEFAULT_CHUNK_THRESHOLD),
+        chunk_overlap=stream::DEFAULT_CHUNK_OVERLAP,
+        normalize=None,
+        repair=None,
+        max_text_bytes=None,
+        builtin_allowlist=true,
+        taxonomy=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        patterns: Vec<(String, String, f32, usize)>,
+        two_pass: bool,
+        postprocess: Option<HashMap<String, Vec<String>>>,
+        watchdog_ms: Option<f64>,
+        regions: Option<HashMap<String, Vec<String>>>,
+        suppress_hexdump: bool,
+        chunk_threshold: Option<usize>,
+        chunk_overlap: usize,
+        normalize: Option<Vec<String>>,
+        repair: Option<HashMap<String, Vec<String>>>,
+        max_text_bytes: Option<usize>,
+        builtin_allowlist: bool,
+        taxonomy: Option<Taxonomy>,
+    ) -> PyResult<Self> {
+        let postprocess = match postprocess {
+            Some(config) => PostProcessors::from_config(config)?,
+            None => PostProcessors::default(),
+        };
+        let repair = match repair {
+            Some(config) => Repairs::from_config(config)?,
+            None => Repairs::default(),
+        };
+        let watchdog = match watchdog_ms {
+            Some(ms) if ms.is_finite() && ms >= 0.0 => Some(Watchdog::new(Duration::from_secs_f64(ms / 1000.0))),
+            Some(ms) => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "watchdog_ms must be a non-negative number, got {}",
+                    ms
+                )))
+            }
+            None => None,
+        };
+        let region_masks = regions
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(entity_type, kinds)| {
+                regions::mask_from_names(&kinds)
+                    .map(|mask| (entity_type, mask))
+                    .map_err(pyo3::exceptions::PyValueError::new_err)
+            })
+            .collect::<PyResult<HashMap<String, RegionMask>>>()?;
+        let mut compiled = compile_patterns(&patterns, two_pass);
+        compiled.stats = ScanStats::with_watchdog(watchdog);
+        compiled.restrict_regions(&region_masks);
+        compiled.suppress_hexdump = suppress_hexdump;
+        compiled.chunking = chunk_threshold
+            .map(|threshold| Chunking::new(threshold, chunk_overlap))
+            .transpose()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        compiled.normalizer = Pipeline::from_names(&normalize.unwrap_or_default())
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        if max_text_bytes == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err("max_text_bytes must be at least 1"));
+        }
+        compiled.max_text_bytes = max_text_bytes;
 
         Ok(Self {
-            pattern_count: compiled.individual_regexes.len(),
-            failed_count: patterns.len() - compiled.individual_regexes.len(),
+            postprocess,
+            repair,
+            builtin_allowlist,
+            taxonomy: taxonomy.unwrap_or_default(),
+            feedback: RwLock::default(),
+            pattern_count: compiled.engines.len(),
+            failed_count: patterns.len() - compiled.engines.len(),
+            compiled: Arc::new(compiled),
+        })
+    }
+
+    /// Approximate memory held by this matcher's compiled patterns
+    ///
+    /// Dictionaries are not part of a PatternMatcher; add each
+    /// DictionaryMatcher.memory_usage() to size a worker's full footprint.
+    ///
+    /// Returns:
+    ///     Dict of byte counts: regex_bytes (per-pattern regexes),
+    ///     regex_set_bytes, literal_bytes (literal alternation automata),
+    ///     prefilter_bytes, two_pass_bytes, metadata_bytes and total_bytes
+    fn memory_usage(&self) -> BTreeMap<&'static str, usize> {
+        self.compiled.memory_usage()
+    }
+
+    /// Canonical serialization of the effective configuration
+    ///
+    /// Lists every compiled pattern (source, entity type, confidence,
+    /// group, execution engine and region restriction), the input indices
+    /// of patterns that failed to compile, post-processing and repair rules,
+    /// the built-in allowlist switch, taxonomy overrides, two-pass mode and
+    /// the watchdog threshold. Keys are sorted and numbers use a fixed format, so
+    /// identical configurations export identical text.
+    ///
+    /// Args:
+    ///     format: "json" or "toml"
+    ///
+    /// Raises:
+    ///     ValueError: For an unknown format
+    #[pyo3(signature = (format="json"))]
+    fn export_config(&self, format: &str) -> PyResult<String> {
+        let format = config::Format::parse(format).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("unknown config format '{}': expected json or toml", format))
+        })?;
+        let submitted = self.pattern_count + self.failed_count;
+        let mut root = self.compiled.effective_config(submitted, &self.postprocess, &self.repair);
+        root.insert("builtin_allowlist".to_string(), Value::Bool(self.builtin_allowlist));
+        let taxonomy = self.taxonomy.to_config().into_iter().map(|(t, class)| (t, Value::Str(class))).collect();
+        root.insert("taxonomy".to_string(), Value::Table(taxonomy));
+        Ok(config::render(&root, format))
+    }
+
+    /// Cumulative scan counters since creation (or the last reset_stats())
+    ///
+    /// Returns:
+    ///     Dict with scans (texts or byte-scan runs scanned), bytes_scanned,
+    ///     matches and slow_events (watchdog threshold breaches)
+    fn stats(&self) -> BTreeMap<&'static str, u64> {
+        self.compiled.stats.snapshot()
+    }
+
+    /// Pattern passes that exceeded the watchdog threshold, oldest first
+    ///
+    /// Keeps the most recent 256; empty if the watchdog is off.
+    fn slow_patterns(&self) -> Vec<SlowPattern> {
+        self.compiled.stats.watchdog.as_ref().map_or_else(Vec::new, |w| w.events())
+    }
+
+    /// Watchdog threshold in milliseconds (None if disabled)
+    #[getter]
+    fn watchdog_ms(&self) -> Option<f64> {
+        self.compiled.stats.watchdog.as_ref().map(|w| w.threshold_ms())
+    }
+
+    /// Zero the counters and clear recorded slow passes
+    fn reset_stats(&self) {
+        self.compiled.stats.reset();
+    }
+
+    /// Whether this matcher runs the two-pass scan
+    #[getter]
+    fn two_pass(&self) -> bool {
+        self.compiled.two_pass.is_some()
+    }
+
+    /// Taxonomy classifying this matcher's findings
+    #[getter]
+    fn taxonomy(&self) -> Taxonomy {
+        self.taxonomy.clone()
+    }
+
+    /// Prime lazy DFA states and per-thread caches before real traffic
+    ///
+    /// Scans synthetic text (plus the optional sample) with every pattern
+    /// on the calling thread and on every thread of the pool used by
+    /// find_matches_batch, with the GIL released. Nothing is returned and
+    /// stats() is unchanged. Safe to call more than once.
+    ///
+    /// Args:
+    ///     sample: Representative production text to warm on as well
+    ///
+    /// Returns:
+    ///     Elapsed wall time in milliseconds
+    #[pyo3(signature = (sample=None))]
+    fn warmup(&self, py: Python<'_>, sample: Option<&str>) -> f64 {
+        let compiled = &self.compiled;
+        py.allow_threads(|| {
+            let started = std::time::Instant::now();
+            let text = crate::warmup::synthetic_text(sample);
+            compiled.warm(&text);
+            rayon::broadcast(|_| compiled.warm(&text));
+            started.elapsed().as_secs_f64() * 1000.0
         })
     }
 
@@ -111,23 +613,393 @@ impl PatternMatcher {
     ///
     /// Args:
     ///     text: The text to scan
+    ///     context: Optional object (document id, tenant, source path...)
+    ///         attached by reference to every returned match
     ///
     /// Returns:
     ///     List of RawMatch objects
-    fn find_matches(&self, py: Python<'_>, text: &str) -> PyResult<Vec<RawMatch>> {
+    #[pyo3(signature = (text, context=None))]
+    fn find_matches(&self, py: Python<'_>, text: &str, context: Option<PyObject>) -> Vec<RawMatch> {
         // Release the GIL during the heavy lifting
-        py.allow_threads(|| {
-            let compiled = COMPILED_PATTERNS.get().expect("Patterns not initialized");
-            find_matches_impl(compiled, text)
-        })
+        let mut matches = py.allow_threads(|| find_matches_impl(&self.compiled, text));
+        attach_context(&mut matches, context);
+        matches
+    }
+
+    /// Scan text and keep the matches natively, to be pulled in pages
+    ///
+    /// Use instead of find_matches() when a scan may produce more matches
+    /// than fit in memory as Python objects. Releases the GIL while scanning.
+    ///
+    /// Args:
+    ///     text: The text to scan
+    ///     page_size: Default number of matches per page
+    ///     context: Optional object attached to every returned match
+    ///
+    /// Returns:
+    ///     ScanCursor yielding lists of RawMatch objects
+    #[pyo3(signature = (text, page_size=10000, context=None))]
+    fn scan_paged(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        page_size: usize,
+        context: Option<PyObject>,
+    ) -> PyResult<ScanCursor> {
+        let matches = py.allow_threads(|| find_matches_impl(&self.compiled, text));
+        ScanCursor::new(matches, page_size, context)
+    }
+
+    /// Detect entities: match, normalize, validate and score in one call
+    ///
+    /// Higher-level counterpart of find_matches(). Each Finding carries the
+    /// normalized value, per-validator verdicts and a score. Releases the
+    /// GIL while scanning.
+    ///
+    /// Args:
+    ///     text: The text to scan
+    ///     context: Optional metadata object attached to every finding
+    ///     min_score: Drop findings scoring below this value
+    ///     ocr_confidence: OCR confidence (0.0-1.0) for the text, either one
+    ///         float per character or a list of (start, end, confidence)
+    ///         byte spans; each score is multiplied by the lowest
+    ///         confidence under the finding
+    ///
+    /// Returns:
+    ///     List of Finding objects
+    ///
+    /// Raises:
+    ///     ValueError: If ocr_confidence does not fit the text
+    #[pyo3(signature = (text, context=None, min_score=0.0, ocr_confidence=None))]
+    fn detect(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        context: Option<PyObject>,
+        min_score: f32,
+        ocr_confidence: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<Finding>> {
+        let ocr = ocr_confidence.map(|value| ocr::from_py(text, &value)).transpose()?;
+        let (mut matches, full) = py.allow_threads(|| find_matches_full(&self.compiled, text));
+        attach_context(&mut matches, context);
+        Ok(py.allow_threads(|| {
+            let feedback = self.feedback.read().unwrap_or_else(|e| e.into_inner());
+            self.build_findings(text, matches, full, min_score, &feedback, ocr.as_ref())
+        }))
+    }
+
+    /// Detect entities grouped by value: one entry per distinct value
+    ///
+    /// Same arguments as detect(). Findings with the same entity type and
+    /// normalized value (after post-processing) are collected into one
+    /// GroupedFinding listing every location, ordered by first occurrence.
+    ///
+    /// Returns:
+    ///     List of GroupedFinding objects
+    ///
+    /// Raises:
+    ///     ValueError: If ocr_confidence does not fit the text
+    #[pyo3(signature = (text, context=None, min_score=0.0, ocr_confidence=None))]
+    fn detect_grouped(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        context: Option<PyObject>,
+        min_score: f32,
+        ocr_confidence: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<GroupedFinding>> {
+        let findings = self.detect(py, text, context, min_score, ocr_confidence)?;
+        Ok(py.allow_threads(|| group_findings(findings)))
+    }
+
+    /// Detect entities and sign the results for a tamper-evident audit trail
+    ///
+    /// Findings are serialized to canonical JSON (with SHA-256 digests of
+    /// the text and of export_config()) and signed with HMAC-SHA256 under
+    /// `key` before any Python object is created. Store the payload and
+    /// signature; verify_signed_result() checks them later.
+    ///
+    /// Args:
+    ///     text: Text to scan
+    ///     key: Signing key (bytes)
+    ///     min_score: Drop findings scoring below this
+    ///     scan_id: Optional caller identifier recorded in the payload
+    ///     ocr_confidence: As for detect()
+    ///
+    /// Returns:
+    ///     SignedResult with findings, payload and signature
+    ///
+    /// Raises:
+    ///     ValueError: If the key is empty or ocr_confidence does not fit the text
+    #[pyo3(signature = (text, key, min_score=0.0, scan_id=None, ocr_confidence=None))]
+    fn detect_signed(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        key: &[u8],
+        min_score: f32,
+        scan_id: Option<&str>,
+        ocr_confidence: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<SignedResult> {
+        if key.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err("signing key must not be empty"));
+        }
+        let findings = self.detect(py, text, None, min_score, ocr_confidence)?;
+        let config = self.export_config("json")?;
+        Ok(py.allow_threads(|| {
+            let payload = signing::payload(&findings, text, &config, scan_id);
+            let signature = signing::sign(key, &payload);
+            SignedResult { findings, payload, signature }
+        }))
+    }
+
+    /// Detect entities and report only per-entity counts and a few examples
+    ///
+    /// For summary reports that need how much of each entity type a text
+    /// holds rather than every finding. Examples are chosen by hashing the
+    /// normalized values, so the same text always gives the same examples.
+    ///
+    /// Args:
+    ///     text: Text to scan
+    ///     max_examples: Most example values per entity type (0 for counts only)
+    ///     min_score: Drop findings scoring below this
+    ///     ocr_confidence: As for detect()
+    ///
+    /// Returns:
+    ///     List of EntitySummary objects ordered by entity type
+    ///
+    /// Raises:
+    ///     ValueError: If ocr_confidence does not fit the text
+    #[pyo3(signature = (text, max_examples=3, min_score=0.0, ocr_confidence=None))]
+    fn detect_summary(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        max_examples: usize,
+        min_score: f32,
+        ocr_confidence: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<EntitySummary>> {
+        let findings = self.detect(py, text, None, min_score, ocr_confidence)?;
+        Ok(py.allow_threads(|| summary::summarize(findings, max_examples)))
+    }
+
+    /// Recognize identity documents (W-2, I-9, driver's license) in a scanned form
+    ///
+    /// A document is reported when enough field labels of its layout occur
+    /// together; a form title and validated values of the entity types it
+    /// carries raise the confidence. Each document lists the labels and
+    /// findings that contributed.
+    ///
+    /// Args:
+    ///     text: Text of the form, typically OCR output
+    ///     min_confidence: Drop documents below this confidence
+    ///     min_score: Drop findings scoring below this before they count
+    ///     ocr_confidence: As for detect()
+    ///
+    /// Returns:
+    ///     List of IdentityDocument objects ordered by start offset
+    ///
+    /// Raises:
+    ///     ValueError: If ocr_confidence does not fit the text
+    #[pyo3(signature = (text, min_confidence=0.5, min_score=0.0, ocr_confidence=None))]
+    fn detect_documents(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        min_confidence: f32,
+        min_score: f32,
+        ocr_confidence: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<IdentityDocument>> {
+        let findings = self.detect(py, text, None, min_score, ocr_confidence)?;
+        Ok(py.allow_threads(|| iddoc::recognize(text, &findings, min_confidence)))
+    }
+
+    /// Precision, recall and F1 per entity type over a labeled corpus
+    ///
+    /// Documents are scanned in parallel with the GIL released.
+    ///
+    /// Args:
+    ///     corpus: List of (text, expected) where expected is a list of
+    ///         (start, end, entity_type) byte spans
+    ///     mode: "exact" (identical spans) or "overlap" (spans share a byte)
+    ///     min_score: If given, score detect() findings at or above this
+    ///         score instead of raw matches
+    ///
+    /// Returns:
+    ///     EvaluationReport with per-entity and overall scores
+    #[pyo3(signature = (corpus, mode="exact", min_score=None))]
+    fn evaluate(
+        &self,
+        py: Python<'_>,
+        corpus: Vec<(String, Vec<LabeledSpan>)>,
+        mode: &str,
+        min_score: Option<f32>,
+    ) -> PyResult<EvaluationReport> {
+        let mode = MatchMode::parse(mode).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("unknown mode '{}': expected exact or overlap", mode))
+        })?;
+        Ok(py.allow_threads(|| {
+            let feedback = self.feedback.read().unwrap_or_else(|e| e.into_inner());
+            evaluate::evaluate(&corpus, mode, |text| {
+                let (matches, full) = find_matches_full(&self.compiled, text);
+                match min_score {
+                    Some(min_score) => self
+                        .build_findings(text, matches, full, min_score, &feedback, None)
+                        .into_iter()
+                        .map(|f| (f.start, f.end, f.entity_type))
+                        .collect(),
+                    None => matches.into_iter().map(|m| (m.start, m.end, m.entity_type)).collect(),
+                }
+            })
+        }))
+    }
+
+    /// Load analyst feedback used by detect() to demote false positives
+    ///
+    /// Verdicts accumulate across calls; a value's score is multiplied by
+    /// 1.0 with no false-positive votes, down to 0.1 when all votes are
+    /// false positives.
+    ///
+    /// Args:
+    ///     verdicts: Finding.feedback_hash -> "false_positive" / "fp" or
+    ///         "true_positive" / "tp"
+    ///
+    /// Returns:
+    ///     Number of distinct values with feedback after loading
+    ///
+    /// Raises:
+    ///     ValueError: On a malformed hash or unknown verdict (nothing is loaded)
+    fn load_feedback(&self, verdicts: HashMap<String, String>) -> PyResult<usize> {
+        let mut parsed = Vec::with_capacity(verdicts.len());
+        for (hash, verdict) in &verdicts {
+            if !feedback::is_feedback_hash(hash) {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "feedback hash must be 64 hex characters, got '{}'",
+                    hash
+                )));
+            }
+            let verdict = Verdict::parse(verdict).ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown verdict '{}': expected false_positive or true_positive",
+                    verdict
+                ))
+            })?;
+            parsed.push((hash, verdict));
+        }
+        let mut store = self.feedback.write().unwrap_or_else(|e| e.into_inner());
+        for (hash, verdict) in parsed {
+            store.add(hash, verdict);
+        }
+        Ok(store.len())
+    }
+
+    /// Forget all loaded feedback
+    fn clear_feedback(&self) {
+        self.feedback.write().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Number of distinct values with analyst feedback
+    #[getter]
+    fn feedback_size(&self) -> usize {
+        self.feedback.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Scan text and return values introduced by a label ("SSN: ...")
+    ///
+    /// Runs find_matches() and pairs each value with the label keyword
+    /// directly before it. Releases the GIL while scanning.
+    ///
+    /// Args:
+    ///     text: The text to scan
+    ///     extractor: PairExtractor holding the label keywords
+    ///
+    /// Returns:
+    ///     List of LabeledPair objects
+    fn find_pairs(&self, py: Python<'_>, text: &str, extractor: PyRef<PairExtractor>) -> Vec<LabeledPair> {
+        let extractor: &PairExtractor = &extractor;
+        py.allow_threads(|| extractor.pair(text, &find_matches_impl(&self.compiled, text)))
+    }
+
+    /// Find all pattern matches in a byte buffer that may not be valid UTF-8
+    ///
+    /// Invalid sequences never abort the scan; their byte ranges are
+    /// reported alongside the matches. Releases the GIL while scanning.
+    ///
+    /// Args:
+    ///     data: Raw bytes to scan
+    ///     mode: "lossy" (replace invalid sequences with U+FFFD and scan
+    ///         across them) or "strict" (scan each valid run separately)
+    ///     context: Optional object attached to every returned match
+    ///
+    /// Returns:
+    ///     ByteScanResult with byte-offset matches and invalid_spans
+    #[pyo3(signature = (data, mode="lossy", context=None))]
+    fn find_matches_bytes(
+        &self,
+        py: Python<'_>,
+        data: &[u8],
+        mode: &str,
+        context: Option<PyObject>,
+    ) -> PyResult<ByteScanResult> {
+        let mode = Utf8Mode::parse(mode)?;
+        let mut result = py.allow_threads(|| scan_bytes(&self.compiled, data, mode));
+        attach_context(&mut result.matches, context);
+        Ok(result)
+    }
+
+    /// Mask every match directly in a writable buffer, keeping its length
+    ///
+    /// Each matched byte is overwritten with `fill`; nothing is copied, so
+    /// offsets of everything outside the matches are unchanged. Valid UTF-8
+    /// runs are scanned where they lie, as in strict mode, so invalid bytes
+    /// are left alone and no match crosses them. The buffer is held through
+    /// the buffer protocol, so a bytearray cannot be resized during the
+    /// call, and the GIL is released while scanning; other threads must not
+    /// write to the buffer until the call returns.
+    ///
+    /// Args:
+    ///     buffer: Writable, contiguous bytes-like object (bytearray,
+    ///         memoryview, mmap, ...) to redact in place
+    ///     fill: Single replacement byte (default b"*")
+    ///
+    /// Returns:
+    ///     List of (start, end) byte ranges that were masked
+    ///
+    /// Raises:
+    ///     ValueError: If fill is not exactly one byte
+    ///     TypeError: If buffer is read-only or not contiguous
+    #[pyo3(signature = (buffer, fill=b"*".as_slice()))]
+    fn redact_inplace(&self, py: Python<'_>, buffer: &Bound<'_, PyAny>, fill: &[u8]) -> PyResult<Vec<(usize, usize)>> {
+        let [fill] = *fill else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "fill must be a single byte, got {} bytes",
+                fill.len()
+            )));
+        };
+        let view = PyBuffer::<u8>::get_bound(buffer)?;
+        if view.readonly() || !view.is_c_contiguous() {
+            view.release(py);
+            return Err(pyo3::exceptions::PyTypeError::new_err("buffer must be writable and contiguous"));
+        }
+        // The address crosses into the GIL-free closure as an integer
+        let (addr, len) = (view.buf_ptr() as usize, view.len_bytes());
+        let spans = py.allow_threads(|| {
+            // SAFETY: the exported view keeps the memory alive and the
+            // exporter from resizing it until released below; it is
+            // writable and contiguous, and no other reference to it is
+            // held on our side
+            let data = unsafe { std::slice::from_raw_parts_mut(addr as *mut u8, len) };
+            let spans = match_spans(&self.compiled, data);
+            redact::mask_spans(data, &spans, fill);
+            spans
+        });
+        view.release(py);
+        Ok(spans)
     }
 
     /// Check if a specific pattern index is available
     fn has_pattern(&self, index: usize) -> bool {
-        COMPILED_PATTERNS
-            .get()
-            .map(|c| c.index_map.contains_key(&index))
-            .unwrap_or(false)
+        self.compiled.index_map.contains_key(&index)
     }
 
     /// Find matches in multiple texts in parallel (batch API)
@@ -137,18 +1009,113 @@ impl PatternMatcher {
     ///
     /// Args:
     ///     texts: List of texts to scan
+    ///     contexts: Optional list of context objects, one per text; each
+    ///         text's matches carry its own context regardless of ordering
     ///
     /// Returns:
     ///     List of lists of RawMatch objects (one per input text)
-    fn find_matches_batch(&self, py: Python<'_>, texts: Vec<String>) -> PyResult<Vec<Vec<RawMatch>>> {
-        Ok(py.allow_threads(|| {
-            let compiled = COMPILED_PATTERNS.get().expect("Patterns not initialized");
+    #[pyo3(signature = (texts, contexts=None))]
+    fn find_matches_batch(
+        &self,
+        py: Python<'_>,
+        texts: Vec<String>,
+        contexts: Option<Vec<PyObject>>,
+    ) -> PyResult<Vec<Vec<RawMatch>>> {
+        if let Some(ctxs) = &contexts {
+            if ctxs.len() != texts.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "contexts has {} entries but texts has {}",
+                    ctxs.len(),
+                    texts.len()
+                )));
+            }
+        }
+
+        let mut results = py.allow_threads(|| {
+            let compiled = &self.compiled;
 
             // Process texts in parallel
             texts
                 .par_iter()
-                .map(|text| find_matches_impl(compiled, text.as_str()).unwrap_or_default())
+                .map(|text| find_matches_impl(compiled, text.as_str()))
                 .collect::<Vec<Vec<RawMatch>>>()
+        });
+
+        if let Some(ctxs) = contexts {
+            for (matches, ctx) in results.iter_mut().zip(ctxs) {
+                attach_context(matches, Some(ctx));
+            }
+        }
+        Ok(results)
+    }
+
+    /// Match counts per entity type over many texts, optionally noised
+    ///
+    /// Every entity type of this matcher's patterns is reported, including
+    /// zero counts. Each text contributes at most max_per_document matches
+    /// to any one count. With epsilon, each count gets independent Laplace
+    /// noise of scale max_per_document / epsilon before leaving the native
+    /// layer, then is rounded and clamped at zero. Texts are scanned in
+    /// parallel with the GIL released.
+    ///
+    /// Args:
+    ///     texts: Texts to scan
+    ///     epsilon: Privacy parameter (> 0); None returns exact counts
+    ///     max_per_document: Per-text cap on each entity's contribution;
+    ///         required with epsilon, optional otherwise
+    ///     seed: Fixed noise seed for reproducible tests; never use for
+    ///         shared telemetry
+    ///
+    /// Returns:
+    ///     Dict of entity_type -> count
+    ///
+    /// Raises:
+    ///     ValueError: If epsilon is not positive or max_per_document is
+    ///         missing or zero with epsilon
+    #[pyo3(signature = (texts, epsilon=None, max_per_document=None, seed=None))]
+    fn aggregate_counts(
+        &self,
+        py: Python<'_>,
+        texts: Vec<String>,
+        epsilon: Option<f64>,
+        max_per_document: Option<u64>,
+        seed: Option<u64>,
+    ) -> PyResult<BTreeMap<String, u64>> {
+        let noise = match epsilon {
+            None => None,
+            Some(eps) if !(eps.is_finite() && eps > 0.0) => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "epsilon must be a positive number, got {}",
+                    eps
+                )))
+            }
+            Some(eps) => match max_per_document {
+                Some(cap) if cap > 0 => Some((eps, cap)),
+                _ => {
+                    return Err(pyo3::exceptions::PyValueError::new_err(
+                        "max_per_document must be at least 1 when epsilon is given (it bounds each text's influence)",
+                    ))
+                }
+            },
+        };
+
+        Ok(py.allow_threads(|| {
+            let compiled = &self.compiled;
+            let mut totals: BTreeMap<String, u64> =
+                compiled.metadata.iter().map(|m| (m.entity_type.clone(), 0)).collect();
+            let per_text: Vec<Vec<RawMatch>> =
+                texts.par_iter().map(|text| find_matches_impl(compiled, text.as_str())).collect();
+            for matches in &per_text {
+                noise::add_document(&mut totals, matches.iter().map(|m| m.entity_type.as_str()), max_per_document);
+            }
+
+            match noise {
+                Some((epsilon, cap)) => {
+                    let mut source = seed.map_or_else(NoiseSource::from_entropy, NoiseSource::from_seed);
+                    noise::noisy_counts(&totals, epsilon, cap, &mut source)
+                }
+                None => totals,
+            }
         }))
     }
 
@@ -164,32 +1131,44 @@ impl PatternMatcher {
     /// Returns:
     ///     True if text might contain patterns, False if definitely not
     fn might_contain_patterns(&self, py: Python<'_>, text: &str) -> bool {
-        py.allow_threads(|| {
-            let compiled = COMPILED_PATTERNS.get().expect("Patterns not initialized");
-            match &compiled.prefilter {
-                Some(ac) => ac.is_match(text),
-                None => true, // No prefilter, assume might match
-            }
+        py.allow_threads(|| match &self.compiled.prefilter {
+            Some(ac) => ac.is_match(text),
+            None => true, // No prefilter, assume might match
         })
     }
 }
 
 /// Compile all patterns into RegexSet and individual Regexes
-fn compile_patterns(patterns: &[(String, String, f32, usize)]) -> CompiledPatterns {
+///
+/// Large pure literal alternations become Aho-Corasick automata instead
+/// and are left out of the RegexSet and two-pass triggers.
+pub(crate) fn compile_patterns(
+    patterns: &[(String, String, f32, usize)],
+    two_pass: bool,
+) -> CompiledPatterns {
     let mut successful_patterns: Vec<String> = Vec::new();
-    let mut individual_regexes: Vec<Regex> = Vec::new();
+    let mut engines: Vec<Engine> = Vec::new();
+    let mut literal_patterns: Vec<usize> = Vec::new();
     let mut metadata: Vec<PatternMetadata> = Vec::new();
     let mut index_map: HashMap<usize, usize> = HashMap::new();
 
     for (original_idx, (pattern_str, entity_type, confidence, group_idx)) in patterns.iter().enumerate() {
         // Try to compile the pattern
-        match Regex::new(pattern_str) {
-            Ok(regex) => {
+        let compiled = match LiteralAlternation::parse(pattern_str, *group_idx) {
+            Some(alt) => {
+                literal_patterns.push(successful_patterns.len());
+                Ok((Engine::Literals(alt), NEVER_MATCHES.to_string()))
+            }
+            None => meta::Regex::new(pattern_str).map(|regex| (Engine::Regex(regex), pattern_str.clone())),
+        };
+        match compiled {
+            Ok((engine, set_pattern)) => {
                 let new_idx = successful_patterns.len();
                 index_map.insert(original_idx, new_idx);
-                successful_patterns.push(pattern_str.clone());
-                individual_regexes.push(regex);
+                successful_patterns.push(set_pattern);
+                engines.push(engine);
                 metadata.push(PatternMetadata {
+                    pattern: pattern_str.clone(),
                     entity_type: entity_type.clone(),
                     confidence: *confidence,
                     group_idx: *group_idx,
@@ -202,10 +1181,14 @@ fn compile_patterns(patterns: &[(String, String, f32, usize)]) -> CompiledPatter
         }
     }
 
-    // Build RegexSet from successful patterns with increased size limit
-    let regex_set = RegexSetBuilder::new(&successful_patterns)
-        .size_limit(50 * 1024 * 1024)  // 50MB limit (default is 10MB)
-        .build()
+    // Build the pattern set from successful patterns with increased size limit
+    let regex_set = meta::Regex::builder()
+        .configure(
+            meta::Config::new()
+                .match_kind(MatchKind::All)
+                .nfa_size_limit(Some(50 * 1024 * 1024)),  // 50MB limit (default is 10MB)
+        )
+        .build_many(&successful_patterns)
         .expect("Failed to build RegexSet");
 
     // Build Aho-Corasick prefilter for common pattern literals
@@ -219,72 +1202,442 @@ fn compile_patterns(patterns: &[(String, String, f32, usize)]) -> CompiledPatter
         " ",     // Names, addresses (whitespace between words)
     ];
     let prefilter = AhoCorasick::builder()
-        .build(&prefilter_patterns)
+        .build(prefilter_patterns)
         .ok();
 
+    let two_pass = if two_pass {
+        TwoPassPlan::build(&successful_patterns)
+    } else {
+        None
+    };
+
     CompiledPatterns {
         regex_set,
-        individual_regexes,
+        engines,
+        literal_patterns,
         metadata,
         index_map,
         prefilter,
+        two_pass,
+        stats: ScanStats::default(),
+        region_masks: None,
+        suppress_hexdump: false,
+        chunking: None,
+        normalizer: Pipeline::default(),
+        max_text_bytes: None,
     }
 }
 
 /// Find all matches in text using compiled patterns
-fn find_matches_impl(compiled: &CompiledPatterns, text: &str) -> PyResult<Vec<RawMatch>> {
+///
+/// With normalization stages, patterns run over the normalized text: match
+/// offsets refer to `text` but the matched text is the normalized form.
+/// Texts above the chunking threshold are scanned as overlapping chunks.
+/// Chunking applies to the normalized text, which (with its offset maps)
+/// is built whole first: the guardrail bounds the per-chunk scan
+/// structures, not that copy.
+pub(crate) fn find_matches_impl(compiled: &CompiledPatterns, text: &str) -> Vec<RawMatch> {
+    scan_normalized(compiled, text, compiled.max_text_bytes, false).0
+}
+
+/// (start, end) of every match, as find_matches_impl reports them, without
+/// copying any matched text
+pub(crate) fn find_match_spans(compiled: &CompiledPatterns, text: &str) -> Vec<(usize, usize)> {
+    let (matches, _) = scan_normalized(compiled, text, Some(0), false);
+    matches.iter().map(|m| (m.start, m.end)).collect()
+}
+
+/// Find all matches, with the whole value of each truncated normalized match
+///
+/// The second list lines up with the matches. With normalization stages a
+/// truncated match's whole value is the normalized text, not the input
+/// slice at its offsets, so it is kept for validation; entries are None
+/// where `text[start..end]` is the value (or the match is not truncated).
+pub(crate) fn find_matches_full(compiled: &CompiledPatterns, text: &str) -> (Vec<RawMatch>, Vec<Option<String>>) {
+    scan_normalized(compiled, text, compiled.max_text_bytes, true)
+}
+
+/// Scan through the normalizer; `text_limit` caps the text copied per match
+fn scan_normalized(
+    compiled: &CompiledPatterns,
+    text: &str,
+    text_limit: Option<usize>,
+    keep_full: bool,
+) -> (Vec<RawMatch>, Vec<Option<String>>) {
+    let mut full = Vec::new();
+    let matches = if compiled.normalizer.is_empty() {
+        scan_whole(compiled, text, text_limit)
+    } else {
+        let normalized = compiled.normalizer.apply(text);
+        let mut matches = scan_whole(compiled, &normalized.text, text_limit);
+        if keep_full {
+            full = matches.iter().map(|m| m.truncated.then(|| normalized.text[m.start..m.end].to_string())).collect();
+        }
+        for m in matches.iter_mut() {
+            (m.start, m.end) = normalized.to_original(m.start, m.end);
+        }
+        matches
+    };
+    compiled.stats.record_scan(text.len(), matches.len());
+    (matches, full)
+}
+
+/// Scan a text whole or, above the chunking threshold, in chunks
+fn scan_whole(compiled: &CompiledPatterns, text: &str, text_limit: Option<usize>) -> Vec<RawMatch> {
+    match &compiled.chunking {
+        Some(chunking) if text.len() > chunking.threshold => find_matches_chunked(compiled, chunking, text, text_limit),
+        _ => scan_text(compiled, text, text_limit),
+    }
+}
+
+/// Scan overlapping chunks, reporting each match once, in whole-text order
+///
+/// A match running into the end of its chunk may continue past it, and if
+/// it is longer than the overlap the next chunk cannot see it whole either,
+/// so its pattern is re-run on the whole text from the match start.
+fn find_matches_chunked(
+    compiled: &CompiledPatterns,
+    chunking: &Chunking,
+    text: &str,
+    text_limit: Option<usize>,
+) -> Vec<RawMatch> {
+    let bounds = chunking.bounds(text);
+    let mut dedup = OverlapDedup::new(chunking.overlap);
     let mut matches = Vec::new();
+    let mut rescanned = Vec::new();
+    for (i, &(start, end)) in bounds.iter().enumerate() {
+        let last = i + 1 == bounds.len();
+        let mut found = scan_text(compiled, &text[start..end], text_limit);
+        // Matches starting at a later chunk's start were cut by it and
+        // are re-run by the chunk whose end cut them
+        found.retain(|m| {
+            let cut = !last && m.end == end - start && (start == 0 || m.start > 0);
+            let chunk = (start, end);
+            !(cut && rescan_cut_match(compiled, m.pattern_id, text, chunk, start + m.start, text_limit, &mut rescanned))
+        });
+        matches.extend(dedup.admit(found, start, end - start, None, last));
+        matches.extend(rescanned.drain(..).filter_map(|m| dedup.admit_whole(m)));
+    }
+    // Whole-text scans report pattern by pattern, each in text order
+    matches.sort_by_key(|m| (m.pattern_id, m.start, m.end));
+    matches
+}
 
-    // Fast check: which patterns match anywhere in text?
-    let matching_indices: Vec<usize> = compiled.regex_set.matches(text).into_iter().collect();
+/// Re-run one pattern on the whole text for a match that ends at the end
+/// of `chunk`; returns false if the pattern is left to the overlap
+///
+/// `start` is the match's absolute start. Literal alternations are left to
+/// the next chunk: their terms are far shorter than any overlap.
+fn rescan_cut_match(
+    compiled: &CompiledPatterns,
+    set_idx: usize,
+    text: &str,
+    chunk: (usize, usize),
+    start: usize,
+    text_limit: Option<usize>,
+    out: &mut Vec<RawMatch>,
+) -> bool {
+    let Engine::Regex(regex) = &compiled.engines[set_idx] else {
+        return false;
+    };
+    let group_idx = compiled.metadata[set_idx].group_idx;
+    if group_idx == 0 {
+        let input = Input::new(text).range(start..).anchored(Anchored::Yes);
+        if let Some(m) = regex.search(&input) {
+            push_match(out, compiled, set_idx, text, m.start(), m.end(), text_limit);
+        }
+        return true;
+    }
+    // The group's match may begin before the group: walk the pattern from
+    // the chunk start to the first match that reaches the chunk end
+    for caps in regex.captures_iter(Input::new(text).range(chunk.0..)) {
+        let Some(whole) = caps.get_match() else {
+            continue;
+        };
+        if whole.start() > start {
+            break;
+        }
+        if whole.end() >= chunk.1 {
+            if let Some(span) = caps.get_group(group_idx) {
+                push_match(out, compiled, set_idx, text, span.start, span.end, text_limit);
+            }
+            break;
+        }
+    }
+    true
+}
 
-    // For each matching pattern, find actual positions
-    for set_idx in matching_indices {
-        let regex = &compiled.individual_regexes[set_idx];
-        let meta = &compiled.metadata[set_idx];
+/// Scan one text in a single piece
+fn scan_text(compiled: &CompiledPatterns, text: &str, text_limit: Option<usize>) -> Vec<RawMatch> {
+    // Region-restricted patterns and hex dump suppression need the document's regions first
+    let regions =
+        (compiled.region_masks.is_some() || compiled.suppress_hexdump).then(|| regions::classify_regions(text));
+    let regions = regions.as_deref();
+    let mut matches = match &compiled.two_pass {
+        Some(plan) => find_matches_two_pass(compiled, plan, text, regions, text_limit),
+        None => find_matches_single_pass(compiled, text, regions, text_limit),
+    };
+    if let Some(regions) = regions.filter(|_| compiled.suppress_hexdump) {
+        // Judge the whole span: match text may be a truncated preview
+        regions::drop_numeric_in_hexdumps(regions, &mut matches, |m| (m.start, m.end, &text[m.start..m.end]));
+    }
+    matches
+}
 
-        // Use captures if we need a specific group, otherwise find_iter is faster
-        if meta.group_idx > 0 {
-            // Need to extract a specific capture group
-            for caps in regex.captures_iter(text) {
-                if let Some(group_match) = caps.get(meta.group_idx) {
-                    let matched_text = group_match.as_str();
-                    if !matched_text.is_empty() && !matched_text.trim().is_empty() {
-                        matches.push(RawMatch {
-                            pattern_id: set_idx,
-                            start: group_match.start(),
-                            end: group_match.end(),
-                            text: matched_text.to_string(),
-                            entity_type: meta.entity_type.clone(),
-                            confidence: meta.confidence,
-                        });
-                    }
+/// Run one pattern's pass, timed by the watchdog when one is enabled
+fn run_pass(compiled: &CompiledPatterns, set_idx: usize, text: &str, pass: impl FnOnce()) {
+    match &compiled.stats.watchdog {
+        Some(watchdog) => {
+            let entity_type = &compiled.metadata[set_idx].entity_type;
+            watchdog.time(set_idx, entity_type, text.len(), pass)
+        }
+        None => pass(),
+    }
+}
+
+/// Single-pass scan: RegexSet to find matching patterns, then sweep each
+fn find_matches_single_pass(
+    compiled: &CompiledPatterns,
+    text: &str,
+    regions: Option<&[Region]>,
+    text_limit: Option<usize>,
+) -> Vec<RawMatch> {
+    let mut matches = Vec::new();
+
+    with_scratch(|scratch| {
+        // Fast check: which patterns match anywhere in text?
+        compiled.set_matches(text, scratch);
+
+        // For each matching pattern, find actual positions
+        for &set_idx in &scratch.hits {
+            run_pass(compiled, set_idx, text, || {
+                sweep_pattern(compiled, set_idx, text, regions, text_limit, &mut matches)
+            });
+        }
+    });
+
+    matches
+}
+
+/// Two-pass scan: literal candidates first, anchored verification second
+///
+/// Produces the same matches, in the same order, as the single-pass scan.
+fn find_matches_two_pass(
+    compiled: &CompiledPatterns,
+    plan: &TwoPassPlan,
+    text: &str,
+    regions: Option<&[Region]>,
+    text_limit: Option<usize>,
+) -> Vec<RawMatch> {
+    let mut matches = Vec::new();
+
+    with_scratch(|scratch| {
+        // Full-scan patterns still need the RegexSet to tell whether they occur
+        let full_scan = plan.has_full_scan_patterns();
+        if full_scan {
+            compiled.set_matches(text, scratch);
+        }
+        let full_scan_hits = &scratch.hits;
+        let candidates = &mut scratch.candidates;
+        plan.candidates(text, candidates);
+
+        for (set_idx, positions) in candidates.iter().enumerate() {
+            if !plan.is_triggered(set_idx) {
+                if full_scan && full_scan_hits.binary_search(&set_idx).is_ok() {
+                    run_pass(compiled, set_idx, text, || {
+                        sweep_pattern(compiled, set_idx, text, regions, text_limit, &mut matches)
+                    });
                 }
+                continue;
+            }
+
+            if positions.is_empty() {
+                continue;
+            }
+            // Candidates everywhere: one sweep is cheaper than many anchored searches.
+            // Region-restricted patterns always sweep their regions, which keeps
+            // matches at region edges identical to the single-pass scan.
+            if positions.len() * DENSE_CANDIDATE_RATIO > text.len() || compiled.is_region_restricted(set_idx) {
+                run_pass(compiled, set_idx, text, || {
+                    sweep_pattern(compiled, set_idx, text, regions, text_limit, &mut matches)
+                });
+            } else {
+                run_pass(compiled, set_idx, text, || {
+                    verify_candidates(compiled, set_idx, text, positions, text_limit, &mut matches)
+                });
+            }
+        }
+    });
+
+    matches
+}
+
+/// Run one pattern over the text, or only over its allowed regions
+fn sweep_pattern(
+    compiled: &CompiledPatterns,
+    set_idx: usize,
+    text: &str,
+    regions: Option<&[Region]>,
+    text_limit: Option<usize>,
+    matches: &mut Vec<RawMatch>,
+) {
+    match (regions, &compiled.region_masks) {
+        (Some(regions), Some(masks)) if masks[set_idx] != ALL_REGIONS => {
+            for (start, end) in regions::allowed_ranges(regions, masks[set_idx]) {
+                sweep_range(compiled, set_idx, text, start, end, text_limit, matches);
+            }
+        }
+        _ => sweep_range(compiled, set_idx, text, 0, text.len(), text_limit, matches),
+    }
+}
+
+/// Run one pattern over text[start..end], with the rest of the text
+/// still visible to look-around assertions
+fn sweep_range(
+    compiled: &CompiledPatterns,
+    set_idx: usize,
+    text: &str,
+    start: usize,
+    end: usize,
+    text_limit: Option<usize>,
+    matches: &mut Vec<RawMatch>,
+) {
+    let meta = &compiled.metadata[set_idx];
+    let regex = match &compiled.engines[set_idx] {
+        Engine::Regex(regex) => regex,
+        Engine::Literals(alt) => {
+            alt.find_iter(text, start, end, |s, e| push_match(matches, compiled, set_idx, text, s, e, text_limit));
+            return;
+        }
+    };
+    let input = Input::new(text).range(start..end);
+
+    // Use captures if we need a specific group, otherwise find_iter is faster
+    if meta.group_idx > 0 {
+        // Need to extract a specific capture group
+        for caps in regex.captures_iter(input) {
+            if let Some(span) = caps.get_group(meta.group_idx) {
+                push_match(matches, compiled, set_idx, text, span.start, span.end, text_limit);
+            }
+        }
+    } else {
+        // Use faster find_iter when we want the entire match
+        for m in regex.find_iter(input) {
+            push_match(matches, compiled, set_idx, text, m.start(), m.end(), text_limit);
+        }
+    }
+}
+
+/// Run one pattern anchored at each candidate position
+///
+/// Candidates are visited in order and skipped once covered by a previous
+/// match, which reproduces find_iter's leftmost, non-overlapping results.
+fn verify_candidates(
+    compiled: &CompiledPatterns,
+    set_idx: usize,
+    text: &str,
+    positions: &[usize],
+    text_limit: Option<usize>,
+    matches: &mut Vec<RawMatch>,
+) {
+    let Engine::Regex(regex) = &compiled.engines[set_idx] else {
+        unreachable!("literal alternations are never two-pass triggered");
+    };
+    let meta = &compiled.metadata[set_idx];
+    let mut caps = regex.create_captures();
+    let mut last_end = 0;
+
+    for &pos in positions {
+        if pos < last_end {
+            continue;
+        }
+        let input = Input::new(text).range(pos..).anchored(Anchored::Yes);
+
+        if meta.group_idx > 0 {
+            regex.search_captures(&input, &mut caps);
+            let Some(whole) = caps.get_match() else {
+                continue;
+            };
+            if whole.is_empty() {
+                continue;
+            }
+            last_end = whole.end();
+            if let Some(span) = caps.get_group(meta.group_idx) {
+                push_match(matches, compiled, set_idx, text, span.start, span.end, text_limit);
             }
         } else {
-            // Use faster find_iter when we want the entire match
-            for m in regex.find_iter(text) {
-                let matched_text = m.as_str();
-                if !matched_text.is_empty() && !matched_text.trim().is_empty() {
-                    matches.push(RawMatch {
-                        pattern_id: set_idx,
-                        start: m.start(),
-                        end: m.end(),
-                        text: matched_text.to_string(),
-                        entity_type: meta.entity_type.clone(),
-                        confidence: meta.confidence,
-                    });
-                }
+            let Some(m) = regex.search(&input) else {
+                continue;
+            };
+            if m.is_empty() {
+                continue;
             }
+            last_end = m.end();
+            push_match(matches, compiled, set_idx, text, m.start(), m.end(), text_limit);
+        }
+    }
+}
+
+/// Share one context object across all matches of a scan (no per-match copy)
+pub(crate) fn attach_context(matches: &mut [RawMatch], context: Option<PyObject>) {
+    if let Some(ctx) = context {
+        let shared = Arc::new(ctx);
+        for m in matches.iter_mut() {
+            m.context = Some(Arc::clone(&shared));
         }
     }
+}
 
-    Ok(matches)
+/// Record a match unless it is empty or whitespace-only
+///
+/// Spans are first snapped to whole grapheme clusters with edge bidi
+/// controls removed (see `grapheme::adjust_span`). Only a preview of
+/// matches longer than `text_limit` is copied (Some(0): none of it).
+fn push_match(
+    matches: &mut Vec<RawMatch>,
+    compiled: &CompiledPatterns,
+    set_idx: usize,
+    text: &str,
+    start: usize,
+    end: usize,
+    text_limit: Option<usize>,
+) {
+    let Some((start, end)) = grapheme::adjust_span(text, start, end) else {
+        return;
+    };
+    let matched_text = &text[start..end];
+    if !matched_text.is_empty() && !matched_text.trim().is_empty() {
+        let meta = &compiled.metadata[set_idx];
+        let preview = text_limit.map_or(matched_text, |max| text_preview(matched_text, max));
+        matches.push(RawMatch {
+            pattern_id: set_idx,
+            start,
+            end,
+            text: preview.to_string(),
+            entity_type: meta.entity_type.clone(),
+            confidence: meta.confidence,
+            truncated: preview.len() < matched_text.len(),
+            context: None,
+        });
+    }
+}
+
+/// The longest prefix of `text` of at most `max_bytes` that ends on a char boundary
+pub(crate) fn text_preview(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::regions::RegionKind;
 
     #[test]
     fn test_pattern_compilation() {
@@ -294,8 +1647,8 @@ mod tests {
             (r"[a-z]+@[a-z]+\.[a-z]+".to_string(), "EMAIL".to_string(), 0.95, 0),
         ];
 
-        let compiled = compile_patterns(&patterns);
-        assert_eq!(compiled.individual_regexes.len(), 3);
+        let compiled = compile_patterns(&patterns, false);
+        assert_eq!(compiled.engines.len(), 3);
     }
 
     #[test]
@@ -305,9 +1658,9 @@ mod tests {
             (r"\b[a-z]+@[a-z]+\.[a-z]+\b".to_string(), "EMAIL".to_string(), 0.95, 0),
         ];
 
-        let compiled = compile_patterns(&patterns);
+        let compiled = compile_patterns(&patterns, false);
         let text = "SSN: 123-45-6789, email: test@example.com";
-        let matches = find_matches_impl(&compiled, text).unwrap();
+        let matches = find_matches_impl(&compiled, text);
 
         assert_eq!(matches.len(), 2);
     }
@@ -319,12 +1672,306 @@ mod tests {
             (r"SSN:\s*(\d{3}-\d{2}-\d{4})".to_string(), "SSN".to_string(), 0.95, 1),
         ];
 
-        let compiled = compile_patterns(&patterns);
+        let compiled = compile_patterns(&patterns, false);
         let text = "SSN: 123-45-6789";
-        let matches = find_matches_impl(&compiled, text).unwrap();
+        let matches = find_matches_impl(&compiled, text);
 
         assert_eq!(matches.len(), 1);
         assert_eq!(matches[0].text, "123-45-6789");
         assert_eq!(matches[0].start, 5);  // Position of "123" after "SSN: "
     }
+
+    #[test]
+    fn test_two_pass_matches_single_pass() {
+        let patterns = vec![
+            (r"\b\d{3}-\d{2}-\d{4}\b".to_string(), "SSN".to_string(), 0.95, 0),
+            (r"\b[a-z]+@[a-z]+\.[a-z]+\b".to_string(), "EMAIL".to_string(), 0.95, 0),
+            (r"(?i)mrn[:#]?\s*(\d{6,10})".to_string(), "MRN".to_string(), 0.9, 1),
+            (r"\w+ Street".to_string(), "ADDRESS".to_string(), 0.6, 0),
+        ];
+
+        let single = compile_patterns(&patterns, false);
+        let double = compile_patterns(&patterns, true);
+        assert!(double.two_pass.is_some());
+
+        let text = "SSN: 123-45-6789 and 1234-56-7890, email: test@example.com, \
+                    MRN# 00123456 lives on Main Street";
+        let expected = find_matches_impl(&single, text);
+        let actual = find_matches_impl(&double, text);
+
+        assert_eq!(expected.len(), 4);
+        let spans = |ms: &[RawMatch]| ms.iter().map(|m| (m.pattern_id, m.start, m.end)).collect::<Vec<_>>();
+        assert_eq!(spans(&actual), spans(&expected));
+    }
+
+    #[test]
+    fn test_literal_alternation_uses_automaton() {
+        let terms: Vec<String> = (0..500).map(|i| format!("acct{:04}", i)).collect();
+        let patterns = vec![
+            (r"\b\d{3}-\d{2}-\d{4}\b".to_string(), "SSN".to_string(), 0.95, 0),
+            (format!(r"\b({})\b", terms.join("|")), "ACCOUNT".to_string(), 0.8, 1),
+        ];
+        let text = "acct0042 and 123-45-6789, not xacct0007 or acct0499x, but acct0499.";
+
+        for two_pass in [false, true] {
+            let compiled = compile_patterns(&patterns, two_pass);
+            assert!(matches!(compiled.engines[1], Engine::Literals(_)));
+            assert!(compiled.memory_usage()["literal_bytes"] > 0);
+
+            let found: Vec<(usize, &str)> = find_matches_impl(&compiled, text)
+                .iter()
+                .map(|m| (m.pattern_id, &text[m.start..m.end]))
+                .collect();
+            assert_eq!(found, [(0, "123-45-6789"), (1, "acct0042"), (1, "acct0499")]);
+        }
+    }
+
+    #[test]
+    fn test_region_restricted_patterns() {
+        let patterns = vec![
+            (r"\b[a-z]+@[a-z]+\.[a-z]+\b".to_string(), "EMAIL".to_string(), 0.95, 0),
+            (r"sk_[a-z0-9]{8}".to_string(), "API_KEY".to_string(), 0.9, 0),
+        ];
+        let text = "Mail jane@example.com about sk_abcd1234 today.\n\
+                    let key = \"sk_efgh5678\"; // owner: bob@example.com\n";
+        let mut masks = HashMap::new();
+        masks.insert("EMAIL".to_string(), RegionKind::Prose.bit());
+        masks.insert("API_KEY".to_string(), RegionKind::Code.bit());
+
+        for two_pass in [false, true] {
+            let mut compiled = compile_patterns(&patterns, two_pass);
+            assert_eq!(find_matches_impl(&compiled, text).len(), 4);
+            compiled.restrict_regions(&masks);
+            let found: Vec<String> = find_matches_impl(&compiled, text).into_iter().map(|m| m.text).collect();
+            assert_eq!(found, ["jane@example.com", "sk_efgh5678"]);
+        }
+    }
+
+    #[test]
+    fn test_warm_runs_every_engine_without_stats() {
+        let mut patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0)];
+        let terms: Vec<String> = (0..crate::literal::MIN_LITERAL_TERMS).map(|i| format!("acme{}", i)).collect();
+        patterns.push((format!(r"\b(?:{})\b", terms.join("|")), "ORG".to_string(), 0.5, 0));
+        let compiled = compile_patterns(&patterns, true);
+
+        compiled.warm(&crate::warmup::synthetic_text(Some("acme7 and 123-45-6789")));
+        assert_eq!(compiled.stats.snapshot()["scans"], 0);
+        assert_eq!(find_matches_impl(&compiled, "acme7 123-45-6789").len(), 2);
+    }
+
+    #[test]
+    fn test_suppress_hexdump_drops_numeric_matches() {
+        let patterns = vec![
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0),
+            (r"\b\d{2} \d{2} \d{2} \d{2}\b".to_string(), "PHONE".to_string(), 0.5, 0),
+            (r"[a-z]+@[a-z]+\.com".to_string(), "EMAIL".to_string(), 0.8, 0),
+        ];
+        let text = "SSN 123-45-6789 in the ticket.\n\
+                    00000000  31 32 33 2d 34 35 2d 36  37 38 39 20 61 40 62 2e  |123-45-6789 a@b.|\n\
+                    00000010  63 6f 6d 0a 00 00 00 00  00 00 00 00 00 00 00 00  |com.............|\n\
+                    00000020: 123-45-6789 bob@ex.com\n";
+        let mut compiled = compile_patterns(&patterns, false);
+        let all = find_matches_impl(&compiled, text).len();
+
+        compiled.suppress_hexdump = true;
+        let kept: Vec<(String, String)> =
+            find_matches_impl(&compiled, text).into_iter().map(|m| (m.entity_type, m.text)).collect();
+        assert!(all > kept.len());
+        assert_eq!(
+            kept,
+            [
+                ("SSN".to_string(), "123-45-6789".to_string()),
+                ("SSN".to_string(), "123-45-6789".to_string()),
+                ("EMAIL".to_string(), "bob@ex.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_text_bytes_keeps_full_span() {
+        let patterns = vec![
+            (r"secret=[A-Za-z0-9+/é]+".to_string(), "SECRET".to_string(), 0.9, 0),
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0),
+        ];
+        let blob = format!("secret=abcd{}", "é".repeat(5000));
+        let text = format!("{} and 123-45-6789", blob);
+        let mut compiled = compile_patterns(&patterns, false);
+        compiled.max_text_bytes = Some(12);
+
+        let matches = find_matches_impl(&compiled, &text);
+        assert_eq!(matches.len(), 2);
+        // Cut back to a char boundary inside the first "é"
+        assert_eq!(matches[0].text, "secret=abcd");
+        assert!(matches[0].truncated);
+        assert_eq!((matches[0].start, matches[0].end), (0, blob.len()));
+        assert_eq!(matches[1].text, "123-45-6789");
+        assert!(!matches[1].truncated);
+
+        // The span-only scan reports the same spans
+        let spans: Vec<(usize, usize)> = matches.iter().map(|m| (m.start, m.end)).collect();
+        assert_eq!(find_match_spans(&compiled, &text), spans);
+    }
+
+    #[test]
+    fn test_chunked_scan_matches_whole_scan() {
+        let patterns = vec![
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0),
+            (r"[a-z]+@[a-z]+\.com".to_string(), "EMAIL".to_string(), 0.8, 0),
+        ];
+        let text = "é ssn 123-45-6789 mail ann@ex.com ".repeat(40);
+        let mut compiled = compile_patterns(&patterns, false);
+        let whole: Vec<(usize, usize, usize)> =
+            find_matches_impl(&compiled, &text).iter().map(|m| (m.pattern_id, m.start, m.end)).collect();
+        assert_eq!(whole.len(), 80);
+
+        for (threshold, overlap) in [(64, 16), (100, 32), (257, 20)] {
+            compiled.chunking = Some(Chunking::new(threshold, overlap).unwrap());
+            let chunked: Vec<(usize, usize, usize)> =
+                find_matches_impl(&compiled, &text).iter().map(|m| (m.pattern_id, m.start, m.end)).collect();
+            assert_eq!(chunked, whole, "threshold {} overlap {}", threshold, overlap);
+        }
+        assert_eq!(compiled.stats.snapshot()["scans"], 4);
+    }
+
+    #[test]
+    fn test_chunked_scan_of_normalized_text() {
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0)];
+        let text = "ssn 123%2D45%2D6789 and 987-65-4321; ".repeat(30);
+        let mut compiled = compile_patterns(&patterns, false);
+        compiled.normalizer = Pipeline::from_names(&["decode_percent".to_string()]).unwrap();
+        let spans = |compiled: &CompiledPatterns| {
+            find_matches_impl(compiled, &text).iter().map(|m| (m.start, m.end, m.text.clone())).collect::<Vec<_>>()
+        };
+        let whole = spans(&compiled);
+        assert_eq!(whole.len(), 60);
+        assert_eq!(&text[whole[0].0..whole[0].1], "123%2D45%2D6789");
+
+        // The whole normalized copy is chunked: offsets still map to the input
+        compiled.chunking = Some(Chunking::new(100, 32).unwrap());
+        assert_eq!(spans(&compiled), whole);
+    }
+
+    #[test]
+    fn test_chunked_scan_keeps_matches_longer_than_overlap() {
+        let patterns = vec![
+            (r"key=[a-z]+".to_string(), "SECRET".to_string(), 0.9, 0),
+            (r"token: ([A-Z]+)".to_string(), "TOKEN".to_string(), 0.9, 1),
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0),
+        ];
+        let text = format!(
+            "{}key={} {}token: {} ssn 123-45-6789 {}",
+            " ".repeat(600),
+            "a".repeat(700),
+            " ".repeat(500),
+            "Q".repeat(900),
+            " ".repeat(1200)
+        );
+        let mut compiled = compile_patterns(&patterns, false);
+        let spans = |compiled: &CompiledPatterns| -> Vec<(usize, usize, usize)> {
+            find_matches_impl(compiled, &text).iter().map(|m| (m.pattern_id, m.start, m.end)).collect()
+        };
+        let whole = spans(&compiled);
+        assert_eq!(whole.len(), 3);
+        assert_eq!(whole[0], (0, 600, 1304));
+
+        // Both long matches cross a chunk boundary and exceed the overlap
+        compiled.chunking = Some(Chunking::new(1024, 256).unwrap());
+        assert_eq!(spans(&compiled), whole);
+    }
+
+    #[test]
+    fn test_normalized_scan_reports_original_offsets() {
+        let patterns = vec![(r"\b\d{3}-\d{2}-\d{4}\b".to_string(), "SSN".to_string(), 0.9, 0)];
+        let text = "ssn ４５６\u{2013}７８\u{2013}9012 and 123%2D45%2D6789";
+        let mut compiled = compile_patterns(&patterns, false);
+        assert!(find_matches_impl(&compiled, text).is_empty());
+
+        compiled.normalizer = Pipeline::from_names(&["unicode_fold".to_string(), "decode_percent".to_string()]).unwrap();
+        let matches = find_matches_impl(&compiled, text);
+        let found: Vec<(&str, &str)> = matches.iter().map(|m| (&text[m.start..m.end], m.text.as_str())).collect();
+        assert_eq!(found, [("４５６\u{2013}７８\u{2013}9012", "456-78-9012"), ("123%2D45%2D6789", "123-45-6789")]);
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let patterns = vec![
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.95, 0),
+            (r"[a-z]+@[a-z]+\.[a-z]+".to_string(), "EMAIL".to_string(), 0.95, 0),
+        ];
+
+        let single = compile_patterns(&patterns, false).memory_usage();
+        assert!(single["regex_bytes"] > 0);
+        assert!(single["regex_set_bytes"] > 0);
+        assert_eq!(single["two_pass_bytes"], 0);
+
+        let double = compile_patterns(&patterns, true).memory_usage();
+        assert!(double["two_pass_bytes"] > 0);
+        let parts: usize = double.iter().filter(|(k, _)| **k != "total_bytes").map(|(_, v)| v).sum();
+        assert_eq!(double["total_bytes"], parts);
+    }
+
+    #[test]
+    fn test_effective_config() {
+        let patterns = vec![
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.95, 0),
+            (r"(unclosed".to_string(), "BROKEN".to_string(), 0.5, 0),
+            (r"[a-z]+@[a-z]+\.[a-z]+".to_string(), "EMAIL".to_string(), 0.9, 0),
+        ];
+        let mut compiled = compile_patterns(&patterns, false);
+        compiled.restrict_regions(&HashMap::from([("EMAIL".to_string(), RegionKind::Prose.bit())]));
+        let post = PostProcessors::from_config(HashMap::from([("SSN".to_string(), vec!["digits_only".to_string()])]))
+            .unwrap();
+
+        let root = compiled.effective_config(patterns.len(), &post, &Repairs::default());
+        assert_eq!(root["failed_patterns"], Value::List(vec![Value::Int(1)]));
+        let Value::List(exported) = &root["patterns"] else {
+            panic!("patterns is not a list");
+        };
+        let Value::Table(email) = &exported[1] else {
+            panic!("pattern is not a table");
+        };
+        assert_eq!(email["input_index"], Value::Int(2));
+        assert_eq!(email["regions"], Value::strings(&["prose"]));
+        assert!(!matches!(&exported[0], Value::Table(ssn) if ssn.contains_key("regions")));
+
+        let toml = config::render(&root, config::Format::Toml);
+        assert!(toml.contains("[postprocess]\nSSN = [\"digits_only\"]\n"));
+        let again = compiled.effective_config(patterns.len(), &post, &Repairs::default());
+        assert_eq!(toml, config::render(&again, config::Format::Toml));
+    }
+
+    #[test]
+    fn test_spans_keep_combining_marks() {
+        let patterns = vec![(r"[\x{05D0}-\x{05EA}]+".to_string(), "NAME".to_string(), 0.5, 0)];
+        let compiled = compile_patterns(&patterns, false);
+
+        // Hebrew "david" with niqqud on each letter, wrapped in RLM
+        let text = "\u{200F}\u{05D3}\u{05B8}\u{05D5}\u{05B4}\u{05D3}\u{200F}";
+        let matches = find_matches_impl(&compiled, text);
+
+        // Every mark stays attached to its letter; no RLM in any span
+        let covered: String = matches.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(covered, "\u{05D3}\u{05B8}\u{05D5}\u{05B4}\u{05D3}");
+        assert!(matches.iter().all(|m| !m.text.contains('\u{200F}')));
+    }
+
+    #[test]
+    fn test_watchdog_times_each_pattern() {
+        let patterns = vec![
+            (r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.95, 0),
+            (r"[a-z]+@[a-z]+\.[a-z]+".to_string(), "EMAIL".to_string(), 0.95, 0),
+        ];
+        let mut compiled = compile_patterns(&patterns, false);
+        compiled.stats = ScanStats::with_watchdog(Some(Watchdog::new(Duration::ZERO)));
+
+        let text = "SSN: 123-45-6789";
+        find_matches_impl(&compiled, text);
+
+        // Only the pattern that matched ran a pass
+        let events = compiled.stats.watchdog.as_ref().unwrap().events();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].entity_type.as_str(), events[0].input_len), ("SSN", text.len()));
+        let snap = compiled.stats.snapshot();
+        assert_eq!((snap["scans"], snap["matches"]), (1, 1));
+    }
 }
@@ -0,0 +1,208 @@
+//! Metro 2 credit-report (tradeline) structural detector
+//!
+//! Metro 2 character-format exports are fixed-width: a 426-byte base
+//! segment per account, optionally followed by appended segments (J1, K1,
+//! ...), with HEADER and TRAILER records around them. Flat scanning of
+//! these files mostly finds runs of digits; recognizing the record lets us
+//! report exactly which consumer fields are present.
+//!
+//! Records may be newline-delimited or packed back to back; each starts
+//! with a 4-digit record descriptor word (RDW) giving its length.
+
+use crate::layout::{self, FieldMatch};
+use crate::validators;
+
+/// Length of the base segment
+pub const BASE_SEGMENT_LEN: usize = 426;
+
+/// Confidence for a populated field of a recognized base segment
+pub const CONFIDENCE_STRUCTURAL: f32 = 0.95;
+/// Confidence for a field whose content fails the entity's format check
+pub const CONFIDENCE_MALFORMED: f32 = 0.6;
+
+/// Base segment consumer fields: (name, zero-based start, length, entity)
+///
+/// Starts are the CDIA Base Segment positions minus one; position 231 is
+/// the Consumer Transaction Type, so the surname begins at 232.
+const BASE_FIELDS: &[(&str, usize, usize, &str)] = &[
+    ("consumer_account_number", 42, 30, "ACCOUNT_NUMBER"),
+    ("surname", 231, 25, "NAME"),
+    ("first_name", 256, 20, "NAME"),
+    ("middle_name", 276, 20, "NAME"),
+    ("ssn", 297, 9, "SSN"),
+    ("date_of_birth", 306, 8, "DATE_DOB"),
+    ("telephone", 314, 10, "PHONE"),
+    ("address_line_1", 329, 32, "ADDRESS"),
+    ("address_line_2", 361, 32, "ADDRESS"),
+    ("city", 393, 20, "ADDRESS"),
+    ("postal_code", 415, 9, "ZIP"),
+];
+
+/// Record length from the RDW at the start of `data`
+fn rdw(data: &str) -> Option<usize> {
+    let word = data.get(..4)?;
+    if !word.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    word.parse().ok()
+}
+
+/// Whether a record is a base segment (not a header or trailer)
+fn is_base_segment(record: &str) -> bool {
+    let bytes = record.as_bytes();
+    record.len() >= 19
+        && record.is_ascii()
+        && bytes[4] == b'1'
+        && bytes[5..19].iter().all(|b| b.is_ascii_digit())
+}
+
+/// Whether a field value is Metro 2 filler for "not reported"
+fn is_filler(value: &str) -> bool {
+    value.bytes().all(|b| b == b'0' || b == b'9')
+}
+
+/// Whether the value passes the entity's format check
+fn well_formed(entity_type: &str, value: &str) -> bool {
+    match entity_type {
+        "SSN" => validators::ssn_format(value),
+        "DATE_DOB" => {
+            value.len() == 8
+                && value.bytes().all(|b| b.is_ascii_digit())
+                && matches!(value[..2].parse::<u8>(), Ok(1..=12))
+                && matches!(value[2..4].parse::<u8>(), Ok(1..=31))
+        }
+        "PHONE" => value.len() == 10 && value.bytes().all(|b| b.is_ascii_digit()),
+        _ => true,
+    }
+}
+
+/// Consumer fields of one base segment starting at `offset` in the input
+fn scan_base_segment(record: &str, offset: usize, index: usize, out: &mut Vec<FieldMatch>) {
+    for &(name, start, len, entity_type) in BASE_FIELDS {
+        let Some((s, e)) = layout::trimmed_span(record, start, len) else {
+            continue;
+        };
+        let value = &record[s..e];
+        if is_filler(value) {
+            continue;
+        }
+        out.push(FieldMatch {
+            record: index,
+            field: name.to_string(),
+            entity_type: entity_type.to_string(),
+            start: offset + s,
+            end: offset + e,
+            text: value.to_string(),
+            confidence: if well_formed(entity_type, value) {
+                CONFIDENCE_STRUCTURAL
+            } else {
+                CONFIDENCE_MALFORMED
+            },
+        });
+    }
+}
+
+/// Find consumer fields in Metro 2 base segments
+///
+/// `record` in each FieldMatch counts base segments only, so it matches
+/// the account's position in the file.
+pub fn find_metro2(text: &str) -> Vec<FieldMatch> {
+    let mut found = Vec::new();
+    let mut index = 0;
+    for (line_start, line) in layout::lines(text) {
+        let mut pos = 0;
+        while let Some(len) = line.get(pos..).and_then(rdw) {
+            if len <= 4 {
+                break;
+            }
+            // Editors often strip trailing padding, so a record may be short
+            let end = (pos + len).min(line.len());
+            let Some(record) = line.get(pos..end) else {
+                break;
+            };
+            if len >= BASE_SEGMENT_LEN && is_base_segment(record) {
+                scan_base_segment(record, line_start + pos, index, &mut found);
+                index += 1;
+            }
+            pos = end;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A base segment with values at one-based CDIA field positions
+    fn base_segment(fields: &[(usize, &str)]) -> String {
+        let mut record = vec![b' '; BASE_SEGMENT_LEN];
+        record[..4].copy_from_slice(b"0426");
+        record[4] = b'1';
+        record[5..19].copy_from_slice(b"01152024120000");
+        for &(position, value) in fields {
+            record[position - 1..position - 1 + value.len()].copy_from_slice(value.as_bytes());
+        }
+        String::from_utf8(record).unwrap()
+    }
+
+    fn sample() -> String {
+        base_segment(&[
+            (43, "ACCT0001234567"),
+            (231, "1"),
+            (232, "DOE"),
+            (257, "JANE"),
+            (297, "J"),
+            (298, "123456789"),
+            (307, "04151980"),
+            (315, "0000000000"),
+            (328, "US"),
+            (330, "12 MAIN ST"),
+            (414, "GA"),
+            (416, "30301"),
+        ])
+    }
+
+    #[test]
+    fn test_fields_attributed() {
+        let header = format!("0426HEADER{}", " ".repeat(416));
+        let text = format!("{}\n{}\n", header, sample());
+        let found = find_metro2(&text);
+        let fields: Vec<&str> = found.iter().map(|m| m.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            ["consumer_account_number", "surname", "first_name", "ssn", "date_of_birth", "address_line_1", "postal_code"]
+        );
+        let values: Vec<&str> = found.iter().map(|m| &text[m.start..m.end]).collect();
+        assert_eq!(values, ["ACCT0001234567", "DOE", "JANE", "123456789", "04151980", "12 MAIN ST", "30301"]);
+        let ssn = &found[3];
+        assert_eq!((ssn.entity_type.as_str(), ssn.record), ("SSN", 0));
+        assert_eq!(&text[ssn.start..ssn.end], "123456789");
+        assert_eq!(ssn.confidence, CONFIDENCE_STRUCTURAL);
+    }
+
+    #[test]
+    fn test_packed_records_and_malformed_values() {
+        let second = base_segment(&[(298, "000456789")]);
+        let text = format!("{}{}", sample(), second);
+        let found = find_metro2(&text);
+        let ssn: Vec<&FieldMatch> = found.iter().filter(|m| m.field == "ssn").collect();
+        assert_eq!(ssn.len(), 2);
+        assert_eq!(ssn[1].record, 1);
+        assert_eq!(ssn[1].start, BASE_SEGMENT_LEN + 297);
+        assert_eq!(ssn[1].confidence, CONFIDENCE_MALFORMED);
+    }
+
+    #[test]
+    fn test_stripped_padding() {
+        let text = format!("{}\n", sample().trim_end());
+        let found = find_metro2(&text);
+        assert_eq!(found.last().map(|m| m.field.as_str()), Some("postal_code"));
+    }
+
+    #[test]
+    fn test_ignores_non_metro2_text() {
+        assert!(find_metro2("0426 is not a record\n1234567890").is_empty());
+        assert!(find_metro2(&"9".repeat(500)).is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! National identifier validators
+//!
+//! Structural checks for government person identifiers that carry a
+//! check digit or an embedded date. Spaces and hyphens are ignored; any
+//! other non-digit makes the value invalid (except the Corsican
+//! department letters of a French NIR).
+
+/// Digits of `s` with spaces and hyphens skipped; None on any other character
+fn digits(s: &str) -> Option<Vec<u32>> {
+    let mut out = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '0'..='9' => out.push(c as u32 - '0' as u32),
+            ' ' | '-' => {}
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+/// Luhn sum over digits, the last digit being the check digit
+#[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+fn luhn_ok(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 1 { (d * 2) / 10 + (d * 2) % 10 } else { d })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Israeli Teudat Zehut (identity number)
+///
+/// Up to 9 digits, left-padded with zeros to 9. Digits are weighted
+/// 1, 2, 1, 2, ... from the left, two-digit products are reduced to their
+/// digit sum, and the total must be a multiple of 10.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn teudat_zehut(value: &str) -> bool {
+    let Some(d) = digits(value) else {
+        return false;
+    };
+    if d.is_empty() || d.len() > 9 || d.iter().all(|&x| x == 0) {
+        return false;
+    }
+    let padded: Vec<u32> = std::iter::repeat_n(0, 9 - d.len()).chain(d).collect();
+    let sum: u32 = padded
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let p = x * if i % 2 == 0 { 1 } else { 2 };
+            p / 10 + p % 10
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Greek AMKA (social security number)
+///
+/// 11 digits: date of birth as DDMMYY, four serial digits, and a Luhn
+/// check digit over the whole number.
+pub fn amka(value: &str) -> bool {
+    let Some(d) = digits(value) else {
+        return false;
+    };
+    if d.len() != 11 {
+        return false;
+    }
+    let (day, month, year) = (d[0] * 10 + d[1], d[2] * 10 + d[3], d[4] * 10 + d[5]);
+    // The century is not encoded; YY divisible by 4 is a leap year in
+    // at least one of 19YY / 20YY (2000 covers 00)
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day) && luhn_ok(&d)
+}
+
+/// UK NHS number
+///
+/// 10 digits. The first nine are weighted 10 down to 2; 11 minus the sum
+/// mod 11 is the check digit (11 means 0, and 10 never occurs in a valid
+/// number).
+pub fn nhs_number(value: &str) -> bool {
+    let Some(d) = digits(value) else {
+        return false;
+    };
+    if d.len() != 10 {
+        return false;
+    }
+    let sum: u32 = d[..9].iter().zip((2..=10).rev()).map(|(&x, w)| x * w).sum();
+    match 11 - sum % 11 {
+        11 => d[9] == 0,
+        10 => false,
+        check => d[9] == check,
+    }
+}
+
+/// French NIR (numéro de sécurité sociale, "INSEE number") with its key
+///
+/// 13 characters (sex, birth year and month, department, commune, order
+/// number) plus a 2-digit key equal to 97 minus the 13-digit number mod 97.
+/// Corsican departments 2A and 2B count as 19 and 18 for the key. The sex
+/// digit must be 1-4, 7 or 8 and the month 01-12, 20-42 or 50-99 (the
+/// ranges used when the birth month is unknown).
+pub fn nir(value: &str) -> bool {
+    let mut compact: String = value.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    if compact.len() != 15 || !compact.is_ascii() {
+        return false;
+    }
+    match &compact[5..7] {
+        "2A" | "2a" => compact.replace_range(5..7, "19"),
+        "2B" | "2b" => compact.replace_range(5..7, "18"),
+        _ => {}
+    }
+    let Some(d) = digits(&compact) else {
+        return false;
+    };
+    let month = d[3] * 10 + d[4];
+    if !matches!(d[0], 1..=4 | 7 | 8) || !matches!(month, 1..=12 | 20..=42 | 50..=99) {
+        return false;
+    }
+    let number = d[..13].iter().fold(0u64, |n, &x| n * 10 + x as u64);
+    let key = d[13] * 10 + d[14];
+    key as u64 == 97 - number % 97
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_teudat_zehut() {
+        assert!(teudat_zehut("000000018"));
+        assert!(teudat_zehut("18")); // Padded to 000000018
+        assert!(teudat_zehut("123456782"));
+        assert!(teudat_zehut("12345678-2"));
+        assert!(!teudat_zehut("123456789")); // Wrong check digit
+        assert!(!teudat_zehut("000000000"));
+        assert!(!teudat_zehut("1234567890")); // Too long
+        assert!(!teudat_zehut("12345678a"));
+    }
+
+    #[test]
+    fn test_nhs_number() {
+        assert!(nhs_number("9434765919"));
+        assert!(nhs_number("943 476 5919"));
+        assert!(nhs_number("943-476-5919"));
+        assert!(!nhs_number("9434765918")); // Wrong check digit
+        assert!(!nhs_number("1234567890")); // Remainder 10: never issued
+        assert!(!nhs_number("943476591")); // Too short
+        assert!(!nhs_number("94347659I9"));
+    }
+
+    #[test]
+    fn test_nir() {
+        assert!(nir("184127645108946"));
+        assert!(nir("1 84 12 76 451 089 46"));
+        assert!(nir("2 69 05 49 588 157 80"));
+        assert!(nir("1 85 07 2A 011 234 58")); // Corsica: 2A counts as 19
+        assert!(nir("1 85 07 2b 011 234 85"));
+        assert!(!nir("1 84 12 76 451 089 47")); // Wrong key
+        assert!(!nir("5 84 12 76 451 089 40")); // Sex digit 5, key fine
+        assert!(!nir("1 84 13 76 451 089 62")); // Month 13, key fine
+        assert!(!nir("1 84 12 76 451 089")); // No key
+    }
+
+    #[test]
+    fn test_amka() {
+        assert!(amka("01019012341"));
+        assert!(amka("290296 12347")); // Feb 29 in a leap year
+        assert!(!amka("01019012342")); // Wrong check digit
+        assert!(!amka("32019012346")); // Day 32, check digit fine
+        assert!(!amka("29029712345")); // Feb 29, non-leap year
+        assert!(!amka("0101901234")); // Too short
+    }
+}
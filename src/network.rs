@@ -0,0 +1,109 @@
+//! CIDR block and host:port validation
+//!
+//! Network topology (subnets, service endpoints) is reported separately
+//! from bare IP addresses, so both forms get their own checks: a CIDR
+//! needs a valid address and an in-range prefix length, a host:port needs
+//! a valid host (IPv4, bracketed IPv6 or hostname) and a port in 1-65535.
+
+use std::net::Ipv6Addr;
+
+use crate::domain::{self, HostClass};
+use crate::validators;
+
+/// Parse a decimal number without sign or leading zeros
+fn parse_plain(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 5 || (s.len() > 1 && s.starts_with('0')) {
+        return None;
+    }
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Split "address/prefix" into the address and prefix length
+///
+/// Returns (address, prefix, is_ipv6).
+pub fn parse_cidr(cidr: &str) -> Option<(&str, u32, bool)> {
+    let (addr, prefix) = cidr.trim().split_once('/')?;
+    let prefix = parse_plain(prefix)?;
+    if validators::ipv4_format(addr) {
+        (prefix <= 32).then_some((addr, prefix, false))
+    } else if addr.parse::<Ipv6Addr>().is_ok() {
+        (prefix <= 128).then_some((addr, prefix, true))
+    } else {
+        None
+    }
+}
+
+/// Validate CIDR notation ("10.0.0.0/8", "2001:db8::/32")
+pub fn cidr_format(cidr: &str) -> bool {
+    parse_cidr(cidr).is_some()
+}
+
+/// Split "host:port" (IPv6 hosts in brackets) into host and port
+pub fn parse_host_port(value: &str) -> Option<(&str, u16)> {
+    let value = value.trim();
+    let (host, port) = if let Some(rest) = value.strip_prefix('[') {
+        let (host, port) = rest.split_once("]:")?;
+        host.parse::<Ipv6Addr>().ok()?;
+        (host, port)
+    } else {
+        let (host, port) = value.rsplit_once(':')?;
+        // An unbracketed IPv6 address is ambiguous with a port
+        if host.contains(':') {
+            return None;
+        }
+        let host_ok = validators::ipv4_format(host)
+            || matches!(domain::classify_hostname(host), HostClass::Public | HostClass::Internal);
+        if !host_ok {
+            return None;
+        }
+        (host, port)
+    };
+    let port = parse_plain(port)?;
+    if port == 0 || port > u16::MAX as u32 {
+        return None;
+    }
+    Some((host, port as u16))
+}
+
+/// Validate a host:port pair with the port in 1-65535
+pub fn host_port_format(value: &str) -> bool {
+    parse_host_port(value).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cidr() {
+        assert!(cidr_format("10.0.0.0/8"));
+        assert!(cidr_format("192.168.1.17/32"));
+        assert!(cidr_format("0.0.0.0/0"));
+        assert!(cidr_format("2001:db8::/32"));
+        assert!(cidr_format("fe80::1/128"));
+        assert!(!cidr_format("10.0.0.0/33"));
+        assert!(!cidr_format("10.0.0.0/08"));
+        assert!(!cidr_format("2001:db8::/129"));
+        assert!(!cidr_format("256.0.0.0/8"));
+        assert!(!cidr_format("10.0.0.0"));
+        assert_eq!(parse_cidr("2001:db8::/48"), Some(("2001:db8::", 48, true)));
+    }
+
+    #[test]
+    fn test_host_port() {
+        assert_eq!(parse_host_port("10.0.0.5:5432"), Some(("10.0.0.5", 5432)));
+        assert_eq!(parse_host_port("[2001:db8::1]:443"), Some(("2001:db8::1", 443)));
+        assert!(host_port_format("db.internal:6379"));
+        assert!(host_port_format("api.example.com:8443"));
+        assert!(host_port_format("localhost:65535"));
+        assert!(!host_port_format("localhost:65536"));
+        assert!(!host_port_format("10.0.0.5:0"));
+        assert!(!host_port_format("10.0.0.5:080"));
+        assert!(!host_port_format("2001:db8::1:443"));
+        assert!(!host_port_format("notes.txt:12"));
+        assert!(!host_port_format("10.0.0.5"));
+    }
+}
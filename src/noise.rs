@@ -0,0 +1,136 @@
+//! Laplace-noised aggregate counts
+//!
+//! Scan telemetry shared across teams must not reveal exact per-document
+//! sensitive counts. Aggregates are built natively with each document's
+//! contribution to an entity count clipped to `max_per_document`, then
+//! every count gets independent Laplace noise of scale
+//! `max_per_document / epsilon` (the Laplace mechanism, per count) before
+//! it is returned. Reported entity types come from the matcher's patterns,
+//! not the data, so zero counts are noised and reported too.
+//!
+//! Noise is drawn from a SHA-256 counter stream seeded from the OS (or
+//! from a caller seed, for reproducible tests only).
+
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use crate::detmath;
+
+/// Uniform random stream: SHA-256(seed || counter) blocks
+pub struct NoiseSource {
+    seed: [u8; 32],
+    counter: u64,
+}
+
+impl NoiseSource {
+    /// Stream seeded from OS entropy
+    pub fn from_entropy() -> Self {
+        let mut seed = [0u8; 32];
+        let read = std::fs::File::open("/dev/urandom").and_then(|mut f| f.read_exact(&mut seed));
+        if read.is_err() {
+            // No urandom (e.g. Windows): hash the process's randomized hasher keys and the clock
+            use std::hash::{BuildHasher, Hasher};
+            let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+            hasher.write_u128(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_nanos()),
+            );
+            seed = Sha256::digest(hasher.finish().to_le_bytes()).into();
+        }
+        Self { seed, counter: 0 }
+    }
+
+    /// Deterministic stream (same seed, same noise)
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed: Sha256::digest(seed.to_le_bytes()).into(),
+            counter: 0,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.counter += 1;
+        let mut block = self.seed.to_vec();
+        block.extend_from_slice(&self.counter.to_le_bytes());
+        let digest = Sha256::digest(&block);
+        u64::from_le_bytes([digest[0], digest[1], digest[2], digest[3], digest[4], digest[5], digest[6], digest[7]])
+    }
+
+    /// Uniform in the open interval (0, 1)
+    fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Laplace(0, scale) sample by inverse CDF
+    pub fn laplace(&mut self, scale: f64) -> f64 {
+        let u = self.uniform() - 0.5;
+        -scale * u.signum() * detmath::ln(1.0 - 2.0 * u.abs())
+    }
+}
+
+/// Add per-document clipped counts of `entity_types` to `totals`
+pub fn add_document<'a>(
+    totals: &mut BTreeMap<String, u64>,
+    entity_types: impl IntoIterator<Item = &'a str>,
+    max_per_document: Option<u64>,
+) {
+    let mut doc: BTreeMap<&str, u64> = BTreeMap::new();
+    for entity_type in entity_types {
+        *doc.entry(entity_type).or_default() += 1;
+    }
+    for (entity_type, n) in doc {
+        let n = max_per_document.map_or(n, |cap| n.min(cap));
+        *totals.entry(entity_type.to_string()).or_default() += n;
+    }
+}
+
+/// Noised counts, rounded and clamped at zero
+///
+/// Rounding and clamping are post-processing and keep the guarantee of
+/// the noised values.
+pub fn noisy_counts(
+    counts: &BTreeMap<String, u64>,
+    epsilon: f64,
+    sensitivity: u64,
+    source: &mut NoiseSource,
+) -> BTreeMap<String, u64> {
+    let scale = sensitivity as f64 / epsilon;
+    counts
+        .iter()
+        .map(|(entity_type, &n)| {
+            let noised = (n as f64 + source.laplace(scale)).round().max(0.0);
+            (entity_type.clone(), noised as u64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_document_clips() {
+        let mut totals = BTreeMap::from([("EMAIL".to_string(), 0)]);
+        add_document(&mut totals, ["SSN", "SSN", "SSN", "EMAIL"], Some(2));
+        add_document(&mut totals, ["SSN"], Some(2));
+        assert_eq!(totals, BTreeMap::from([("EMAIL".to_string(), 1), ("SSN".to_string(), 3)]));
+    }
+
+    #[test]
+    fn test_laplace_noise() {
+        let mut source = NoiseSource::from_seed(7);
+        let samples: Vec<f64> = (0..20_000).map(|_| source.laplace(2.0)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let mean_abs = samples.iter().map(|x| x.abs()).sum::<f64>() / samples.len() as f64;
+        // Laplace(0, b): mean 0, E|x| = b
+        assert!(mean.abs() < 0.1, "mean {}", mean);
+        assert!((mean_abs - 2.0).abs() < 0.1, "mean |x| {}", mean_abs);
+
+        let counts = BTreeMap::from([("SSN".to_string(), 100), ("EMAIL".to_string(), 0)]);
+        let a = noisy_counts(&counts, 1.0, 1, &mut NoiseSource::from_seed(1));
+        assert_eq!(a, noisy_counts(&counts, 1.0, 1, &mut NoiseSource::from_seed(1)));
+        assert!(a["SSN"].abs_diff(100) < 30);
+    }
+}
@@ -0,0 +1,442 @@
+//! Configurable text normalization ahead of matching
+//!
+//! Input from different sources hides the same value behind different
+//! encodings: fullwidth digits and odd dashes in PDFs, HTML entities in
+//! scraped pages, %XX escapes in URLs and logs, letter/digit confusions in
+//! OCR output. A `Pipeline` runs a caller-chosen list of stages in order;
+//! patterns then see the normalized text while match offsets are mapped
+//! back to the original through each stage's offset map.
+//!
+//! Every stage rewrites whole characters, so any char boundary of the
+//! normalized text maps to a char boundary of the input.
+
+/// One normalization pass
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Fullwidth ASCII, Unicode spaces, dashes and quotes to ASCII; drop
+    /// zero-width characters and soft hyphens; expand f-ligatures
+    UnicodeFold,
+    /// Runs of whitespace to one space (one newline if the run has one)
+    CollapseWhitespace,
+    /// HTML character references (&amp;, &#64;, &#x40;, ...)
+    DecodeEntities,
+    /// %XX escapes that decode to valid UTF-8
+    DecodePercent,
+    /// OCR letter/digit confusions (O→0, l→1, S→5, ...) in numeric tokens
+    OcrFold,
+}
+
+/// Stage names accepted by `Stage::parse`, in declaration order
+pub const STAGE_NAMES: &[&str] =
+    &["unicode_fold", "collapse_whitespace", "decode_entities", "decode_percent", "ocr_fold"];
+
+impl Stage {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "unicode_fold" => Some(Stage::UnicodeFold),
+            "collapse_whitespace" => Some(Stage::CollapseWhitespace),
+            "decode_entities" => Some(Stage::DecodeEntities),
+            "decode_percent" => Some(Stage::DecodePercent),
+            "ocr_fold" => Some(Stage::OcrFold),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Stage::UnicodeFold => "unicode_fold",
+            Stage::CollapseWhitespace => "collapse_whitespace",
+            Stage::DecodeEntities => "decode_entities",
+            Stage::DecodePercent => "decode_percent",
+            Stage::OcrFold => "ocr_fold",
+        }
+    }
+
+    /// Normalized text and its offset map, or None if nothing changed
+    fn apply(self, text: &str) -> Option<(String, OffsetMap)> {
+        let mut out = Rewriter::new(text);
+        match self {
+            Stage::UnicodeFold => unicode_fold(text, &mut out),
+            Stage::CollapseWhitespace => collapse_whitespace(text, &mut out),
+            Stage::DecodeEntities => decode_entities(text, &mut out),
+            Stage::DecodePercent => decode_percent(text, &mut out),
+            Stage::OcrFold => ocr_fold(text, &mut out),
+        }
+        out.finish()
+    }
+}
+
+/// A replaced range: `src` bytes of the stage input became `out` bytes of its output
+#[derive(Clone, Copy, Debug)]
+struct Piece {
+    out_start: usize,
+    out_end: usize,
+    src_start: usize,
+    src_end: usize,
+}
+
+/// Offset map from one stage's output back to its input
+///
+/// Text between pieces is unchanged, so offsets there shift by the
+/// length difference of the pieces before them.
+struct OffsetMap {
+    pieces: Vec<Piece>,
+}
+
+impl OffsetMap {
+    /// Input offset for a span start inside a replacement (rounds down)
+    fn map_start(&self, offset: usize) -> usize {
+        let idx = self.pieces.partition_point(|p| p.out_start <= offset);
+        match idx.checked_sub(1).map(|i| self.pieces[i]) {
+            None => offset,
+            Some(p) if offset < p.out_end => p.src_start,
+            Some(p) => p.src_end + (offset - p.out_end),
+        }
+    }
+
+    /// Input offset for a span end inside a replacement (rounds up)
+    fn map_end(&self, offset: usize) -> usize {
+        let idx = self.pieces.partition_point(|p| p.out_start < offset);
+        match idx.checked_sub(1).map(|i| self.pieces[i]) {
+            None => offset,
+            Some(p) if offset < p.out_end => p.src_end,
+            Some(p) => p.src_end + (offset - p.out_end),
+        }
+    }
+}
+
+/// Builds a stage's output: unchanged text is copied lazily between replacements
+struct Rewriter<'a> {
+    src: &'a str,
+    copied: usize,
+    text: String,
+    pieces: Vec<Piece>,
+}
+
+impl<'a> Rewriter<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, copied: 0, text: String::new(), pieces: Vec::new() }
+    }
+
+    /// Replace src[start..end] (at or after the last replacement) with `with`
+    fn replace(&mut self, start: usize, end: usize, with: &str) {
+        if self.text.capacity() == 0 {
+            self.text.reserve(self.src.len());
+        }
+        self.text.push_str(&self.src[self.copied..start]);
+        let out_start = self.text.len();
+        self.text.push_str(with);
+        self.pieces.push(Piece { out_start, out_end: self.text.len(), src_start: start, src_end: end });
+        self.copied = end;
+    }
+
+    fn replace_char(&mut self, start: usize, end: usize, with: char) {
+        let mut buf = [0u8; 4];
+        self.replace(start, end, with.encode_utf8(&mut buf));
+    }
+
+    fn finish(mut self) -> Option<(String, OffsetMap)> {
+        if self.pieces.is_empty() {
+            return None;
+        }
+        self.text.push_str(&self.src[self.copied..]);
+        Some((self.text, OffsetMap { pieces: self.pieces }))
+    }
+}
+
+/// ASCII (or empty) replacement for a character unicode_fold rewrites
+fn fold_char(c: char) -> Option<&'static str> {
+    const FULLWIDTH: &str = "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => {
+            let i = c as usize - 0xFF01;
+            Some(&FULLWIDTH[i..i + 1])
+        }
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => Some(" "),
+        '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE58}' | '\u{FE63}' => Some("-"),
+        '\u{2018}'..='\u{201B}' | '\u{2032}' => Some("'"),
+        '\u{201C}'..='\u{201F}' | '\u{2033}' => Some("\""),
+        '\u{00AD}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' => Some(""),
+        '\u{FB00}' => Some("ff"),
+        '\u{FB01}' => Some("fi"),
+        '\u{FB02}' => Some("fl"),
+        '\u{FB03}' => Some("ffi"),
+        '\u{FB04}' => Some("ffl"),
+        _ => None,
+    }
+}
+
+fn unicode_fold(text: &str, out: &mut Rewriter) {
+    for (i, c) in text.char_indices().filter(|(_, c)| !c.is_ascii()) {
+        if let Some(with) = fold_char(c) {
+            out.replace(i, i + c.len_utf8(), with);
+        }
+    }
+}
+
+fn collapse_whitespace(text: &str, out: &mut Rewriter) {
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !c.is_whitespace() {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        let mut newline = c == '\n';
+        let mut count = 1;
+        while let Some(&(i, next)) = chars.peek().filter(|(_, next)| next.is_whitespace()) {
+            end = i + next.len_utf8();
+            newline |= next == '\n';
+            count += 1;
+            chars.next();
+        }
+        // A lone space or newline is already normal
+        if count > 1 || !(c == ' ' || c == '\n') {
+            out.replace(start, end, if newline { "\n" } else { " " });
+        }
+    }
+}
+
+/// Character for the reference body between '&' and ';'
+fn entity_char(name: &str) -> Option<char> {
+    if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code).filter(|&c| c != '\0');
+    }
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{00A0}'),
+        "ndash" => Some('\u{2013}'),
+        "mdash" => Some('\u{2014}'),
+        "commat" => Some('@'),
+        "period" => Some('.'),
+        "lpar" => Some('('),
+        "rpar" => Some(')'),
+        "plus" => Some('+'),
+        "hyphen" | "dash" => Some('-'),
+        _ => None,
+    }
+}
+
+/// Longest reference body considered ("#x10FFFF" plus room for names)
+const MAX_ENTITY_LEN: usize = 10;
+
+fn decode_entities(text: &str, out: &mut Rewriter) {
+    for (start, _) in text.match_indices('&') {
+        let rest = &text[start + 1..];
+        let Some(semi) = rest.bytes().take(MAX_ENTITY_LEN + 1).position(|b| b == b';') else {
+            continue;
+        };
+        if let Some(c) = entity_char(&rest[..semi]) {
+            out.replace_char(start, start + semi + 2, c);
+        }
+    }
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+fn decode_percent(text: &str, out: &mut Rewriter) {
+    let bytes = text.as_bytes();
+    let escape_at = |i: usize| -> Option<u8> {
+        (bytes.get(i) == Some(&b'%')).then_some(())?;
+        Some(hex_value(*bytes.get(i + 1)?)? << 4 | hex_value(*bytes.get(i + 2)?)?)
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        if escape_at(i).is_none() {
+            i += 1;
+            continue;
+        }
+        // Decode the whole run of escapes so multi-byte sequences stay together
+        let run_start = i;
+        let mut decoded = Vec::new();
+        while let Some(b) = escape_at(i) {
+            decoded.push(b);
+            i += 3;
+        }
+        let mut at = run_start;
+        for chunk in decoded.utf8_chunks() {
+            for c in chunk.valid().chars() {
+                let len = 3 * c.len_utf8();
+                out.replace_char(at, at + len, c);
+                at += len;
+            }
+            at += 3 * chunk.invalid().len();
+        }
+    }
+}
+
+/// Digit an OCR engine may have misread as this character
+fn ocr_digit(c: char) -> Option<char> {
+    match c {
+        'O' | 'o' | 'D' | 'Q' => Some('0'),
+        'I' | 'l' | '|' => Some('1'),
+        'Z' => Some('2'),
+        'S' => Some('5'),
+        'G' => Some('6'),
+        'B' => Some('8'),
+        _ => None,
+    }
+}
+
+/// Fold confusions in tokens made only of digits and confusable letters
+///
+/// A token ([A-Za-z0-9|] run) is folded only if it has at least one real
+/// digit and no letter outside the confusion table, so words ("SOLID")
+/// and mixed identifiers ("H2O") are left alone.
+fn ocr_fold(text: &str, out: &mut Rewriter) {
+    let is_token = |c: char| c.is_ascii_alphanumeric() || c == '|';
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !is_token(bytes[i] as char) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && is_token(bytes[i] as char) {
+            i += 1;
+        }
+        let token = &text[start..i];
+        let foldable = token.bytes().any(|b| b.is_ascii_digit())
+            && token.chars().all(|c| c.is_ascii_digit() || ocr_digit(c).is_some());
+        if !foldable {
+            continue;
+        }
+        for (j, c) in token.char_indices() {
+            if let Some(d) = ocr_digit(c) {
+                out.replace_char(start + j, start + j + 1, d);
+            }
+        }
+    }
+}
+
+/// An ordered list of stages
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pipeline {
+    stages: Vec<Stage>,
+}
+
+impl Pipeline {
+    /// Build from stage names, in the order they should run
+    pub fn from_names(names: &[String]) -> Result<Self, String> {
+        let stages = names
+            .iter()
+            .map(|name| {
+                Stage::parse(name).ok_or_else(|| {
+                    format!("unknown normalization stage '{}': expected one of {}", name, STAGE_NAMES.join(", "))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { stages })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.stages.iter().map(|s| s.as_str().to_string()).collect()
+    }
+
+    /// Run every stage over `text`
+    pub fn apply(&self, text: &str) -> Normalized {
+        let mut current = text.to_string();
+        let mut maps = Vec::with_capacity(self.stages.len());
+        for stage in &self.stages {
+            if let Some((text, map)) = stage.apply(&current) {
+                current = text;
+                maps.push(map);
+            }
+        }
+        Normalized { text: current, maps }
+    }
+}
+
+/// Normalized text with the offset maps of the stages that changed it
+pub struct Normalized {
+    pub text: String,
+    maps: Vec<OffsetMap>,
+}
+
+impl Normalized {
+    /// Map a char-aligned span of the normalized text to the original text
+    ///
+    /// A span edge inside a replaced range widens to cover all of it.
+    pub fn to_original(&self, start: usize, end: usize) -> (usize, usize) {
+        self.maps.iter().rev().fold((start, end), |(s, e), map| (map.map_start(s), map.map_end(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline(names: &[&str]) -> Pipeline {
+        Pipeline::from_names(&names.iter().map(|s| s.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    /// Original text under the normalized span of `needle`
+    fn original_of<'a>(text: &'a str, normalized: &Normalized, needle: &str) -> &'a str {
+        let start = normalized.text.find(needle).unwrap();
+        let (s, e) = normalized.to_original(start, start + needle.len());
+        &text[s..e]
+    }
+
+    #[test]
+    fn test_unicode_fold() {
+        let text = "SSN ４５６\u{2013}７８\u{2010}9012, of\u{FB01}ce\u{200B}r j\u{00A0}doe";
+        let normalized = pipeline(&["unicode_fold"]).apply(text);
+        assert_eq!(normalized.text, "SSN 456-78-9012, officer j doe");
+        assert_eq!(original_of(text, &normalized, "456-78-9012"), "４５６\u{2013}７８\u{2010}9012");
+        // The dropped zero-width space stays outside spans that end or start at it
+        assert_eq!(original_of(text, &normalized, "office"), "of\u{FB01}ce");
+        assert_eq!(original_of(text, &normalized, "r j "), "r j\u{00A0}");
+        // A span starting inside a ligature widens to the whole ligature
+        assert_eq!(original_of(text, &normalized, "ice"), "\u{FB01}ce");
+    }
+
+    #[test]
+    fn test_stage_order_matters() {
+        let text = "mail%3A%20a%26amp%3Bb%40x.io &#x31;&#50;3";
+        let entities_first = pipeline(&["decode_entities", "decode_percent"]).apply(text);
+        assert_eq!(entities_first.text, "mail: a&amp;b@x.io 123");
+        let percent_first = pipeline(&["decode_percent", "decode_entities"]).apply(text);
+        assert_eq!(percent_first.text, "mail: a&b@x.io 123");
+        assert_eq!(original_of(text, &percent_first, "a&b@x.io"), "a%26amp%3Bb%40x.io");
+        assert_eq!(original_of(text, &percent_first, "123"), "&#x31;&#50;3");
+    }
+
+    #[test]
+    fn test_percent_decode_keeps_invalid_utf8() {
+        let text = "caf%C3%A9 %FF%41 100%";
+        let normalized = pipeline(&["decode_percent"]).apply(text);
+        assert_eq!(normalized.text, "café %FFA 100%");
+        assert_eq!(original_of(text, &normalized, "é"), "%C3%A9");
+    }
+
+    #[test]
+    fn test_collapse_whitespace_and_ocr_fold() {
+        let text = "Card 4lll  1111\t\t1lI1 SOLID H2O\r\n\r\nSSN I23-4S-67B9";
+        let normalized = pipeline(&["collapse_whitespace", "ocr_fold"]).apply(text);
+        assert_eq!(normalized.text, "Card 4111 1111 1111 SOLID H2O\nSSN 123-45-6789");
+        assert_eq!(original_of(text, &normalized, "4111 1111 1111"), "4lll  1111\t\t1lI1");
+        assert_eq!(original_of(text, &normalized, "\nSSN"), "\r\n\r\nSSN");
+    }
+
+    #[test]
+    fn test_unknown_stage() {
+        let err = Pipeline::from_names(&["unicode_fold".to_string(), "stem".to_string()]).unwrap_err();
+        assert!(err.contains("'stem'") && err.contains("ocr_fold"));
+        let empty = pipeline(&[]).apply("unchanged");
+        assert_eq!((empty.text.as_str(), empty.to_original(2, 4)), ("unchanged", (2, 4)));
+    }
+}
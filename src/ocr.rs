@@ -0,0 +1,119 @@
+//! OCR confidence weighting
+//!
+//! Text recognized from scans comes with a confidence per character or per
+//! word. A finding read from a low-confidence region is often a misread
+//! (an 8 for a B, a dropped digit), so detect() scales its score by the
+//! lowest OCR confidence under its span. Both input forms are reduced to
+//! sorted, non-overlapping byte spans so findings can be looked up by
+//! binary search.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::overlap;
+
+/// OCR confidence over byte spans of one text
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OcrConfidence {
+    /// (start, end, confidence), sorted by start and non-overlapping
+    spans: Vec<(usize, usize, f32)>,
+}
+
+fn check_confidence(confidence: f32) -> Result<f32, String> {
+    if (0.0..=1.0).contains(&confidence) {
+        Ok(confidence)
+    } else {
+        Err(format!("OCR confidence {} is outside 0.0-1.0", confidence))
+    }
+}
+
+impl OcrConfidence {
+    /// One confidence per character of `text`
+    pub fn from_chars(text: &str, confidences: &[f32]) -> Result<Self, String> {
+        let chars = text.chars().count();
+        if confidences.len() != chars {
+            return Err(format!(
+                "got {} OCR confidences for a text of {} characters",
+                confidences.len(),
+                chars
+            ));
+        }
+        let spans = text
+            .char_indices()
+            .zip(confidences)
+            .map(|((start, c), &conf)| Ok((start, start + c.len_utf8(), check_confidence(conf)?)))
+            .collect::<Result<_, String>>()?;
+        Ok(Self { spans })
+    }
+
+    /// Confidences for (start, end) byte spans, e.g. OCR words
+    ///
+    /// Bytes outside every span have no OCR information and are not weighted.
+    pub fn from_spans(text: &str, mut spans: Vec<(usize, usize, f32)>) -> Result<Self, String> {
+        for &(start, end, conf) in &spans {
+            if !overlap::in_bounds(text, start, end) {
+                return Err(format!("invalid OCR span {}:{} for text of {} bytes", start, end, text.len()));
+            }
+            check_confidence(conf)?;
+        }
+        spans.sort_by_key(|&(start, end, _)| (start, end));
+        if let Some(w) = spans.windows(2).find(|w| w[1].0 < w[0].1) {
+            return Err(format!("OCR spans {}:{} and {}:{} overlap", w[0].0, w[0].1, w[1].0, w[1].1));
+        }
+        Ok(Self { spans })
+    }
+
+    /// Lowest confidence among spans overlapping `start..end`
+    pub fn min_confidence(&self, start: usize, end: usize) -> Option<f32> {
+        let first = self.spans.partition_point(|&(_, e, _)| e <= start);
+        self.spans[first..]
+            .iter()
+            .take_while(|&&(s, _, _)| s < end)
+            .map(|&(_, _, conf)| conf)
+            .reduce(f32::min)
+    }
+}
+
+/// Read the `ocr_confidence` argument of detect()
+///
+/// Accepts a list with one float per character of `text`, or a list of
+/// (start, end, confidence) byte spans.
+pub fn from_py(text: &str, value: &Bound<'_, PyAny>) -> PyResult<OcrConfidence> {
+    let parsed = if let Ok(spans) = value.extract::<Vec<(usize, usize, f32)>>() {
+        OcrConfidence::from_spans(text, spans)
+    } else if let Ok(per_char) = value.extract::<Vec<f32>>() {
+        OcrConfidence::from_chars(text, &per_char)
+    } else {
+        Err("ocr_confidence must be a list of floats (one per character) or of (start, end, confidence) spans"
+            .to_string())
+    };
+    parsed.map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_character_confidence() {
+        let text = "né 123";
+        let ocr = OcrConfidence::from_chars(text, &[0.9, 0.8, 1.0, 0.95, 0.4, 0.99]).unwrap();
+        // "é" is two bytes: "123" starts at byte 4
+        assert_eq!(ocr.min_confidence(4, 7), Some(0.4));
+        assert_eq!(ocr.min_confidence(0, 3), Some(0.8));
+        assert!(OcrConfidence::from_chars(text, &[0.9]).is_err());
+        assert!(OcrConfidence::from_chars("ab", &[0.9, 95.0]).is_err());
+    }
+
+    #[test]
+    fn test_span_confidence() {
+        let text = "SSN 123-45-6789 ok";
+        let ocr = OcrConfidence::from_spans(text, vec![(16, 18, 0.2), (0, 3, 0.99), (4, 15, 0.7)]).unwrap();
+        assert_eq!(ocr.min_confidence(4, 15), Some(0.7));
+        assert_eq!(ocr.min_confidence(0, 15), Some(0.7));
+        assert_eq!(ocr.min_confidence(3, 4), None);
+
+        assert!(OcrConfidence::from_spans(text, vec![(0, 5, 0.9), (4, 8, 0.9)]).is_err());
+        assert!(OcrConfidence::from_spans(text, vec![(0, 99, 0.9)]).is_err());
+    }
+}
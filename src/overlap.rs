@@ -0,0 +1,210 @@
+//! Span selection for renderers
+//!
+//! Output that inserts markup around matches (annotated previews, HTML)
+//! cannot represent partially overlapping spans, and a span that is out
+//! of range or splits a UTF-8 sequence would corrupt the output. Renderers
+//! check spans here and then pick a non-overlapping subset with the same
+//! rule everywhere: earliest start first, longest first on ties.
+//!
+//! `merge` applies the same selection to results from several matchers,
+//! optionally preferring higher scores instead of earlier starts.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+/// A labeled byte span of a text
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub label: String,
+}
+
+/// Whether `start..end` is a valid, char-aligned span of `text`
+pub fn in_bounds(text: &str, start: usize, end: usize) -> bool {
+    start <= end && end <= text.len() && text.is_char_boundary(start) && text.is_char_boundary(end)
+}
+
+/// Read spans from match objects (RawMatch, Finding, FieldMatch, ...)
+///
+/// Each object needs `start`, `end` and `entity_type` attributes.
+/// Raises ValueError if a span does not fit `text`.
+pub fn spans_from_py(text: &str, items: &[Bound<'_, PyAny>]) -> PyResult<Vec<Span>> {
+    items
+        .iter()
+        .map(|item| {
+            let start: usize = item.getattr("start")?.extract()?;
+            let end: usize = item.getattr("end")?.extract()?;
+            if !in_bounds(text, start, end) {
+                return Err(PyValueError::new_err(format!(
+                    "invalid span {}:{} for text of {} bytes",
+                    start,
+                    end,
+                    text.len()
+                )));
+            }
+            Ok(Span { start, end, label: item.getattr("entity_type")?.extract()? })
+        })
+        .collect()
+}
+
+/// Non-overlapping subset of `spans` in text order
+///
+/// Among overlapping spans the earliest-starting wins, then the longest;
+/// empty spans are dropped.
+pub fn select_non_overlapping(mut spans: Vec<Span>) -> Vec<Span> {
+    spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    let mut out: Vec<Span> = Vec::with_capacity(spans.len());
+    for span in spans {
+        if span.start == span.end || out.last().is_some_and(|last| span.start < last.end) {
+            continue;
+        }
+        out.push(span);
+    }
+    out
+}
+
+/// How `merge` resolves overlapping spans
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Keep every span; only exact duplicates are dropped
+    KeepAll,
+    /// Earliest start, then longest (the `select_non_overlapping` rule)
+    Earliest,
+    /// Highest score, then the earliest/longest rule
+    HighestScore,
+}
+
+impl OverlapPolicy {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "keep_all" => Some(OverlapPolicy::KeepAll),
+            "earliest" => Some(OverlapPolicy::Earliest),
+            "highest_score" => Some(OverlapPolicy::HighestScore),
+            _ => None,
+        }
+    }
+}
+
+/// A span to merge, with its score
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoredSpan {
+    pub span: Span,
+    pub score: f32,
+}
+
+/// Read a match object for `merge`
+///
+/// Uses `score` when present (Finding), else `confidence` (RawMatch),
+/// else 0.0. There is no text to check against, so only start <= end is
+/// enforced.
+pub fn scored_span_from_py(item: &Bound<'_, PyAny>) -> PyResult<ScoredSpan> {
+    let start: usize = item.getattr("start")?.extract()?;
+    let end: usize = item.getattr("end")?.extract()?;
+    if end < start {
+        return Err(PyValueError::new_err(format!("invalid span {}:{}", start, end)));
+    }
+    let score = if item.hasattr("score")? {
+        item.getattr("score")?.extract()?
+    } else if item.hasattr("confidence")? {
+        item.getattr("confidence")?.extract()?
+    } else {
+        0.0
+    };
+    Ok(ScoredSpan { span: Span { start, end, label: item.getattr("entity_type")?.extract()? }, score })
+}
+
+fn earliest_longest(a: &Span, b: &Span) -> Ordering {
+    a.start.cmp(&b.start).then(b.end.cmp(&a.end))
+}
+
+/// Indices of the spans kept from `items`, in text order
+///
+/// `items` is the concatenation of every matcher's results in precedence
+/// order. Exact duplicates (same span and entity type) keep the first
+/// occurrence. Except with KeepAll, empty spans are dropped and the rest
+/// are picked greedily in policy order, skipping any that overlap a span
+/// already kept; remaining ties go to the earlier item.
+pub fn merge(items: &[ScoredSpan], policy: OverlapPolicy) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    let mut order: Vec<usize> = (0..items.len())
+        .filter(|&i| {
+            let span = &items[i].span;
+            seen.insert((span.start, span.end, span.label.as_str()))
+                && (policy == OverlapPolicy::KeepAll || span.start < span.end)
+        })
+        .collect();
+    let by_position = |&a: &usize, &b: &usize| earliest_longest(&items[a].span, &items[b].span);
+    if policy == OverlapPolicy::KeepAll {
+        order.sort_by(by_position);
+        return order;
+    }
+    if policy == OverlapPolicy::HighestScore {
+        order.sort_by(|a, b| items[*b].score.total_cmp(&items[*a].score).then(by_position(a, b)));
+    } else {
+        order.sort_by(by_position);
+    }
+
+    // Kept spans by start; they never overlap, so one neighbour on each side decides
+    let mut kept: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+    for i in order {
+        let Span { start, end, .. } = items[i].span;
+        let before = kept.range(..start).next_back().is_some_and(|(_, &(e, _))| e > start);
+        let after = kept.range(start..end).next().is_some();
+        if !before && !after {
+            kept.insert(start, (end, i));
+        }
+    }
+    kept.into_values().map(|(_, i)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize, label: &str) -> Span {
+        Span { start, end, label: label.to_string() }
+    }
+
+    #[test]
+    fn test_select_non_overlapping() {
+        let spans = vec![span(10, 20, "B"), span(0, 5, "A"), span(10, 25, "C"), span(22, 30, "D"), span(30, 30, "E")];
+        let kept: Vec<String> = select_non_overlapping(spans).into_iter().map(|s| s.label).collect();
+        assert_eq!(kept, ["A", "C"]);
+    }
+
+    fn scored(start: usize, end: usize, label: &str, score: f32) -> ScoredSpan {
+        ScoredSpan { span: span(start, end, label), score }
+    }
+
+    #[test]
+    fn test_merge_matches_native_selection() {
+        let items = vec![scored(10, 20, "B", 0.9), scored(0, 5, "A", 0.5), scored(10, 25, "C", 0.1), scored(22, 30, "D", 1.0)];
+        let labels = |kept: Vec<usize>| -> Vec<&str> { kept.into_iter().map(|i| items[i].span.label.as_str()).collect() };
+
+        let native: Vec<Span> = select_non_overlapping(items.iter().map(|s| s.span.clone()).collect());
+        let earliest = merge(&items, OverlapPolicy::Earliest);
+        assert_eq!(earliest.iter().map(|&i| items[i].span.clone()).collect::<Vec<_>>(), native);
+        assert_eq!(labels(merge(&items, OverlapPolicy::HighestScore)), ["A", "B", "D"]);
+        assert_eq!(labels(merge(&items, OverlapPolicy::KeepAll)), ["A", "C", "B", "D"]);
+    }
+
+    #[test]
+    fn test_merge_drops_duplicates_across_matchers() {
+        let items = vec![scored(0, 5, "SSN", 0.8), scored(0, 5, "SSN", 0.9), scored(0, 5, "PHONE", 0.9)];
+        assert_eq!(merge(&items, OverlapPolicy::KeepAll), [0, 2]);
+        assert_eq!(merge(&items, OverlapPolicy::Earliest), [0]);
+        assert_eq!(merge(&items, OverlapPolicy::HighestScore), [2]);
+    }
+
+    #[test]
+    fn test_in_bounds() {
+        let text = "né 123";
+        assert!(in_bounds(text, 0, 3));
+        assert!(!in_bounds(text, 0, 2));
+        assert!(!in_bounds(text, 4, 3));
+        assert!(!in_bounds(text, 4, 20));
+    }
+}
@@ -0,0 +1,234 @@
+//! Label/value pair extraction ("SSN: 123-45-6789")
+//!
+//! A value match preceded by a keyword naming its entity type, with only a
+//! separator in between, is much stronger evidence than either alone.
+//! Labels are found with one case-insensitive Aho-Corasick pass and paired
+//! with the nearest following value of a matching entity type.
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::matcher::RawMatch;
+
+/// Confidence added to a value that is introduced by its label
+pub const LABEL_BOOST: f32 = 0.1;
+
+/// Words allowed between a label and its value ("SSN is", "Account No.")
+const CONNECTORS: &[&str] = &["is", "was", "no", "num", "number", "nr", "of", "id", "#"];
+
+/// A value together with the label that introduced it
+#[pyclass]
+#[derive(Clone)]
+pub struct LabeledPair {
+    #[pyo3(get)]
+    pub entity_type: String,
+    /// Label text as it appears in the input
+    #[pyo3(get)]
+    pub label: String,
+    #[pyo3(get)]
+    pub label_start: usize,
+    #[pyo3(get)]
+    pub label_end: usize,
+    #[pyo3(get)]
+    pub value: String,
+    #[pyo3(get)]
+    pub value_start: usize,
+    #[pyo3(get)]
+    pub value_end: usize,
+    /// Value confidence plus LABEL_BOOST, capped at 1.0
+    #[pyo3(get)]
+    pub confidence: f32,
+    #[pyo3(get)]
+    pub pattern_id: usize,
+}
+
+#[pymethods]
+impl LabeledPair {
+    /// Start of the whole pair (the label)
+    #[getter]
+    fn start(&self) -> usize {
+        self.label_start
+    }
+
+    /// End of the whole pair (the value)
+    #[getter]
+    fn end(&self) -> usize {
+        self.value_end
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LabeledPair(type={}, label='{}', value='{}', pos={}:{})",
+            self.entity_type, self.label, self.value, self.label_start, self.value_end
+        )
+    }
+}
+
+/// Compiled label keywords per entity type
+#[pyclass]
+pub struct PairExtractor {
+    automaton: AhoCorasick,
+    /// Entity types each automaton pattern labels
+    entity_types: Vec<Vec<String>>,
+    max_gap: usize,
+}
+
+impl PairExtractor {
+    /// Build from {entity_type: [label, ...]}; labels match case-insensitively
+    pub fn build(labels: HashMap<String, Vec<String>>, max_gap: usize) -> Result<Self, String> {
+        let mut by_label: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (entity_type, words) in labels {
+            for word in words {
+                let word = word.trim().to_ascii_lowercase();
+                if !word.is_empty() {
+                    by_label.entry(word).or_default().push(entity_type.clone());
+                }
+            }
+        }
+        if by_label.is_empty() {
+            return Err("no labels given".to_string());
+        }
+        let automaton = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(true)
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(by_label.keys())
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            automaton,
+            entity_types: by_label.into_values().collect(),
+            max_gap,
+        })
+    }
+
+    /// Label hits that sit on word boundaries: (start, end, pattern index)
+    fn labels(&self, text: &str) -> Vec<(usize, usize, usize)> {
+        let bytes = text.as_bytes();
+        let is_word = |i: usize| bytes.get(i).is_some_and(|b| b.is_ascii_alphanumeric());
+        self.automaton
+            .find_iter(text)
+            .filter(|m| !is_word(m.end()) && (m.start() == 0 || !is_word(m.start() - 1)))
+            .map(|m| (m.start(), m.end(), m.pattern().as_usize()))
+            .collect()
+    }
+
+    /// Pair each match with the label immediately before it, if any
+    pub fn pair(&self, text: &str, matches: &[RawMatch]) -> Vec<LabeledPair> {
+        let labels = self.labels(text);
+        let mut pairs = Vec::new();
+        for m in matches {
+            // Nearest label ending at or before the value
+            let idx = labels.partition_point(|&(_, end, _)| end <= m.start);
+            let Some(&(ls, le, pattern)) = idx.checked_sub(1).and_then(|i| labels.get(i)) else {
+                continue;
+            };
+            if !self.entity_types[pattern].contains(&m.entity_type) {
+                continue;
+            }
+            if !text.is_char_boundary(m.start) || !is_separator(&text[le..m.start], self.max_gap) {
+                continue;
+            }
+            pairs.push(LabeledPair {
+                entity_type: m.entity_type.clone(),
+                label: text[ls..le].to_string(),
+                label_start: ls,
+                label_end: le,
+                value: m.text.clone(),
+                value_start: m.start,
+                value_end: m.end,
+                confidence: (m.confidence + LABEL_BOOST).min(1.0),
+                pattern_id: m.pattern_id,
+            });
+        }
+        pairs
+    }
+}
+
+/// Whether the gap between label and value is only a separator
+///
+/// Punctuation (: = - # . ( ) and dashes) and whitespace, plus connector
+/// words, within `max_gap` characters.
+fn is_separator(gap: &str, max_gap: usize) -> bool {
+    if gap.chars().count() > max_gap {
+        return false;
+    }
+    gap.split(|c: char| c.is_whitespace() || matches!(c, ':' | '=' | '-' | '.' | '(' | ')' | '–' | '—'))
+        .filter(|w| !w.is_empty())
+        .all(|w| CONNECTORS.iter().any(|c| c.eq_ignore_ascii_case(w)))
+}
+
+#[pymethods]
+impl PairExtractor {
+    /// Create an extractor
+    ///
+    /// Args:
+    ///     labels: {entity_type: [label keyword, ...]}, e.g.
+    ///         {"SSN": ["ssn", "social security number"]}
+    ///     max_gap: Maximum characters between label and value
+    #[new]
+    #[pyo3(signature = (labels, max_gap=12))]
+    fn new(labels: HashMap<String, Vec<String>>, max_gap: usize) -> PyResult<Self> {
+        Self::build(labels, max_gap).map_err(PyValueError::new_err)
+    }
+
+    /// Pair matches from any scan (PatternMatcher, DictionaryMatcher...)
+    /// with the labels preceding them in `text`
+    #[pyo3(name = "pair")]
+    fn py_pair(&self, py: Python<'_>, text: &str, matches: Vec<PyRef<RawMatch>>) -> Vec<LabeledPair> {
+        let matches: Vec<RawMatch> = matches.iter().map(|m| (**m).clone()).collect();
+        py.allow_threads(|| self.pair(text, &matches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor() -> PairExtractor {
+        let mut labels = HashMap::new();
+        labels.insert("SSN".to_string(), vec!["SSN".to_string(), "social security number".to_string()]);
+        labels.insert("ACCOUNT".to_string(), vec!["account".to_string()]);
+        PairExtractor::build(labels, 12).unwrap()
+    }
+
+    fn value(text: &str, entity_type: &str, needle: &str) -> RawMatch {
+        let start = text.find(needle).unwrap();
+        RawMatch {
+            pattern_id: 0,
+            start,
+            end: start + needle.len(),
+            text: needle.to_string(),
+            entity_type: entity_type.to_string(),
+            confidence: 0.8,
+            truncated: false,
+            context: None,
+        }
+    }
+
+    #[test]
+    fn test_pairs_label_and_value() {
+        let text = "Patient Social Security Number: 123-45-6789, Account No. 99812";
+        let ex = extractor();
+        let pairs = ex.pair(text, &[value(text, "SSN", "123-45-6789"), value(text, "ACCOUNT", "99812")]);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].label, "Social Security Number");
+        assert!((pairs[0].confidence - 0.9).abs() < 1e-6);
+        assert_eq!(pairs[1].label, "Account");
+        assert_eq!(&text[pairs[1].label_start..pairs[1].value_end], "Account No. 99812");
+    }
+
+    #[test]
+    fn test_rejects_wrong_type_and_long_gaps() {
+        let ex = extractor();
+        let text = "SSN: 4111111111111111";
+        assert!(ex.pair(text, &[value(text, "CREDIT_CARD", "4111111111111111")]).is_empty());
+
+        let text = "SSN was recorded elsewhere, 123-45-6789";
+        assert!(ex.pair(text, &[value(text, "SSN", "123-45-6789")]).is_empty());
+
+        // Label must be a whole word
+        let text = "LESSN: 123-45-6789";
+        assert!(ex.pair(text, &[value(text, "SSN", "123-45-6789")]).is_empty());
+    }
+}
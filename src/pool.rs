@@ -0,0 +1,357 @@
+//! Scan worker pool with an internal queue
+//!
+//! Python submits documents and collects results later (by job id or by
+//! polling), so all scheduling happens on native threads instead of a
+//! Python ThreadPoolExecutor contending for the GIL. Workers are a
+//! dedicated work-stealing rayon pool, separate from the global pool used
+//! by find_matches_batch.
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::matcher::{attach_context, find_matches_impl, CompiledPatterns, PatternMatcher, RawMatch};
+
+/// Jobs in flight and results not yet collected
+#[derive(Default)]
+struct JobState {
+    pending: HashSet<u64>,
+    done: BTreeMap<u64, Vec<RawMatch>>,
+    /// Panic messages of jobs that failed, until wait() raises them
+    failed: BTreeMap<u64, String>,
+}
+
+impl JobState {
+    fn finished(&self, id: u64) -> bool {
+        self.done.contains_key(&id) || self.failed.contains_key(&id)
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<JobState>,
+    finished: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, JobState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait until `ready` holds or `timeout` passes (None: wait forever)
+    fn wait_until<'a>(
+        &'a self,
+        mut state: MutexGuard<'a, JobState>,
+        timeout: Option<Duration>,
+        ready: impl Fn(&JobState) -> bool,
+    ) -> MutexGuard<'a, JobState> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        while !ready(&state) {
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    self.finished.wait_timeout(state, deadline - now).unwrap_or_else(|e| e.into_inner()).0
+                }
+                None => self.finished.wait(state).unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+        state
+    }
+}
+
+/// Message of a caught panic payload
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+fn timeout_arg(timeout: Option<f64>) -> PyResult<Option<Duration>> {
+    match timeout {
+        Some(t) if t.is_finite() && t >= 0.0 => Ok(Some(Duration::from_secs_f64(t))),
+        Some(t) => Err(PyValueError::new_err(format!("timeout must be a non-negative number, got {}", t))),
+        None => Ok(None),
+    }
+}
+
+/// Native scan worker pool
+#[pyclass]
+pub struct ScannerPool {
+    compiled: Arc<CompiledPatterns>,
+    pool: rayon::ThreadPool,
+    shared: Arc<Shared>,
+    next_id: Mutex<u64>,
+    /// Context objects of uncollected jobs, attached when results are taken
+    contexts: Mutex<HashMap<u64, PyObject>>,
+}
+
+impl ScannerPool {
+    /// Attach each job's context to its matches
+    fn take_results(&self, py: Python<'_>, results: Vec<(u64, Vec<RawMatch>)>) -> Vec<(u64, Vec<RawMatch>)> {
+        let mut contexts = self.contexts.lock().unwrap_or_else(|e| e.into_inner());
+        results
+            .into_iter()
+            .map(|(id, mut matches)| {
+                attach_context(&mut matches, contexts.remove(&id).map(|c| c.clone_ref(py)));
+                (id, matches)
+            })
+            .collect()
+    }
+
+    /// Run `job` on a worker and file its result under `id`
+    ///
+    /// A panicking job is caught and recorded as failed; left alone, rayon
+    /// would abort the whole process.
+    fn spawn_job(&self, id: u64, job: impl FnOnce() -> Vec<RawMatch> + Send + 'static) {
+        self.shared.lock().pending.insert(id);
+        let shared = Arc::clone(&self.shared);
+        self.pool.spawn(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+            let mut state = shared.lock();
+            state.pending.remove(&id);
+            match result {
+                Ok(matches) => {
+                    state.done.insert(id, matches);
+                }
+                Err(payload) => {
+                    state.failed.insert(id, panic_message(payload));
+                }
+            }
+            drop(state);
+            shared.finished.notify_all();
+        });
+    }
+}
+
+#[pymethods]
+impl ScannerPool {
+    /// Create a pool scanning with a matcher's patterns
+    ///
+    /// Args:
+    ///     matcher: PatternMatcher whose compiled patterns are shared
+    ///     workers: Number of worker threads (default: one per CPU)
+    ///
+    /// Raises:
+    ///     ValueError: If workers is 0
+    #[new]
+    #[pyo3(signature = (matcher, workers=None))]
+    fn new(matcher: PyRef<PatternMatcher>, workers: Option<usize>) -> PyResult<Self> {
+        if workers == Some(0) {
+            return Err(PyValueError::new_err("workers must be at least 1"));
+        }
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(workers.unwrap_or(0))
+            .thread_name(|i| format!("openlabels-scan-{}", i))
+            .build()
+            .map_err(|e| PyRuntimeError::new_err(format!("cannot start scan workers: {}", e)))?;
+        Ok(Self {
+            compiled: matcher.compiled(),
+            pool,
+            shared: Arc::default(),
+            next_id: Mutex::new(0),
+            contexts: Mutex::default(),
+        })
+    }
+
+    /// Number of worker threads
+    #[getter]
+    fn workers(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Jobs submitted but not finished
+    #[getter]
+    fn pending(&self) -> usize {
+        self.shared.lock().pending.len()
+    }
+
+    /// Finished jobs (including failed ones) whose results have not been collected
+    #[getter]
+    fn completed(&self) -> usize {
+        let state = self.shared.lock();
+        state.done.len() + state.failed.len()
+    }
+
+    /// Queue a document for scanning
+    ///
+    /// Args:
+    ///     text: The text to scan
+    ///     context: Optional object attached to every match of this job
+    ///
+    /// Returns:
+    ///     Job id for wait()
+    #[pyo3(signature = (text, context=None))]
+    fn submit(&self, text: String, context: Option<PyObject>) -> u64 {
+        let id = {
+            let mut next = self.next_id.lock().unwrap_or_else(|e| e.into_inner());
+            *next += 1;
+            *next
+        };
+        if let Some(ctx) = context {
+            self.contexts.lock().unwrap_or_else(|e| e.into_inner()).insert(id, ctx);
+        }
+        let compiled = Arc::clone(&self.compiled);
+        self.spawn_job(id, move || find_matches_impl(&compiled, &text));
+        id
+    }
+
+    /// Wait for one job and collect its matches
+    ///
+    /// Releases the GIL while waiting.
+    ///
+    /// Args:
+    ///     job_id: Id returned by submit()
+    ///     timeout: Seconds to wait (None: until done)
+    ///
+    /// Returns:
+    ///     List of RawMatch, or None if the timeout passed first
+    ///
+    /// Raises:
+    ///     ValueError: If the id is unknown or its results were already collected
+    ///     RuntimeError: If the job panicked while scanning
+    #[pyo3(signature = (job_id, timeout=None))]
+    fn wait(&self, py: Python<'_>, job_id: u64, timeout: Option<f64>) -> PyResult<Option<Vec<RawMatch>>> {
+        let timeout = timeout_arg(timeout)?;
+        let result = py.allow_threads(|| {
+            let state = self.shared.lock();
+            if !state.pending.contains(&job_id) && !state.finished(job_id) {
+                return Err(None);
+            }
+            let mut state = self.shared.wait_until(state, timeout, |s| s.finished(job_id));
+            match state.failed.remove(&job_id) {
+                Some(message) => Err(Some(message)),
+                None => Ok(state.done.remove(&job_id)),
+            }
+        });
+        match result {
+            Ok(Some(matches)) => Ok(self.take_results(py, vec![(job_id, matches)]).pop().map(|(_, m)| m)),
+            Ok(None) => Ok(None),
+            Err(Some(message)) => {
+                self.contexts.lock().unwrap_or_else(|e| e.into_inner()).remove(&job_id);
+                Err(PyRuntimeError::new_err(format!("job {} failed: {}", job_id, message)))
+            }
+            Err(None) => Err(PyValueError::new_err(format!("unknown or already collected job {}", job_id))),
+        }
+    }
+
+    /// Collect every finished job
+    ///
+    /// Failed jobs are not returned; they stay until wait() raises their error.
+    ///
+    /// Args:
+    ///     timeout: If nothing has finished, wait up to this many seconds
+    ///         for the first job (default: return immediately)
+    ///
+    /// Returns:
+    ///     List of (job_id, matches) in job id order
+    #[pyo3(signature = (timeout=None))]
+    fn poll(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<Vec<(u64, Vec<RawMatch>)>> {
+        let timeout = timeout_arg(timeout)?.unwrap_or_default();
+        let finished = py.allow_threads(|| {
+            let state = self.shared.lock();
+            let mut state =
+                self.shared.wait_until(state, Some(timeout), |s| !s.done.is_empty() || s.pending.is_empty());
+            std::mem::take(&mut state.done).into_iter().collect()
+        });
+        Ok(self.take_results(py, finished))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ScannerPool(workers={}, pending={}, completed={})",
+            self.workers(),
+            self.pending(),
+            self.completed()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::compile_patterns;
+
+    fn pool(workers: usize) -> ScannerPool {
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.95, 0)];
+        ScannerPool {
+            compiled: Arc::new(compile_patterns(&patterns, false)),
+            pool: rayon::ThreadPoolBuilder::new().num_threads(workers).build().unwrap(),
+            shared: Arc::default(),
+            next_id: Mutex::new(0),
+            contexts: Mutex::default(),
+        }
+    }
+
+    #[test]
+    fn test_wait_until_times_out_and_wakes() {
+        let shared = Arc::new(Shared::default());
+        let state = shared.wait_until(shared.lock(), Some(Duration::from_millis(10)), |s| !s.done.is_empty());
+        assert!(state.done.is_empty());
+        drop(state);
+
+        let worker = Arc::clone(&shared);
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            worker.lock().done.insert(7, Vec::new());
+            worker.finished.notify_all();
+        });
+        let state = shared.wait_until(shared.lock(), None, |s| s.done.contains_key(&7));
+        assert!(state.done.contains_key(&7));
+        drop(state);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit_then_wait_returns_each_jobs_matches() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let pool = pool(2);
+            let texts = ["111-11-1111 and 222-22-2222", "none here", "333-33-3333"];
+            let ids: Vec<u64> = texts.iter().map(|t| pool.submit(t.to_string(), None)).collect();
+            assert_eq!(ids, vec![1, 2, 3]);
+
+            // Collected out of submission order, each job gets its own matches
+            let spans = |id| {
+                let matches = pool.wait(py, id, None).unwrap().unwrap();
+                matches.iter().map(|m| (m.start, m.end, m.text.clone())).collect::<Vec<_>>()
+            };
+            assert_eq!(spans(3), vec![(0, 11, "333-33-3333".to_string())]);
+            assert_eq!(spans(2), vec![]);
+            assert_eq!(spans(1), vec![(0, 11, "111-11-1111".to_string()), (16, 27, "222-22-2222".to_string())]);
+
+            assert!(pool.wait(py, 1, Some(0.0)).is_err());
+            assert_eq!((pool.pending(), pool.completed()), (0, 0));
+        });
+    }
+
+    #[test]
+    fn test_panicking_job_fails_only_itself() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let pool = pool(1);
+            let bad = 100;
+            pool.spawn_job(bad, || panic!("scanner bug"));
+            let ok = pool.submit("111-11-1111".to_string(), None);
+
+            let Err(err) = pool.wait(py, bad, None) else { panic!("the panicking job should fail") };
+            assert!(err.is_instance_of::<PyRuntimeError>(py));
+            assert!(err.to_string().contains("scanner bug"));
+            // The worker survives and later jobs still complete
+            assert_eq!(pool.wait(py, ok, None).unwrap().unwrap().len(), 1);
+            assert!(pool.wait(py, bad, Some(0.0)).is_err());
+            assert_eq!(pool.completed(), 0);
+        });
+    }
+}
@@ -0,0 +1,223 @@
+//! Region-aware postal code validation
+//!
+//! Codes are checked against the format of the given country (ISO 3166
+//! alpha-2, case-insensitive; "UK" is accepted for GB). Input is trimmed
+//! and uppercased, and internal whitespace may be a single space or absent
+//! where the national format has one.
+
+/// Countries with a postal format check
+pub const SUPPORTED_COUNTRIES: &[&str] = &[
+    "AU", "BR", "CA", "CH", "DE", "ES", "FR", "GB", "IE", "IN", "IT", "JP", "NL", "PL", "SE", "US",
+];
+
+/// UK postcode areas (the leading letters of the outward code)
+const UK_AREAS: &[&str] = &[
+    "AB", "AL", "B", "BA", "BB", "BD", "BH", "BL", "BN", "BR", "BS", "BT", "CA", "CB", "CF", "CH",
+    "CM", "CO", "CR", "CT", "CV", "CW", "DA", "DD", "DE", "DG", "DH", "DL", "DN", "DT", "DY", "E",
+    "EC", "EH", "EN", "EX", "FK", "FY", "G", "GL", "GU", "GY", "HA", "HD", "HG", "HP", "HR", "HS",
+    "HU", "HX", "IG", "IM", "IP", "IV", "JE", "KA", "KT", "KW", "KY", "L", "LA", "LD", "LE", "LL",
+    "LN", "LS", "LU", "M", "ME", "MK", "ML", "N", "NE", "NG", "NN", "NP", "NR", "NW", "OL", "OX",
+    "PA", "PE", "PH", "PL", "PO", "PR", "RG", "RH", "RM", "S", "SA", "SE", "SG", "SK", "SL", "SM",
+    "SN", "SO", "SP", "SR", "SS", "ST", "SW", "SY", "TA", "TD", "TF", "TN", "TQ", "TR", "TS", "TW",
+    "UB", "W", "WA", "WC", "WD", "WF", "WN", "WR", "WS", "WV", "YO", "ZE",
+];
+
+/// Letters never used in Canadian postal codes
+const CA_EXCLUDED: &[u8] = b"DFIOQU";
+
+/// Irish Eircode routing keys are a letter and two digits, except D6W
+const IE_EXCLUDED: &[u8] = b"BIJLMOQSUZ";
+
+/// Character-class shape of a code: letters -> 'A', digits -> '9'
+fn shape(code: &str) -> String {
+    code.chars()
+        .map(|c| match c {
+            'A'..='Z' => 'A',
+            '0'..='9' => '9',
+            other => other,
+        })
+        .collect()
+}
+
+/// Trim, uppercase and collapse internal whitespace runs to one space
+fn canonical(code: &str) -> String {
+    code.split_whitespace().collect::<Vec<_>>().join(" ").to_ascii_uppercase()
+}
+
+/// Split "OUTINN" or "OUT INN" at a fixed-length inward part
+fn split_inward(code: &str, inward_len: usize) -> Option<(&str, &str)> {
+    if let Some((out, inn)) = code.split_once(' ') {
+        return (inn.len() == inward_len).then_some((out, inn));
+    }
+    (code.len() > inward_len).then(|| code.split_at(code.len() - inward_len))
+}
+
+fn digits(code: &str, n: usize) -> bool {
+    code.len() == n && code.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// US ZIP or ZIP+4 ("12345", "12345-6789")
+fn us(code: &str) -> bool {
+    let (zip, plus4) = match code.split_once('-') {
+        Some((zip, plus4)) => (zip, Some(plus4)),
+        None => (code, None),
+    };
+    digits(zip, 5) && zip != "00000" && plus4.is_none_or(|p| digits(p, 4) && p != "0000")
+}
+
+/// UK postcode with area and position-specific letter rules
+fn gb(code: &str) -> bool {
+    let Some((outward, inward)) = split_inward(code, 3) else {
+        return false;
+    };
+    if outward == "GIR" {
+        return inward == "0AA";
+    }
+    let inn = inward.as_bytes();
+    if shape(inward) != "9AA" || inn[1..].iter().any(|b| b"CIKMOV".contains(b)) {
+        return false;
+    }
+
+    let area_len = outward.bytes().take_while(|b| b.is_ascii_alphabetic()).count();
+    let area = &outward[..area_len];
+    if !UK_AREAS.contains(&area) {
+        return false;
+    }
+    let district = &outward[area_len..];
+    match shape(district).as_str() {
+        "9" | "99" => true,
+        // A9A (e.g. W1A) and AA9A (e.g. EC1A) have restricted final letters
+        "9A" => {
+            let last = district.as_bytes()[1];
+            if area_len == 1 {
+                b"ABCDEFGHJKPSTUW".contains(&last)
+            } else {
+                b"ABEHMNPRVWXY".contains(&last)
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Canadian postal code: A9A 9A9 without D, F, I, O, Q, U (or leading W, Z)
+fn ca(code: &str) -> bool {
+    let Some((fsa, ldu)) = split_inward(code, 3) else {
+        return false;
+    };
+    if shape(fsa) != "A9A" || shape(ldu) != "9A9" {
+        return false;
+    }
+    let first = fsa.as_bytes()[0];
+    let letters = [first, fsa.as_bytes()[2], ldu.as_bytes()[1]];
+    !letters.iter().any(|b| CA_EXCLUDED.contains(b)) && first != b'W' && first != b'Z'
+}
+
+/// Dutch postcode: 1234 AB, no leading zero, no SA/SD/SS series
+fn nl(code: &str) -> bool {
+    let Some((num, letters)) = split_inward(code, 2) else {
+        return false;
+    };
+    digits(num, 4) && !num.starts_with('0') && shape(letters) == "AA" && !matches!(letters, "SA" | "SD" | "SS")
+}
+
+/// Irish Eircode: routing key (A99 or D6W) plus four-character unique id
+fn ie(code: &str) -> bool {
+    let Some((key, id)) = split_inward(code, 4) else {
+        return false;
+    };
+    let routing_ok = key == "D6W"
+        || (shape(key) == "A99" && !IE_EXCLUDED.contains(&key.as_bytes()[0]));
+    routing_ok && id.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+/// Validate `code` as a postal code of `country`
+///
+/// Returns None if the country has no format check.
+pub fn validate_postal(code: &str, country: &str) -> Option<bool> {
+    let code = canonical(code);
+    let code = code.as_str();
+    let valid = match country.trim().to_ascii_uppercase().as_str() {
+        "US" => us(code),
+        "GB" | "UK" => gb(code),
+        "CA" => ca(code),
+        "NL" => nl(code),
+        "IE" => ie(code),
+        "DE" => digits(code, 5) && !code.starts_with("00"),
+        // Departments 01-95 plus overseas 97x/98x
+        "FR" => digits(code, 5) && !code.starts_with("00") && !code.starts_with("96") && !code.starts_with("99"),
+        // Two leading digits are the province, 01-52
+        "ES" => digits(code, 5) && matches!(code[..2].parse::<u32>(), Ok(1..=52)),
+        "IT" => digits(code, 5),
+        "AU" => digits(code, 4),
+        "CH" => digits(code, 4) && !code.starts_with('0'),
+        "IN" => {
+            let code = code.replace(' ', "");
+            digits(&code, 6) && !code.starts_with('0')
+        }
+        "JP" => matches!(code.split_once('-'), Some((a, b)) if digits(a, 3) && digits(b, 4)) || digits(code, 7),
+        "BR" => matches!(code.split_once('-'), Some((a, b)) if digits(a, 5) && digits(b, 3)) || digits(code, 8),
+        "PL" => matches!(code.split_once('-'), Some((a, b)) if digits(a, 2) && digits(b, 3)),
+        "SE" => matches!(split_inward(code, 2), Some((a, b)) if digits(a, 3) && digits(b, 2) && !a.starts_with('0')),
+        _ => return None,
+    };
+    Some(valid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid(code: &str, country: &str) -> bool {
+        validate_postal(code, country).unwrap()
+    }
+
+    #[test]
+    fn test_us_zip() {
+        assert!(valid("94105", "US"));
+        assert!(valid("94105-1804", "us"));
+        assert!(!valid("00000", "US"));
+        assert!(!valid("9410", "US"));
+        assert!(!valid("94105-18", "US"));
+    }
+
+    #[test]
+    fn test_uk_postcodes() {
+        assert!(valid("SW1A 1AA", "GB"));
+        assert!(valid("ec1a1bb", "UK"));
+        assert!(valid("M1 1AE", "GB"));
+        assert!(valid("B33 8TH", "GB"));
+        assert!(valid("W1A 0AX", "GB"));
+        assert!(valid("GIR 0AA", "GB"));
+        assert!(!valid("QQ1 1AA", "GB")); // Unknown area
+        assert!(!valid("X1 1AA", "GB")); // Single-letter area that doesn't exist
+        assert!(!valid("SW1A 1AC", "GB")); // C never appears in the inward code
+        assert!(!valid("W1I 0AX", "GB")); // I not allowed in A9A district
+    }
+
+    #[test]
+    fn test_canadian_postal_codes() {
+        assert!(valid("K1A 0B1", "CA"));
+        assert!(valid("m5v3l9", "CA"));
+        assert!(!valid("D1A 0B1", "CA")); // D never used
+        assert!(!valid("K1O 0B1", "CA")); // O never used
+        assert!(!valid("W1A 0B1", "CA")); // W never leads
+        assert!(!valid("K1A B01", "CA"));
+    }
+
+    #[test]
+    fn test_other_formats() {
+        assert!(valid("1012 AB", "NL"));
+        assert!(!valid("1012 SS", "NL"));
+        assert!(valid("D02 AF30", "IE"));
+        assert!(valid("D6W 1234", "IE"));
+        assert!(valid("100-0001", "JP"));
+        assert!(valid("01310-100", "BR"));
+        assert!(valid("00-950", "PL"));
+        assert!(valid("114 55", "SE"));
+        assert!(valid("28013", "ES"));
+        assert!(!valid("53013", "ES"));
+        assert!(valid("110 001", "IN"));
+        assert!(!valid("96000", "FR"));
+        assert_eq!(validate_postal("12345", "ZZ"), None);
+    }
+}
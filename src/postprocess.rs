@@ -0,0 +1,162 @@
+//! Per-entity post-processing of normalized finding values
+//!
+//! Rules are configured per entity type as an ordered list of steps,
+//! e.g. {"IBAN": ["strip_spaces", "upper"], "EMAIL": ["lower"]}, and are
+//! applied to `Finding.normalized` so one value always has one surface form.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::scratch::with_scratch;
+
+/// A single post-processing step
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Upper,
+    Lower,
+    /// Remove all whitespace
+    StripSpaces,
+    /// Remove whitespace and common separators (- . / _)
+    StripSeparators,
+    /// Keep ASCII digits only
+    DigitsOnly,
+    /// Keep at most N characters
+    Truncate(usize),
+}
+
+impl Step {
+    /// Parse a step name ("upper", "truncate:8", ...)
+    pub fn parse(spec: &str) -> PyResult<Self> {
+        let spec = spec.trim();
+        if let Some(n) = spec.strip_prefix("truncate:") {
+            return n
+                .trim()
+                .parse()
+                .map(Step::Truncate)
+                .map_err(|_| PyValueError::new_err(format!("invalid truncate length in '{}'", spec)));
+        }
+        match spec {
+            "upper" => Ok(Step::Upper),
+            "lower" => Ok(Step::Lower),
+            "strip_spaces" => Ok(Step::StripSpaces),
+            "strip_separators" => Ok(Step::StripSeparators),
+            "digits_only" => Ok(Step::DigitsOnly),
+            other => Err(PyValueError::new_err(format!(
+                "unknown post-processing step '{}'",
+                other
+            ))),
+        }
+    }
+
+    /// The step's name as accepted by `parse`
+    pub fn spec(&self) -> String {
+        match self {
+            Step::Upper => "upper".to_string(),
+            Step::Lower => "lower".to_string(),
+            Step::StripSpaces => "strip_spaces".to_string(),
+            Step::StripSeparators => "strip_separators".to_string(),
+            Step::DigitsOnly => "digits_only".to_string(),
+            Step::Truncate(n) => format!("truncate:{}", n),
+        }
+    }
+
+    /// Write the step's result for `value` into `out` (cleared first)
+    fn apply_into(&self, value: &str, out: &mut String) {
+        out.clear();
+        match self {
+            Step::Upper => out.extend(value.chars().flat_map(char::to_uppercase)),
+            // Context-sensitive (final sigma), so not done per character
+            Step::Lower => out.push_str(&value.to_lowercase()),
+            Step::StripSpaces => out.extend(value.chars().filter(|c| !c.is_whitespace())),
+            Step::StripSeparators => out.extend(
+                value
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '.' | '/' | '_')),
+            ),
+            Step::DigitsOnly => out.extend(value.chars().filter(|c| c.is_ascii_digit())),
+            Step::Truncate(n) => out.extend(value.chars().take(*n)),
+        }
+    }
+}
+
+/// Post-processing rules keyed by entity type
+#[derive(Clone, Debug, Default)]
+pub struct PostProcessors {
+    rules: HashMap<String, Vec<Step>>,
+}
+
+impl PostProcessors {
+    /// Build from {entity_type: [step, ...]} as passed from Python
+    pub fn from_config(config: HashMap<String, Vec<String>>) -> PyResult<Self> {
+        let mut rules = HashMap::new();
+        // Sorted, so the first invalid step reported doesn't depend on hash order
+        for (entity_type, steps) in config.into_iter().collect::<BTreeMap<_, _>>() {
+            let parsed = steps.iter().map(|s| Step::parse(s)).collect::<PyResult<Vec<_>>>()?;
+            rules.insert(entity_type, parsed);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rules as {entity_type: [step, ...]}, sorted by entity type
+    pub fn to_config(&self) -> BTreeMap<String, Vec<String>> {
+        self.rules
+            .iter()
+            .map(|(entity_type, steps)| (entity_type.clone(), steps.iter().map(Step::spec).collect()))
+            .collect()
+    }
+
+    /// Apply the entity's steps in order (unchanged if it has none)
+    pub fn apply(&self, entity_type: &str, value: String) -> String {
+        match self.rules.get(entity_type) {
+            // Steps alternate between the value and the thread's scratch buffer
+            Some(steps) => with_scratch(|scratch| {
+                let mut value = value;
+                for step in steps {
+                    step.apply_into(&value, &mut scratch.text);
+                    std::mem::swap(&mut value, &mut scratch.text);
+                }
+                value
+            }),
+            None => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(pairs: &[(&str, &[&str])]) -> PostProcessors {
+        let config = pairs
+            .iter()
+            .map(|(e, steps)| (e.to_string(), steps.iter().map(|s| s.to_string()).collect()))
+            .collect();
+        PostProcessors::from_config(config).unwrap()
+    }
+
+    #[test]
+    fn test_steps_apply_in_order() {
+        let post = rules(&[
+            ("IBAN", &["strip_spaces", "upper"]),
+            ("EMAIL", &["lower"]),
+            ("ACCOUNT", &["digits_only", "truncate:4"]),
+        ]);
+        assert_eq!(post.apply("IBAN", "gb82 west 1234".to_string()), "GB82WEST1234");
+        assert_eq!(post.apply("EMAIL", "Bob@Example.com".to_string()), "bob@example.com");
+        assert_eq!(post.apply("ACCOUNT", "ac-98-7654".to_string()), "9876");
+        assert_eq!(post.apply("NAME", "Ann Lee".to_string()), "Ann Lee");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Step::parse("title_case").is_err());
+        assert!(Step::parse("truncate:x").is_err());
+        assert_eq!(Step::parse(" truncate: 3 ").unwrap(), Step::Truncate(3));
+        assert_eq!(Step::parse("strip_separators").unwrap(), Step::StripSeparators);
+    }
+}
@@ -0,0 +1,330 @@
+//! Deterministic, format-preserving pseudonyms
+//!
+//! Every surrogate is derived from HMAC-SHA256(key, entity type, value),
+//! so the same value always maps to the same replacement under one key
+//! (joins across tables survive) while different keys give unrelated
+//! output. Surrogates keep the shape of the original and stay valid for
+//! downstream checks: card numbers keep their length, separators and
+//! leading digit and pass Luhn, SSNs avoid the never-issued ranges, and
+//! names come from an embedded list with the original capitalization.
+
+use hmac::{Hmac, Mac};
+use pyo3::prelude::*;
+use sha2::Sha256;
+
+use crate::checkdigit;
+use crate::finding::Finding;
+
+const FIRST_NAMES: &[&str] = &[
+    "James", "Mary", "Robert", "Patricia", "John", "Jennifer", "Michael", "Linda", "David",
+    "Elizabeth", "William", "Barbara", "Richard", "Susan", "Joseph", "Jessica", "Thomas", "Sarah",
+    "Charles", "Karen", "Daniel", "Lisa", "Matthew", "Nancy", "Anthony", "Betty", "Mark", "Sandra",
+    "Steven", "Ashley", "Andrew", "Emily", "Kevin", "Donna", "Brian", "Michelle", "George", "Carol",
+    "Edward", "Amanda", "Ronald", "Melissa", "Timothy", "Deborah", "Jason", "Stephanie", "Ryan",
+    "Rebecca", "Jacob", "Laura", "Nicholas", "Sharon", "Eric", "Cynthia", "Samuel", "Kathleen",
+    "Benjamin", "Amy", "Gregory", "Angela", "Alexander", "Helen", "Patrick", "Anna", "Raymond",
+    "Brenda", "Omar", "Priya", "Wei", "Aisha", "Mateo", "Sofia", "Hiroshi", "Fatima",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis", "Rodriguez",
+    "Martinez", "Hernandez", "Lopez", "Gonzalez", "Wilson", "Anderson", "Thomas", "Taylor", "Moore",
+    "Jackson", "Martin", "Lee", "Perez", "Thompson", "White", "Harris", "Sanchez", "Clark",
+    "Ramirez", "Lewis", "Robinson", "Walker", "Young", "Allen", "King", "Wright", "Scott", "Torres",
+    "Nguyen", "Hill", "Flores", "Green", "Adams", "Nelson", "Baker", "Hall", "Rivera", "Campbell",
+    "Mitchell", "Carter", "Roberts", "Patel", "Kim", "Chen", "Singh", "Okafor", "Novak", "Haddad",
+    "Sato", "Kowalski", "Silva",
+];
+
+/// Domains reserved for documentation (RFC 2606), used for surrogate emails
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+/// HMAC-SHA256 of the concatenated `parts` under `key`
+fn keyed_hash(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Deterministic byte stream seeded from the keyed hash of one value
+struct Stream<'a> {
+    key: &'a [u8],
+    seed: [u8; 32],
+    block: [u8; 32],
+    pos: usize,
+    counter: u32,
+}
+
+impl<'a> Stream<'a> {
+    fn new(key: &'a [u8], entity_type: &str, value: &str) -> Self {
+        let seed = keyed_hash(key, &[entity_type.as_bytes(), b"\0", value.as_bytes()]);
+        Self { key, seed, block: seed, pos: 0, counter: 0 }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        if self.pos + 4 > self.block.len() {
+            self.counter += 1;
+            self.block = keyed_hash(self.key, &[&self.seed, &self.counter.to_be_bytes()]);
+            self.pos = 0;
+        }
+        let b = &self.block[self.pos..self.pos + 4];
+        self.pos += 4;
+        u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+    }
+
+    /// Uniform-enough value in 0..n (n is always tiny relative to u32)
+    fn below(&mut self, n: u32) -> u32 {
+        self.next_u32() % n
+    }
+
+    fn digit(&mut self) -> u8 {
+        b'0' + self.below(10) as u8
+    }
+
+    fn pick<'s>(&mut self, items: &[&'s str]) -> &'s str {
+        items[self.below(items.len() as u32) as usize]
+    }
+}
+
+/// Replace each ASCII digit of `template` in order with `digits`
+fn fill_digits(template: &str, digits: &[u8]) -> String {
+    let mut it = digits.iter();
+    template
+        .chars()
+        .map(|c| if c.is_ascii_digit() { it.next().map_or(c, |&d| d as char) } else { c })
+        .collect()
+}
+
+fn card(stream: &mut Stream, value: &str) -> String {
+    let digits: Vec<u8> = value.bytes().filter(|b| b.is_ascii_digit()).collect();
+    if digits.len() < 2 {
+        return generic(stream, value);
+    }
+    // Keep the leading digit so the network (Visa, Mastercard...) is unchanged
+    let mut out = vec![digits[0]];
+    while out.len() < digits.len() - 1 {
+        out.push(stream.digit());
+    }
+    let payload = std::str::from_utf8(&out).unwrap_or_default();
+    let check = checkdigit::luhn_digit(payload).map_or(b'0', |c| c as u8);
+    out.push(check);
+    fill_digits(value, &out)
+}
+
+fn ssn(stream: &mut Stream, value: &str) -> String {
+    // Area 001-899 except 666, group 01-99, serial 0001-9999
+    let area = loop {
+        let a = 1 + stream.below(899);
+        if a != 666 {
+            break a;
+        }
+    };
+    let group = 1 + stream.below(99);
+    let serial = 1 + stream.below(9999);
+    let digits = format!("{:03}{:02}{:04}", area, group, serial);
+    if value.bytes().filter(|b| b.is_ascii_digit()).count() == 9 {
+        fill_digits(value, digits.as_bytes())
+    } else {
+        format!("{}-{}-{}", &digits[..3], &digits[3..5], &digits[5..])
+    }
+}
+
+fn phone(stream: &mut Stream, value: &str) -> String {
+    let digits: Vec<u8> = value.bytes().filter(|b| b.is_ascii_digit()).collect();
+    // Keep a leading country code, then NXX-NXX-XXXX
+    let mut out = Vec::with_capacity(digits.len());
+    if digits.len() == 11 {
+        out.push(digits[0]);
+    }
+    for i in 0..digits.len().saturating_sub(out.len()) {
+        out.push(if i == 0 || i == 3 { b'2' + stream.below(8) as u8 } else { stream.digit() });
+    }
+    fill_digits(value, &out)
+}
+
+/// Copy the capitalization style of `original` onto `name`
+fn match_case(original: &str, name: &str) -> String {
+    let letters: Vec<char> = original.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+        name.to_uppercase()
+    } else if letters.iter().all(|c| c.is_lowercase()) {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+fn person_name(key: &[u8], value: &str, surname_only: bool) -> String {
+    let tokens: Vec<&str> = value.split_whitespace().collect();
+    let last = tokens.len().saturating_sub(1);
+    let mut out = value.to_string();
+    let mut search_from = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        // Each token is keyed on its own so "John" maps the same way in
+        // "John Smith" and "John"; the last token is treated as a surname
+        let (label, list) = if surname_only || (i == last && tokens.len() > 1) {
+            ("NAME_LAST", LAST_NAMES)
+        } else {
+            ("NAME_FIRST", FIRST_NAMES)
+        };
+        let canonical = token.to_lowercase();
+        let replacement = match_case(token, Stream::new(key, label, &canonical).pick(list));
+        let pos = search_from + out[search_from..].find(token).unwrap_or(0);
+        out.replace_range(pos..pos + token.len(), &replacement);
+        search_from = pos + replacement.len();
+    }
+    out
+}
+
+fn email(stream: &mut Stream) -> String {
+    let first = stream.pick(FIRST_NAMES);
+    let last = stream.pick(LAST_NAMES);
+    format!(
+        "{}.{}{}@{}",
+        first.to_lowercase(),
+        last.to_lowercase(),
+        stream.below(100),
+        stream.pick(EMAIL_DOMAINS)
+    )
+}
+
+/// Same character classes at the same positions: digit for digit,
+/// letter for letter (case kept), everything else unchanged
+fn generic(stream: &mut Stream, value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_digit() {
+                stream.digit() as char
+            } else if c.is_ascii_uppercase() {
+                (b'A' + stream.below(26) as u8) as char
+            } else if c.is_ascii_lowercase() {
+                (b'a' + stream.below(26) as u8) as char
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Surrogate for `value` of `entity_type` under `key`
+pub fn pseudonym(key: &[u8], entity_type: &str, value: &str) -> String {
+    let normalized = crate::finding::normalize_value(entity_type, value);
+    let mut stream = Stream::new(key, entity_type, &normalized);
+    match entity_type {
+        "CREDIT_CARD" => card(&mut stream, value),
+        "SSN" => ssn(&mut stream, value),
+        "PHONE" | "PHONE_MOBILE" | "PHONE_HOME" | "PHONE_WORK" | "FAX" => phone(&mut stream, value),
+        "NAME" | "PERSON" | "FIRST_NAME" => person_name(key, value, false),
+        "LAST_NAME" => person_name(key, value, true),
+        "EMAIL" => email(&mut stream),
+        _ => generic(&mut stream, value),
+    }
+}
+
+/// Keyed pseudonym generator
+#[pyclass]
+pub struct Pseudonymizer {
+    key: Vec<u8>,
+}
+
+#[pymethods]
+impl Pseudonymizer {
+    /// Create a pseudonymizer; the same key always gives the same surrogates
+    #[new]
+    fn new(key: &[u8]) -> Self {
+        Self { key: key.to_vec() }
+    }
+
+    /// Surrogate value for one entity value
+    fn pseudonym(&self, entity_type: &str, value: &str) -> String {
+        pseudonym(&self.key, entity_type, value)
+    }
+
+    /// Replace every finding span in `text` with its surrogate
+    ///
+    /// Overlapping findings keep the earliest-starting (then longest) one.
+    fn replace(&self, py: Python<'_>, text: &str, findings: Vec<PyRef<Finding>>) -> String {
+        let spans: Vec<(usize, usize, String)> = findings
+            .iter()
+            .map(|f| (f.start, f.end, f.entity_type.clone()))
+            .collect();
+        py.allow_threads(|| replace_spans(&self.key, text, spans))
+    }
+}
+
+/// Substitute surrogates for (start, end, entity_type) spans of `text`
+pub fn replace_spans(key: &[u8], text: &str, mut spans: Vec<(usize, usize, String)>) -> String {
+    spans.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, end, entity_type) in spans {
+        if start < last || end > text.len() || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            continue;
+        }
+        out.push_str(&text[last..start]);
+        out.push_str(&pseudonym(key, &entity_type, &text[start..end]));
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators;
+
+    const KEY: &[u8] = b"test-key";
+
+    #[test]
+    fn test_deterministic_per_key() {
+        let a = pseudonym(KEY, "SSN", "123-45-6789");
+        assert_eq!(a, pseudonym(KEY, "SSN", "123-45-6789"));
+        // Normalized value is the seed, so formatting doesn't change the identity
+        assert_eq!(a.replace('-', ""), pseudonym(KEY, "SSN", "123456789"));
+        assert_ne!(a, pseudonym(b"other-key", "SSN", "123-45-6789"));
+    }
+
+    #[test]
+    fn test_card_and_ssn_stay_valid() {
+        for (i, card) in ["4111 1111 1111 1111", "5500-0000-0000-0004", "378282246310005"].iter().enumerate() {
+            let p = pseudonym(KEY, "CREDIT_CARD", card);
+            assert_eq!(p.len(), card.len(), "case {}", i);
+            assert_eq!(p.chars().next(), card.chars().next());
+            assert!(validators::luhn(&p), "{} is not Luhn-valid", p);
+        }
+        for v in ["078-05-1120", "219-09-9999", "457555462"] {
+            let p = pseudonym(KEY, "SSN", v);
+            assert!(validators::ssn_format(&p), "{} is not a valid SSN", p);
+        }
+        assert!(validators::phone_format(&pseudonym(KEY, "PHONE", "(212) 555-1234")));
+    }
+
+    #[test]
+    fn test_names_keep_case_and_token_mapping() {
+        let full = pseudonym(KEY, "NAME", "John Smith");
+        let first = pseudonym(KEY, "NAME", "john");
+        assert_eq!(full.split(' ').next().unwrap().to_lowercase(), first);
+        assert!(LAST_NAMES.contains(&full.split(' ').nth(1).unwrap()));
+        let upper = pseudonym(KEY, "NAME", "JOHN SMITH");
+        assert_eq!(upper, full.to_uppercase());
+    }
+
+    #[test]
+    fn test_generic_and_replace_spans() {
+        let p = pseudonym(KEY, "ACCOUNT", "AB-1234x");
+        assert_eq!(p.len(), 8);
+        assert_eq!(&p[2..3], "-");
+        assert!(p[3..7].bytes().all(|b| b.is_ascii_digit()));
+
+        let text = "SSN 123-45-6789 for Ann Lee";
+        let out = replace_spans(KEY, text, vec![(4, 15, "SSN".into()), (20, 27, "NAME".into()), (6, 9, "X".into())]);
+        assert!(out.starts_with("SSN "));
+        assert!(!out.contains("123-45-6789"));
+        assert!(!out.contains("Ann Lee"));
+        assert_eq!(&out[7..8], "-");
+    }
+}
@@ -0,0 +1,285 @@
+//! Redaction of matched spans
+//!
+//! `Redactor` rewrites text in one pass with a policy per entity type:
+//! keep, remove, mask (optionally keeping the last N characters), keyed
+//! hash or format-preserving pseudonym. Overlapping matches are reduced
+//! with `overlap::select_non_overlapping` first.
+//!
+//! Fixed-format files (record layouts, columnar exports) must keep every
+//! byte at its offset; for those `mask_spans` overwrites matched bytes
+//! with a fill byte directly in the caller's buffer. Nothing is copied and
+//! the buffer length never changes.
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
+
+use hmac::{Hmac, Mac};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use sha2::Sha256;
+use std::collections::HashMap;
+
+use crate::finding::normalize_value;
+use crate::overlap::{self, Span};
+use crate::pseudonym;
+
+/// Character written over masked characters
+const MASK_CHAR: char = '*';
+
+/// Hex digits of the keyed hash kept in "hash" output
+const HASH_HEX_LEN: usize = 16;
+
+/// What happens to a matched span
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Leave the text unchanged
+    Keep,
+    /// Delete the span
+    Remove,
+    /// Replace letters and digits with '*', keeping separators and the
+    /// last `keep_last` letters/digits
+    Mask { keep_last: usize },
+    /// "[ENTITY:hex]" keyed hash of the normalized value
+    Hash,
+    /// Format-preserving surrogate (see `pseudonym`)
+    Pseudonym,
+}
+
+impl Policy {
+    /// Parse "keep", "remove", "mask", "mask:N", "hash" or "pseudonym"
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if let Some(n) = spec.strip_prefix("mask:") {
+            return n
+                .trim()
+                .parse()
+                .map(|keep_last| Policy::Mask { keep_last })
+                .map_err(|_| format!("invalid mask length in '{}'", spec));
+        }
+        match spec {
+            "keep" => Ok(Policy::Keep),
+            "remove" => Ok(Policy::Remove),
+            "mask" => Ok(Policy::Mask { keep_last: 0 }),
+            "hash" => Ok(Policy::Hash),
+            "pseudonym" => Ok(Policy::Pseudonym),
+            other => Err(format!(
+                "unknown redaction policy '{}': expected keep, remove, mask, mask:N, hash or pseudonym",
+                other
+            )),
+        }
+    }
+
+    fn needs_key(&self) -> bool {
+        matches!(self, Policy::Hash | Policy::Pseudonym)
+    }
+}
+
+/// Append `value` with letters and digits masked, except the last `keep_last`
+fn push_masked(out: &mut String, value: &str, keep_last: usize) {
+    let total = value.chars().filter(|c| c.is_alphanumeric()).count();
+    let mut seen = 0;
+    for c in value.chars() {
+        if c.is_alphanumeric() {
+            seen += 1;
+            out.push(if seen + keep_last > total { c } else { MASK_CHAR });
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+/// Hex HMAC-SHA256 of "ENTITY_TYPE\0value" under `key`
+fn value_hash(key: &[u8], entity_type: &str, value: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(entity_type.as_bytes());
+    mac.update(b"\0");
+    mac.update(value.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// Per-entity redaction policies with a default for unlisted types
+pub struct Policies {
+    by_entity: HashMap<String, Policy>,
+    default: Policy,
+    key: Vec<u8>,
+}
+
+impl Policies {
+    /// Policies keyed for hash/pseudonym; errors if those are used without a key
+    pub fn new(by_entity: HashMap<String, Policy>, default: Policy, key: Option<Vec<u8>>) -> Result<Self, String> {
+        let needs_key = default.needs_key() || by_entity.values().any(Policy::needs_key);
+        match key {
+            Some(key) => Ok(Self { by_entity, default, key }),
+            None if needs_key => Err("the hash and pseudonym policies need a key".to_string()),
+            None => Ok(Self { by_entity, default, key: Vec::new() }),
+        }
+    }
+
+    pub fn policy(&self, entity_type: &str) -> &Policy {
+        self.by_entity.get(entity_type).unwrap_or(&self.default)
+    }
+
+    /// Append the redacted form of one span
+    fn push_redacted(&self, out: &mut String, entity_type: &str, value: &str) {
+        match self.policy(entity_type) {
+            Policy::Keep => out.push_str(value),
+            Policy::Remove => {}
+            Policy::Mask { keep_last } => push_masked(out, value, *keep_last),
+            Policy::Hash => {
+                let normalized = normalize_value(entity_type, value);
+                out.push('[');
+                out.push_str(entity_type);
+                out.push(':');
+                out.push_str(&value_hash(&self.key, entity_type, &normalized)[..HASH_HEX_LEN]);
+                out.push(']');
+            }
+            Policy::Pseudonym => out.push_str(&pseudonym::pseudonym(&self.key, entity_type, value)),
+        }
+    }
+
+    /// Rewrite `text` with each span redacted by its entity's policy
+    ///
+    /// Spans must be valid for `text` (see `overlap::in_bounds`).
+    pub fn redact(&self, text: &str, spans: Vec<Span>) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for span in overlap::select_non_overlapping(spans) {
+            out.push_str(&text[last..span.start]);
+            self.push_redacted(&mut out, &span.label, &text[span.start..span.end]);
+            last = span.end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+}
+
+/// One-pass redaction with a policy per entity type
+#[pyclass]
+pub struct Redactor {
+    policies: Policies,
+}
+
+#[pymethods]
+impl Redactor {
+    /// Create a redactor
+    ///
+    /// Args:
+    ///     policies: Optional {entity_type: policy}; policies are "keep",
+    ///         "remove", "mask", "mask:N" (keep the last N letters/digits),
+    ///         "hash" ("[ENTITY:hex]" keyed hash of the normalized value)
+    ///         and "pseudonym" (format-preserving surrogate)
+    ///     default: Policy for entity types not in policies
+    ///     key: Secret for hash and pseudonym policies
+    ///
+    /// Raises:
+    ///     ValueError: On an unknown policy, or hash/pseudonym without a key
+    #[new]
+    #[pyo3(signature = (policies=None, default="mask", key=None))]
+    fn new(policies: Option<HashMap<String, String>>, default: &str, key: Option<Vec<u8>>) -> PyResult<Self> {
+        let by_entity = policies
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(entity_type, spec)| Policy::parse(&spec).map(|p| (entity_type, p)))
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map_err(PyValueError::new_err)?;
+        let default = Policy::parse(default).map_err(PyValueError::new_err)?;
+        let policies = Policies::new(by_entity, default, key).map_err(PyValueError::new_err)?;
+        Ok(Self { policies })
+    }
+
+    /// Text with every match redacted by its entity's policy
+    ///
+    /// Args:
+    ///     text: The scanned text
+    ///     matches: Objects with start, end (byte offsets) and entity_type,
+    ///         e.g. RawMatch or Finding
+    ///
+    /// Overlapping matches keep the earliest-starting (then longest) one.
+    ///
+    /// Raises:
+    ///     ValueError: If a span is out of range or not on a character boundary
+    fn redact(&self, py: Python<'_>, text: &str, matches: Vec<Bound<'_, PyAny>>) -> PyResult<String> {
+        let spans = overlap::spans_from_py(text, &matches)?;
+        Ok(py.allow_threads(|| self.policies.redact(text, spans)))
+    }
+}
+
+/// Overwrite every byte of each (start, end) span with `fill`
+///
+/// Spans may overlap and are clamped to the buffer. Returns the number of
+/// bytes overwritten, counting overlaps once.
+pub fn mask_spans(buf: &mut [u8], spans: &[(usize, usize)], fill: u8) -> usize {
+    let mut sorted = spans.to_vec();
+    sorted.sort_unstable();
+
+    let mut masked = 0;
+    let mut covered = 0;
+    for (start, end) in sorted {
+        let start = start.max(covered).min(buf.len());
+        let end = end.min(buf.len());
+        if start >= end {
+            continue;
+        }
+        buf[start..end].fill(fill);
+        masked += end - start;
+        covered = end;
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize, label: &str) -> Span {
+        Span { start, end, label: label.to_string() }
+    }
+
+    #[test]
+    fn test_policy_parse() {
+        assert_eq!(Policy::parse("mask:4"), Ok(Policy::Mask { keep_last: 4 }));
+        assert_eq!(Policy::parse(" remove "), Ok(Policy::Remove));
+        assert!(Policy::parse("mask:x").is_err());
+        assert!(Policy::parse("shred").is_err());
+    }
+
+    #[test]
+    fn test_mixed_policies_in_one_pass() {
+        let by_entity = HashMap::from([
+            ("CREDIT_CARD".to_string(), Policy::Mask { keep_last: 4 }),
+            ("SSN".to_string(), Policy::Remove),
+            ("EMAIL".to_string(), Policy::Hash),
+            ("NAME".to_string(), Policy::Keep),
+        ]);
+        let policies = Policies::new(by_entity, Policy::Mask { keep_last: 0 }, Some(b"k".to_vec())).unwrap();
+
+        let text = "Ann card 4111-1111-1111-1234 ssn 123-45-6789 mail a@b.co zip 90210";
+        let spans = vec![
+            span(0, 3, "NAME"),
+            span(9, 28, "CREDIT_CARD"),
+            span(33, 44, "SSN"),
+            span(50, 56, "EMAIL"),
+            span(61, 66, "ZIP"),
+        ];
+        let out = policies.redact(text, spans);
+        let hash = &value_hash(b"k", "EMAIL", "a@b.co")[..HASH_HEX_LEN];
+        assert_eq!(out, format!("Ann card ****-****-****-1234 ssn  mail [EMAIL:{}] zip *****", hash));
+
+        // Case differences normalize to the same hash
+        assert_eq!(policies.redact("A@B.CO", vec![span(0, 6, "EMAIL")]), format!("[EMAIL:{}]", hash));
+    }
+
+    #[test]
+    fn test_keyed_policies_need_key() {
+        assert!(Policies::new(HashMap::new(), Policy::Hash, None).is_err());
+        assert!(Policies::new(HashMap::new(), Policy::Remove, None).is_ok());
+    }
+
+    #[test]
+    fn test_mask_spans_keeps_length() {
+        let mut buf = b"SSN 123-45-6789 card 4111".to_vec();
+        let masked = mask_spans(&mut buf, &[(21, 25), (4, 15), (10, 12), (24, 99)], b'*');
+        assert_eq!(buf, b"SSN *********** card ****");
+        assert_eq!(masked, 15);
+    }
+}
@@ -0,0 +1,354 @@
+//! Document region classification for mixed-content files
+//!
+//! A cheap line-based pre-pass labels each stretch of a document as prose,
+//! table-like, code, base64 blob, binary or hex dump. Patterns can be restricted to
+//! some region kinds (email patterns to prose and tables, secret patterns
+//! to code), and are then only run over those stretches.
+//!
+//! Consecutive lines of the same kind form one region; blank lines join
+//! the region before them. Regions cover the whole text, line terminators
+//! included, so every byte belongs to exactly one region.
+
+/// Kind of a document region, usable as a bit in a `RegionMask`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionKind {
+    Prose = 1,
+    Table = 2,
+    Code = 4,
+    Base64 = 8,
+    Binary = 16,
+    /// Offset column + hex byte groups (+ ASCII gutter): xxd, hexdump -C,
+    /// packet captures, debugger memory views
+    HexDump = 32,
+}
+
+/// Set of region kinds, one bit per `RegionKind`
+pub type RegionMask = u8;
+
+/// Mask allowing every region kind
+pub const ALL_REGIONS: RegionMask = 0x3f;
+
+impl RegionKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "prose" => Some(Self::Prose),
+            "table" => Some(Self::Table),
+            "code" => Some(Self::Code),
+            "base64" => Some(Self::Base64),
+            "binary" => Some(Self::Binary),
+            "hexdump" => Some(Self::HexDump),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Prose => "prose",
+            Self::Table => "table",
+            Self::Code => "code",
+            Self::Base64 => "base64",
+            Self::Binary => "binary",
+            Self::HexDump => "hexdump",
+        }
+    }
+
+    pub fn bit(self) -> RegionMask {
+        self as RegionMask
+    }
+}
+
+/// Classified stretch of a document (byte offsets)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub start: usize,
+    pub end: usize,
+    pub kind: RegionKind,
+}
+
+/// Minimum length of a single-token line to count as a base64 blob
+const BASE64_MIN_LEN: usize = 40;
+/// Share of control or replacement characters that makes a line binary
+const BINARY_RATIO: f32 = 0.1;
+/// Share of code punctuation among non-space characters that makes a line code
+const CODE_SYMBOL_RATIO: f32 = 0.15;
+
+/// Fewest hex digits in the byte groups of a hex dump line (8 bytes)
+const HEXDUMP_MIN_DIGITS: usize = 16;
+
+/// Line prefixes that introduce code in common languages
+const CODE_PREFIXES: &[&str] = &[
+    "def ", "class ", "import ", "from ", "fn ", "pub ", "use ", "function ", "return ", "const ", "let ", "var ",
+    "public ", "private ", "protected ", "package ", "#include", "#define", "if (", "for (", "while (", "//", "/*",
+    "} else", "@",
+];
+
+fn is_binary(line: &str) -> bool {
+    let mut total = 0usize;
+    let mut odd = 0usize;
+    for c in line.chars() {
+        total += 1;
+        if (c.is_control() && c != '\t') || c == '\u{FFFD}' {
+            odd += 1;
+        }
+    }
+    odd > 0 && odd as f32 >= total as f32 * BINARY_RATIO
+}
+
+/// Offset column: 4+ hex digits, optional 0x prefix, ` separators
+/// (WinDbg) and trailing ':'
+fn is_hex_offset(token: &str) -> bool {
+    let token = token.strip_suffix(':').unwrap_or(token);
+    let token = token.strip_prefix("0x").unwrap_or(token);
+    let digits = token.bytes().filter(|&b| b != b'`').count();
+    digits >= 4 && token.bytes().all(|b| b.is_ascii_hexdigit() || b == b'`')
+}
+
+/// Byte group: 2, 4 or 8 hex digits (single bytes, xxd words, dwords)
+fn is_hex_group(token: &str) -> bool {
+    matches!(token.len(), 2 | 4 | 8) && token.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn is_hexdump(line: &str) -> bool {
+    let mut tokens = line.split_whitespace();
+    if !tokens.next().is_some_and(is_hex_offset) {
+        return false;
+    }
+    // Groups run up to the ASCII gutter (or the end of the line); WinDbg
+    // joins the two halves of a row with '-'
+    let digits: usize = tokens
+        .take_while(|t| t.split('-').all(is_hex_group))
+        .map(|t| t.bytes().filter(u8::is_ascii_hexdigit).count())
+        .sum();
+    digits >= HEXDUMP_MIN_DIGITS
+}
+
+fn is_base64(line: &str) -> bool {
+    line.len() >= BASE64_MIN_LEN
+        && line.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'-' | b'_'))
+        && line.bytes().any(|b| b.is_ascii_digit())
+        && line.bytes().any(|b| b.is_ascii_uppercase())
+        && line.bytes().any(|b| b.is_ascii_lowercase())
+}
+
+fn is_table(line: &str) -> bool {
+    let tokens = line.split_whitespace().count();
+    let count = |c: char| line.matches(c).count();
+    count('\t') >= 2 || count('|') >= 2 || [',', ';'].iter().any(|&c| count(c) >= 3 && count(c) + 1 >= tokens)
+}
+
+fn is_code(line: &str) -> bool {
+    if line.ends_with([';', '{', '}']) || line.starts_with('}') || CODE_PREFIXES.iter().any(|p| line.starts_with(p)) {
+        return true;
+    }
+    let mut visible = 0usize;
+    let mut symbols = 0usize;
+    for b in line.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        visible += 1;
+        if matches!(b, b'{' | b'}' | b'[' | b']' | b'(' | b')' | b'<' | b'>' | b'=' | b';') {
+            symbols += 1;
+        }
+    }
+    visible >= 8 && symbols as f32 >= visible as f32 * CODE_SYMBOL_RATIO
+}
+
+/// Kind of one non-blank line
+pub fn classify_line(line: &str) -> RegionKind {
+    let trimmed = line.trim();
+    if is_hexdump(trimmed) {
+        RegionKind::HexDump
+    } else if is_binary(trimmed) {
+        RegionKind::Binary
+    } else if is_base64(trimmed) {
+        RegionKind::Base64
+    } else if is_table(trimmed) {
+        RegionKind::Table
+    } else if is_code(trimmed) {
+        RegionKind::Code
+    } else {
+        RegionKind::Prose
+    }
+}
+
+/// Split `text` into classified regions, in order and covering all of it
+pub fn classify_regions(text: &str) -> Vec<Region> {
+    let mut regions: Vec<Region> = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let (start, end) = (offset, offset + line.len());
+        offset = end;
+        if line.trim().is_empty() {
+            // Leading blank lines are picked up by the first region
+            if let Some(last) = regions.last_mut() {
+                last.end = end;
+            }
+            continue;
+        }
+        let kind = classify_line(line);
+        match regions.last_mut() {
+            Some(last) if last.kind == kind => last.end = end,
+            Some(_) => regions.push(Region { start, end, kind }),
+            None => regions.push(Region { start: 0, end, kind }),
+        }
+    }
+    if regions.is_empty() && !text.is_empty() {
+        regions.push(Region { start: 0, end: text.len(), kind: RegionKind::Prose });
+    }
+    smooth_tables(&mut regions, text);
+    regions
+}
+
+/// A lone table-like line inside prose is a list sentence, not a table
+fn smooth_tables(regions: &mut Vec<Region>, text: &str) {
+    for region in regions.iter_mut() {
+        if region.kind == RegionKind::Table && !text[region.start..region.end].trim_end().contains('\n') {
+            region.kind = RegionKind::Prose;
+        }
+    }
+    regions.dedup_by(|next, prev| {
+        if prev.kind == next.kind {
+            prev.end = next.end;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Merged byte ranges of the regions whose kind is in `mask`
+pub fn allowed_ranges(regions: &[Region], mask: RegionMask) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for region in regions.iter().filter(|r| mask & r.kind.bit() != 0) {
+        match ranges.last_mut() {
+            Some(last) if last.1 == region.start => last.1 = region.end,
+            _ => ranges.push((region.start, region.end)),
+        }
+    }
+    ranges
+}
+
+/// Whether a matched value is only digits or hex digits (plus separators)
+pub fn is_numeric_value(value: &str) -> bool {
+    value.chars().any(|c| c.is_ascii_digit())
+        && value.chars().all(|c| c.is_ascii_hexdigit() || !c.is_alphanumeric())
+}
+
+/// Remove numeric matches that overlap a hex dump region
+///
+/// `span` gives each item's (start, end, text), the text borrowed from the
+/// scanned document; `regions` must be sorted, as returned by
+/// `classify_regions`.
+pub fn drop_numeric_in_hexdumps<'t, T>(
+    regions: &[Region],
+    items: &mut Vec<T>,
+    span: impl Fn(&T) -> (usize, usize, &'t str),
+) {
+    let dumps: Vec<(usize, usize)> =
+        regions.iter().filter(|r| r.kind == RegionKind::HexDump).map(|r| (r.start, r.end)).collect();
+    if dumps.is_empty() {
+        return;
+    }
+    items.retain(|item| {
+        let (start, end, text) = span(item);
+        let first = dumps.partition_point(|&(_, e)| e <= start);
+        let in_dump = dumps.get(first).is_some_and(|&(s, _)| s < end.max(start + 1));
+        !(in_dump && is_numeric_value(text))
+    });
+}
+
+/// Names of the region kinds in a mask, in `RegionKind` order
+pub fn mask_names(mask: RegionMask) -> Vec<&'static str> {
+    [
+        RegionKind::Prose,
+        RegionKind::Table,
+        RegionKind::Code,
+        RegionKind::Base64,
+        RegionKind::Binary,
+        RegionKind::HexDump,
+    ]
+        .into_iter()
+        .filter(|kind| mask & kind.bit() != 0)
+        .map(RegionKind::as_str)
+        .collect()
+}
+
+/// Parse region kind names into a mask
+pub fn mask_from_names<S: AsRef<str>>(names: &[S]) -> Result<RegionMask, String> {
+    names.iter().try_fold(0, |mask, name| match RegionKind::parse(name.as_ref()) {
+        Some(kind) => Ok(mask | kind.bit()),
+        None => Err(format!(
+            "unknown region kind '{}': expected prose, table, code, base64, binary or hexdump",
+            name.as_ref()
+        )),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<(&str, RegionKind)> {
+        classify_regions(text).iter().map(|r| (&text[r.start..r.end], r.kind)).collect()
+    }
+
+    #[test]
+    fn test_classify_line() {
+        assert_eq!(classify_line("Please contact Jane at jane@example.com, thanks."), RegionKind::Prose);
+        assert_eq!(classify_line("id|name|email"), RegionKind::Table);
+        assert_eq!(classify_line("1,Jane,jane@example.com,555-1234"), RegionKind::Table);
+        assert_eq!(classify_line("I bought apples, pears, plums, and figs."), RegionKind::Prose);
+        assert_eq!(classify_line("    let key = env::var(\"API_KEY\")?;"), RegionKind::Code);
+        assert_eq!(classify_line("def main():"), RegionKind::Code);
+        assert_eq!(classify_line("TWFueSBoYW5kcyBtYWtlIGxpZ2h0IHdvcmsuIE1hbnkgaGFuZHMgbWFr"), RegionKind::Base64);
+        assert_eq!(classify_line("\u{1}\u{2}ELF\u{0}\u{0}"), RegionKind::Binary);
+    }
+
+    #[test]
+    fn test_hexdump_lines() {
+        for line in [
+            "00000010  34 31 31 31 20 31 31 31  31 20 31 31 31 31 20 31  |4111 1111 1111 1|",
+            "00000000: 3132 332d 3435 2d36 3738 390a            123-45-6789.",
+            "0040   00 1a 2b 3c 4d 5e 00 1a 2b 3c 4d 5e 08 00 45 00   ..+<M^..+<M^..E.",
+            "00007ff6`12340000  4d 5a 90 00 03 00 00 00-04 00 00 00 ff ff 00 00  MZ..............",
+            "0x7ffd5000: 41414141 42424242 43434343 44444444",
+        ] {
+            assert_eq!(classify_line(line), RegionKind::HexDump, "{}", line);
+        }
+        // Too few groups, or no offset column
+        assert_ne!(classify_line("2024 12 31 was a busy day"), RegionKind::HexDump);
+        assert_ne!(classify_line("Call 5550 10 44 77 today"), RegionKind::HexDump);
+        assert_ne!(classify_line("4d 5a 90 00 03 00 00 00 04 00 00 00"), RegionKind::HexDump);
+    }
+
+    #[test]
+    fn test_regions_cover_text() {
+        let text = "\nHello there, this is prose.\n\nfn main() {\n    run();\n}\n\nid|name|email\n1|Jane|j@x.io\nBye.";
+        let got = kinds(text);
+        assert_eq!(
+            got,
+            [
+                ("\nHello there, this is prose.\n\n", RegionKind::Prose),
+                ("fn main() {\n    run();\n}\n\n", RegionKind::Code),
+                ("id|name|email\n1|Jane|j@x.io\n", RegionKind::Table),
+                ("Bye.", RegionKind::Prose),
+            ]
+        );
+        assert_eq!(kinds(""), []);
+        assert_eq!(kinds("\n\n"), [("\n\n", RegionKind::Prose)]);
+    }
+
+    #[test]
+    fn test_lone_table_line_is_prose() {
+        let text = "Intro line.\na|b|c\nMore prose.\n";
+        assert_eq!(kinds(text), [(text, RegionKind::Prose)]);
+    }
+
+    #[test]
+    fn test_allowed_ranges_and_masks() {
+        let text = "Prose here.\nfn x() {}\nMore prose.\n";
+        let regions = classify_regions(text);
+        assert_eq!(allowed_ranges(&regions, RegionKind::Prose.bit()), [(0, 12), (22, 34)]);
+        assert_eq!(allowed_ranges(&regions, ALL_REGIONS), [(0, 34)]);
+        assert_eq!(mask_from_names(&["prose", "CODE"]), Ok(5));
+        assert!(mask_from_names(&["markdown"]).is_err());
+    }
+}
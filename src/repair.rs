@@ -0,0 +1,245 @@
+//! Per-entity repair of matched values before validation
+//!
+//! Greedy patterns capture decorations around a value: a label
+//! ("SSN# 123-45-6789", "№ 1234"), enclosing brackets or quotes, trailing
+//! punctuation from the sentence. Validators see the whole match and
+//! reject it. Rules are configured per entity type like post-processing,
+//! e.g. {"SSN": ["labels", "trailing_punct"]}; the steps strip decorations
+//! from the ends of the match until none applies, and the remaining
+//! substring is what gets normalized and validated.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// A single repair step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Repair {
+    /// Leading labels and number markers ("SSN#", "Tel.", "No.", "№")
+    Labels,
+    /// Enclosing brackets and quotes, and unmatched ones at either end
+    Brackets,
+    /// Trailing sentence punctuation (. , ; : ! ? …)
+    TrailingPunct,
+}
+
+impl Repair {
+    pub fn parse(spec: &str) -> PyResult<Self> {
+        match spec.trim() {
+            "labels" => Ok(Repair::Labels),
+            "brackets" => Ok(Repair::Brackets),
+            "trailing_punct" => Ok(Repair::TrailingPunct),
+            other => Err(PyValueError::new_err(format!(
+                "unknown repair step '{}': expected labels, brackets or trailing_punct",
+                other
+            ))),
+        }
+    }
+
+    pub fn spec(self) -> &'static str {
+        match self {
+            Repair::Labels => "labels",
+            Repair::Brackets => "brackets",
+            Repair::TrailingPunct => "trailing_punct",
+        }
+    }
+
+    /// One application of the step to `value`; returns the kept subslice
+    fn apply<'a>(self, entity_type: &str, value: &'a str) -> &'a str {
+        match self {
+            Repair::Labels => strip_label(entity_type, value),
+            Repair::Brackets => strip_brackets(value),
+            Repair::TrailingPunct => value.trim_end_matches(['.', ',', ';', ':', '!', '?', '…']),
+        }
+    }
+}
+
+/// Number markers that may precede any value, lowercase
+const NUMBER_MARKERS: &[&str] = &["number", "num.", "num", "no.", "no", "nr.", "nr", "n°", "nº", "№", "#"];
+
+/// Entity-specific labels, lowercase, longest first within each entity
+fn entity_labels(entity_type: &str) -> &'static [&'static str] {
+    match entity_type {
+        "SSN" => &["social security number", "social security no", "social security", "soc. sec.", "ssn"],
+        "ITIN" => &["itin"],
+        "PHONE" | "PHONE_MOBILE" | "PHONE_HOME" | "PHONE_WORK" | "FAX" => {
+            &["telephone", "mobile", "phone", "cell", "tel.", "tel", "fax", "mob", "ph"]
+        }
+        "CREDIT_CARD" => &["card number", "card no", "card", "cc"],
+        "IBAN" => &["iban"],
+        "PASSPORT" => &["passport"],
+        "NHS_NUMBER" => &["nhs number", "nhs no", "nhs"],
+        "INSEE_FR" => &["n° ss", "insee", "nir"],
+        "AADHAAR" | "AADHAAR_IN" => &["aadhaar"],
+        "MRN" => &["mrn"],
+        _ => &[],
+    }
+}
+
+/// `value` without a leading `label` (ASCII case-insensitive), if it has one
+///
+/// Word labels must not run into a following letter or digit, so "no"
+/// is not stripped from "north".
+fn strip_prefix_ci<'a>(value: &'a str, label: &str) -> Option<&'a str> {
+    let head = value.get(..label.len())?;
+    if !head.eq_ignore_ascii_case(label) {
+        return None;
+    }
+    let rest = &value[label.len()..];
+    let wordy = label.chars().last().is_some_and(char::is_alphanumeric);
+    if wordy && rest.chars().next().is_some_and(char::is_alphanumeric) {
+        return None;
+    }
+    Some(rest)
+}
+
+fn strip_label<'a>(entity_type: &str, value: &'a str) -> &'a str {
+    let separators = |c: char| c.is_whitespace() || matches!(c, ':' | '=' | '-' | '.');
+    let mut rest = value;
+    if let Some(after) = entity_labels(entity_type).iter().find_map(|label| strip_prefix_ci(rest, label)) {
+        rest = after.trim_start_matches(separators);
+    }
+    if let Some(after) = NUMBER_MARKERS.iter().find_map(|marker| strip_prefix_ci(rest, marker)) {
+        rest = after.trim_start_matches(separators);
+    }
+    // A label alone is not a repaired value
+    if rest.len() == value.len() || !rest.chars().any(char::is_alphanumeric) {
+        return value;
+    }
+    rest
+}
+
+/// (opening, closing) bracket and quote pairs
+const PAIRS: &[(char, char)] = &[
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('<', '>'),
+    ('«', '»'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('“', '”'),
+    ('‘', '’'),
+    ('「', '」'),
+];
+
+fn strip_brackets(value: &str) -> &str {
+    let first = value.chars().next();
+    let last = value.chars().next_back();
+    for &(open, close) in PAIRS {
+        let (starts, ends) = (first == Some(open), last == Some(close));
+        if starts && ends && value.len() > open.len_utf8() + close.len_utf8() {
+            return &value[open.len_utf8()..value.len() - close.len_utf8()];
+        }
+        // An unmatched bracket at one end (its partner fell outside the match)
+        if starts && !value[open.len_utf8()..].contains(close) {
+            return &value[open.len_utf8()..];
+        }
+        if ends && open != close && !value[..value.len() - close.len_utf8()].contains(open) {
+            return &value[..value.len() - close.len_utf8()];
+        }
+    }
+    value
+}
+
+/// Most passes over a value; each pass strips at least one character
+const MAX_PASSES: usize = 8;
+
+/// Repair rules keyed by entity type
+#[derive(Clone, Debug, Default)]
+pub struct Repairs {
+    rules: HashMap<String, Vec<Repair>>,
+}
+
+impl Repairs {
+    /// Build from {entity_type: [step, ...]} as passed from Python
+    pub fn from_config(config: HashMap<String, Vec<String>>) -> PyResult<Self> {
+        let mut rules = HashMap::new();
+        // Sorted, so the first invalid step reported doesn't depend on hash order
+        for (entity_type, steps) in config.into_iter().collect::<BTreeMap<_, _>>() {
+            let parsed = steps.iter().map(|s| Repair::parse(s)).collect::<PyResult<Vec<_>>>()?;
+            rules.insert(entity_type, parsed);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rules as {entity_type: [step, ...]}, sorted by entity type
+    pub fn to_config(&self) -> BTreeMap<String, Vec<String>> {
+        self.rules
+            .iter()
+            .map(|(entity_type, steps)| (entity_type.clone(), steps.iter().map(|s| s.spec().to_string()).collect()))
+            .collect()
+    }
+
+    /// Byte range of `text` left after repair, or None if nothing was stripped
+    ///
+    /// Steps run in order, repeatedly, until a whole pass changes nothing;
+    /// surrounding whitespace is trimmed along the way.
+    pub fn apply(&self, entity_type: &str, text: &str) -> Option<(usize, usize)> {
+        let steps = self.rules.get(entity_type)?;
+        let mut value = text.trim();
+        for _ in 0..MAX_PASSES {
+            let before = value.len();
+            for step in steps {
+                value = step.apply(entity_type, value).trim();
+            }
+            if value.len() == before {
+                break;
+            }
+        }
+        if value.len() == text.len() || value.is_empty() {
+            return None;
+        }
+        // Every step returns a subslice, so the offset is the pointer distance
+        let start = value.as_ptr() as usize - text.as_ptr() as usize;
+        Some((start, start + value.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repairs(entity_type: &str, steps: &[&str]) -> Repairs {
+        let config = HashMap::from([(entity_type.to_string(), steps.iter().map(|s| s.to_string()).collect())]);
+        Repairs::from_config(config).unwrap()
+    }
+
+    fn repaired<'a>(rules: &Repairs, entity_type: &str, text: &'a str) -> &'a str {
+        rules.apply(entity_type, text).map_or(text, |(s, e)| &text[s..e])
+    }
+
+    #[test]
+    fn test_repairs_strip_decorations() {
+        let ssn = repairs("SSN", &["labels", "brackets", "trailing_punct"]);
+        assert_eq!(repaired(&ssn, "SSN", "SSN# 123-45-6789."), "123-45-6789");
+        assert_eq!(repaired(&ssn, "SSN", "(Social Security No.: 123-45-6789),"), "123-45-6789");
+        assert_eq!(repaired(&ssn, "SSN", "«123-45-6789»"), "123-45-6789");
+        assert_eq!(repaired(&ssn, "SSN", "123-45-6789)"), "123-45-6789");
+        assert_eq!(ssn.apply("SSN", "123-45-6789"), None);
+        // Only configured entity types are repaired
+        assert_eq!(ssn.apply("PHONE", "Tel. 555-0100."), None);
+
+        let phone = repairs("PHONE", &["labels"]);
+        assert_eq!(repaired(&phone, "PHONE", "Tel. № 555-0100"), "555-0100");
+        assert_eq!(repaired(&phone, "PHONE", "Phone:"), "Phone:");
+    }
+
+    #[test]
+    fn test_labels_need_a_word_boundary() {
+        let rules = repairs("ACCOUNT", &["labels"]);
+        assert_eq!(rules.apply("ACCOUNT", "north 12"), None);
+        assert_eq!(repaired(&rules, "ACCOUNT", "No 12-34"), "12-34");
+        assert_eq!(repaired(&rules, "ACCOUNT", "#4471"), "4471");
+    }
+
+    #[test]
+    fn test_unknown_step() {
+        let config = HashMap::from([("SSN".to_string(), vec!["labels".to_string(), "trim".to_string()])]);
+        assert!(Repairs::from_config(config).is_err());
+    }
+}
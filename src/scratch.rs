@@ -0,0 +1,123 @@
+//! Per-thread scratch buffers reused across scans
+//!
+//! Small documents at high request rates spend a large share of each scan
+//! allocating and freeing working buffers: the pattern hit set, two-pass
+//! candidate lists, dictionary tokens and keys, normalization strings.
+//! Each thread keeps one set of these between calls and clears rather than
+//! frees them, so steady-state scanning only allocates what is returned to
+//! the caller. The regex engines' own search caches are already pooled per
+//! thread inside regex-automata.
+
+use regex_automata::PatternSet;
+use std::cell::RefCell;
+
+/// Buffers larger than this (in elements or bytes) are released after use,
+/// so one huge document does not pin its working memory to the thread
+const MAX_RETAINED: usize = 64 * 1024;
+
+/// Working buffers for one scan; callers clear a buffer before using it
+#[derive(Default)]
+pub struct Scratch {
+    /// Which patterns match anywhere in the text
+    set: Option<PatternSet>,
+    /// Indices of the patterns in `set`
+    pub hits: Vec<usize>,
+    /// Two-pass candidate start positions per pattern
+    pub candidates: Vec<Vec<usize>>,
+    /// Dictionary token spans
+    pub tokens: Vec<(usize, usize)>,
+    /// Dictionary lookup key / normalization buffer
+    pub text: String,
+}
+
+impl Scratch {
+    /// Run `search` on an empty set of `len` patterns and collect the
+    /// indices it marks, in order, into `hits`
+    pub fn collect_hits(&mut self, len: usize, search: impl FnOnce(&mut PatternSet)) {
+        let mut set = match self.set.take() {
+            Some(mut set) if set.capacity() == len => {
+                set.clear();
+                set
+            }
+            _ => PatternSet::new(len),
+        };
+        search(&mut set);
+        self.hits.clear();
+        self.hits.extend(set.iter().map(|p| p.as_usize()));
+        self.set = Some(set);
+    }
+
+    /// Drop buffers that grew past MAX_RETAINED
+    fn release_oversized(&mut self) {
+        if self.set.as_ref().is_some_and(|s| s.capacity() > MAX_RETAINED) {
+            self.set = None;
+        }
+        if self.hits.capacity() > MAX_RETAINED {
+            self.hits = Vec::new();
+        }
+        if self.candidates.iter().map(Vec::capacity).sum::<usize>() > MAX_RETAINED {
+            self.candidates = Vec::new();
+        }
+        if self.tokens.capacity() > MAX_RETAINED {
+            self.tokens = Vec::new();
+        }
+        if self.text.capacity() > MAX_RETAINED {
+            self.text = String::new();
+        }
+    }
+}
+
+thread_local! {
+    static SCRATCH: RefCell<Scratch> = RefCell::default();
+}
+
+/// Run `f` with this thread's scratch buffers
+///
+/// A nested call on the same thread (the buffers are already in use) gets
+/// a fresh temporary set instead.
+pub fn with_scratch<R>(f: impl FnOnce(&mut Scratch) -> R) -> R {
+    SCRATCH.with(|cell| match cell.try_borrow_mut() {
+        Ok(mut scratch) => {
+            let result = f(&mut scratch);
+            scratch.release_oversized();
+            result
+        }
+        Err(_) => f(&mut Scratch::default()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex_automata::PatternID;
+
+    #[test]
+    fn test_buffers_are_reused_and_cleared() {
+        let ptr = with_scratch(|s| {
+            s.hits.extend([1, 2, 3]);
+            s.hits.as_ptr()
+        });
+        with_scratch(|s| {
+            assert_eq!(s.hits.as_ptr(), ptr);
+            s.hits.clear();
+
+            s.collect_hits(4, |set| {
+                set.insert(PatternID::must(2));
+                set.insert(PatternID::must(0));
+            });
+            assert_eq!(s.hits, [0, 2]);
+            s.collect_hits(4, |set| assert!(set.is_empty()));
+            assert!(s.hits.is_empty());
+            s.collect_hits(7, |set| assert_eq!(set.capacity(), 7));
+
+            // Nested use gets its own buffers
+            with_scratch(|inner| assert!(inner.candidates.is_empty()));
+        });
+    }
+
+    #[test]
+    fn test_oversized_buffers_are_released() {
+        with_scratch(|s| s.tokens.reserve(MAX_RETAINED + 1));
+        with_scratch(|s| assert!(s.tokens.capacity() <= MAX_RETAINED));
+    }
+}
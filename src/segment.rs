@@ -0,0 +1,215 @@
+//! Sentence and paragraph segmentation with byte offsets
+//!
+//! Lightweight rule-based splitter: paragraphs break on blank lines,
+//! sentences break after terminal punctuation followed by whitespace,
+//! except after common abbreviations and single-letter initials.
+//! Spans exclude surrounding whitespace and index into the original text.
+
+/// Abbreviations that end in a period without ending the sentence
+/// (compared case-insensitively, without the trailing period)
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "ave", "blvd", "rd", "no", "vs",
+    "etc", "inc", "ltd", "co", "corp", "dept", "apt", "e.g", "i.e", "approx", "jan", "feb",
+    "mar", "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov", "dec", "mon", "tue",
+    "wed", "thu", "fri", "sat", "sun", "fig", "vol", "pp",
+];
+
+/// Characters that can end a sentence
+fn is_terminal(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '؟' | '۔' | '…')
+}
+
+/// Closing characters allowed between terminal punctuation and whitespace
+fn is_closer(c: char) -> bool {
+    matches!(c, '"' | '\'' | ')' | ']' | '}' | '»' | '”' | '’')
+}
+
+/// Trim whitespace from both ends of a span
+fn trim_span(text: &str, start: usize, end: usize) -> Option<(usize, usize)> {
+    let slice = &text[start..end];
+    let lead = slice.len() - slice.trim_start().len();
+    let trail = slice.len() - slice.trim_end().len();
+    if lead + trail >= slice.len() {
+        None
+    } else {
+        Some((start + lead, end - trail))
+    }
+}
+
+/// Paragraph spans: runs of text separated by one or more blank lines
+pub fn paragraphs(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut para_start = 0;
+    let mut line_start = 0;
+    let mut in_blank_run = false;
+
+    for (idx, c) in text.char_indices() {
+        if c != '\n' {
+            continue;
+        }
+        let line = &text[line_start..idx];
+        if line.trim().is_empty() {
+            if !in_blank_run {
+                if let Some(span) = trim_span(text, para_start, line_start) {
+                    spans.push(span);
+                }
+                in_blank_run = true;
+            }
+            para_start = idx + 1;
+        } else {
+            in_blank_run = false;
+        }
+        line_start = idx + 1;
+    }
+
+    if let Some(span) = trim_span(text, para_start, text.len()) {
+        spans.push(span);
+    }
+    spans
+}
+
+/// Whether the word ending at a period is an abbreviation or initial
+fn is_abbreviation(text: &str, period_idx: usize) -> bool {
+    let before = &text[..period_idx];
+    let word_start = before
+        .rfind(|c: char| c.is_whitespace() || c == '(' || c == '"')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let word = &before[word_start..];
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        // Single-letter initial ("J. Smith")
+        (Some(c), None) if c.is_alphabetic() => true,
+        _ => ABBREVIATIONS.iter().any(|a| a.eq_ignore_ascii_case(word)),
+    }
+}
+
+/// Sentence spans within `text`, never crossing paragraph boundaries
+pub fn sentences(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+
+    for (para_start, para_end) in paragraphs(text) {
+        let mut sent_start = para_start;
+        let mut iter = text[para_start..para_end].char_indices().peekable();
+
+        while let Some((rel, c)) = iter.next() {
+            if !is_terminal(c) {
+                continue;
+            }
+            let idx = para_start + rel;
+            if c == '.' && is_abbreviation(text, idx) {
+                continue;
+            }
+
+            // Absorb repeated terminals and closing quotes/brackets
+            let mut end = idx + c.len_utf8();
+            while let Some(&(next_rel, next)) = iter.peek() {
+                if is_terminal(next) || is_closer(next) {
+                    end = para_start + next_rel + next.len_utf8();
+                    iter.next();
+                } else {
+                    break;
+                }
+            }
+
+            // A break needs whitespace (or the paragraph end) after it and
+            // must not run into a lowercase continuation ("Yes!" he said);
+            // CJK full stops break without whitespace
+            let boundary = match iter.peek() {
+                None => true,
+                Some(&(_, next)) => {
+                    let continues_lowercase = text[end..para_end]
+                        .trim_start()
+                        .chars()
+                        .next()
+                        .is_some_and(|n| n.is_lowercase());
+                    (next.is_whitespace() && !continues_lowercase)
+                        || matches!(c, '。' | '！' | '？')
+                }
+            };
+            if boundary {
+                if let Some(span) = trim_span(text, sent_start, end) {
+                    spans.push(span);
+                }
+                sent_start = end;
+            }
+        }
+
+        if let Some(span) = trim_span(text, sent_start, para_end) {
+            spans.push(span);
+        }
+    }
+    spans
+}
+
+/// Smallest union of `spans` covering [start, end), clipped to the text
+///
+/// Falls back to the match itself when no span overlaps it.
+pub fn enclosing_span(spans: &[(usize, usize)], start: usize, end: usize) -> (usize, usize) {
+    let mut result: Option<(usize, usize)> = None;
+    for &(s, e) in spans {
+        if s >= end.max(start + 1) {
+            break;
+        }
+        if e > start {
+            result = Some(match result {
+                Some((rs, re)) => (rs.min(s), re.max(e)),
+                None => (s, e),
+            });
+        }
+    }
+    match result {
+        Some((s, e)) => (s.min(start), e.max(end)),
+        None => (start, end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(text: &'a str, spans: &[(usize, usize)]) -> Vec<&'a str> {
+        spans.iter().map(|&(s, e)| &text[s..e]).collect()
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let text = "First para\nstill first.\n\n  \nSecond para.\n\nThird";
+        assert_eq!(
+            texts(text, &paragraphs(text)),
+            vec!["First para\nstill first.", "Second para.", "Third"]
+        );
+        assert!(paragraphs("  \n\n ").is_empty());
+    }
+
+    #[test]
+    fn test_sentences() {
+        let text = "Dr. Smith saw J. Doe today. Was it fine?  \"Yes!\" he said.\n\nNew para";
+        assert_eq!(
+            texts(text, &sentences(text)),
+            vec![
+                "Dr. Smith saw J. Doe today.",
+                "Was it fine?",
+                "\"Yes!\" he said.",
+                "New para"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sentences_decimals_and_unicode() {
+        let text = "Balance is 3.50 today… Merci. 你好。再见。";
+        assert_eq!(
+            texts(text, &sentences(text)),
+            vec!["Balance is 3.50 today…", "Merci.", "你好。", "再见。"]
+        );
+    }
+
+    #[test]
+    fn test_enclosing_span() {
+        let spans = vec![(0, 10), (11, 20), (21, 30)];
+        assert_eq!(enclosing_span(&spans, 12, 15), (11, 20));
+        assert_eq!(enclosing_span(&spans, 8, 13), (0, 20));
+        assert_eq!(enclosing_span(&[], 3, 5), (3, 5));
+    }
+}
@@ -0,0 +1,206 @@
+//! Structural plausibility scoring for generic serial numbers
+//!
+//! Vendor serial formats are too many to cover with one regex each, so
+//! instead of a yes/no answer this scores how much a token looks like a
+//! machine-assigned identifier: length, mixing of letters and digits,
+//! character diversity, absence of keyboard/sequence runs, and check-digit
+//! schemes where one applies (VIN position 9, Luhn). Callers pick a
+//! threshold for their data.
+
+use crate::validators;
+
+/// Prefixes of internal reference numbers that are not equipment serials
+pub const DEFAULT_EXCLUDED_PREFIXES: &[&str] = &["SKU", "UPC", "INV", "PO", "ORD", "REF", "TMP", "TEST"];
+
+/// Separators allowed inside a serial and ignored for scoring
+fn is_separator(c: char) -> bool {
+    matches!(c, '-' | ' ' | '/' | '.')
+}
+
+/// Scoring limits and exclusions
+#[derive(Clone, Debug)]
+pub struct SerialRules {
+    pub min_len: usize,
+    pub max_len: usize,
+    /// Uppercase prefixes that disqualify a value outright
+    pub excluded_prefixes: Vec<String>,
+}
+
+impl Default for SerialRules {
+    fn default() -> Self {
+        Self {
+            min_len: 6,
+            max_len: 24,
+            excluded_prefixes: DEFAULT_EXCLUDED_PREFIXES.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+/// VIN check digit (ISO 3779, position 9) for a 17-character VIN
+pub fn vin_check(vin: &str) -> bool {
+    const WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+    let bytes = vin.as_bytes();
+    if bytes.len() != 17 {
+        return false;
+    }
+    let mut sum = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        let value = match b.to_ascii_uppercase() {
+            d @ b'0'..=b'9' => (d - b'0') as u32,
+            b'A' | b'J' => 1,
+            b'B' | b'K' | b'S' => 2,
+            b'C' | b'L' | b'T' => 3,
+            b'D' | b'M' | b'U' => 4,
+            b'E' | b'N' | b'V' => 5,
+            b'F' | b'W' => 6,
+            b'G' | b'P' | b'X' => 7,
+            b'H' | b'Y' => 8,
+            b'R' | b'Z' => 9,
+            // I, O and Q are never used in VINs
+            _ => return false,
+        };
+        sum += value * WEIGHTS[i];
+    }
+    let expected = match sum % 11 {
+        10 => b'X',
+        r => b'0' + r as u8,
+    };
+    bytes[8].to_ascii_uppercase() == expected
+}
+
+/// Length of the longest run of consecutive ascending or descending characters
+fn longest_sequence(chars: &[u8]) -> usize {
+    let mut best = 1;
+    let mut run = 1;
+    let mut step = 0i16;
+    for w in chars.windows(2) {
+        let d = w[1] as i16 - w[0] as i16;
+        if d.abs() == 1 && run > 1 && d == step {
+            run += 1;
+        } else if d.abs() == 1 {
+            run = 2;
+            step = d;
+        } else {
+            run = 1;
+        }
+        best = best.max(run);
+    }
+    best
+}
+
+/// Plausibility in [0, 1] that `value` is a machine-assigned serial number
+pub fn score_serial(value: &str, rules: &SerialRules) -> f32 {
+    let value = value.trim();
+    if value.chars().any(|c| !c.is_ascii_alphanumeric() && !is_separator(c)) {
+        return 0.0;
+    }
+    let core: Vec<u8> = value
+        .bytes()
+        .filter(|b| b.is_ascii_alphanumeric())
+        .map(|b| b.to_ascii_uppercase())
+        .collect();
+    if core.len() < rules.min_len || core.len() > rules.max_len {
+        return 0.0;
+    }
+    if rules.excluded_prefixes.iter().any(|p| core.starts_with(p.as_bytes())) {
+        return 0.0;
+    }
+
+    let letters = core.iter().filter(|b| b.is_ascii_alphabetic()).count();
+    let digits = core.len() - letters;
+    // All letters is a word; one repeated character is filler
+    if digits == 0 || core.iter().all(|&b| b == core[0]) {
+        return 0.0;
+    }
+
+    let core_str = std::str::from_utf8(&core).unwrap_or_default();
+    if vin_check(core_str) {
+        return 0.95;
+    }
+
+    let mut score: f32 = if letters == 0 { 0.3 } else { 0.5 };
+
+    // Letter/digit transitions: "AB12CD34" reads as an identifier,
+    // "ABCDEF1" more like a word with a suffix
+    let transitions = core
+        .windows(2)
+        .filter(|w| w[0].is_ascii_alphabetic() != w[1].is_ascii_alphabetic())
+        .count();
+    score += match transitions {
+        0 => 0.0,
+        1 => 0.05,
+        2 | 3 => 0.15,
+        _ => 0.2,
+    };
+
+    // Low diversity ("AA1111AA") and long runs ("123456") are unlikely
+    // for assigned identifiers
+    let mut seen = [false; 36];
+    for &b in &core {
+        let idx = if b.is_ascii_digit() { b - b'0' } else { b - b'A' + 10 };
+        seen[idx as usize] = true;
+    }
+    let distinct = seen.iter().filter(|s| **s).count();
+    let diversity = distinct as f32 / core.len().min(12) as f32;
+    if diversity < 0.4 {
+        score -= 0.25;
+    } else if diversity >= 0.6 {
+        score += 0.1;
+    }
+    if longest_sequence(&core) >= 5 {
+        score -= 0.3;
+    }
+
+    if letters == 0 && core.len() >= 13 && validators::luhn(core_str) {
+        score += 0.15;
+    }
+    // Typical serial lengths
+    if (8..=16).contains(&core.len()) {
+        score += 0.1;
+    }
+
+    score.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(value: &str) -> f32 {
+        score_serial(value, &SerialRules::default())
+    }
+
+    #[test]
+    fn test_vin_check_digit() {
+        assert!(vin_check("1M8GDM9AXKP042788"));
+        assert!(!vin_check("1M8GDM9A1KP042788"));
+        assert!(!vin_check("1M8GDM9AXKP04278O")); // O is not a VIN character
+        assert_eq!(score("1M8GDM9AXKP042788"), 0.95);
+    }
+
+    #[test]
+    fn test_plausible_serials_score_high() {
+        assert!(score("SN4K7-29XQ-81B") >= 0.7);
+        assert!(score("C02XK1ZJJG5J") >= 0.7);
+        assert!(score("FX7391B204") >= 0.7);
+    }
+
+    #[test]
+    fn test_implausible_values_score_low() {
+        assert_eq!(score("INVOICE"), 0.0); // No digits
+        assert_eq!(score("AAAAAAAA"), 0.0);
+        assert_eq!(score("SKU-88213-B"), 0.0); // Excluded prefix
+        assert_eq!(score("AB12"), 0.0); // Too short
+        assert_eq!(score("AB12_CD34"), 0.0); // Unexpected character
+        assert!(score("12345678") < 0.3); // Sequential run
+        assert!(score("ABCDEFG1") < score("FX7391B204"));
+    }
+
+    #[test]
+    fn test_rules_are_tunable() {
+        let rules = SerialRules { min_len: 4, max_len: 8, excluded_prefixes: vec!["FX".to_string()] };
+        assert!(score_serial("K9P2", &rules) > 0.0);
+        assert_eq!(score_serial("FX7391B2", &rules), 0.0);
+        assert_eq!(score_serial("C02XK1ZJJG5J", &rules), 0.0);
+    }
+}
@@ -0,0 +1,221 @@
+//! Tamper-evident scan results
+//!
+//! Audit pipelines need to show that results were not edited after the
+//! scan. Findings are serialized natively, before any Python object
+//! exists, into a canonical JSON payload (sorted keys, fixed number
+//! format; see `config`) together with digests of the scanned text and of
+//! the matcher configuration, and the payload is signed with
+//! HMAC-SHA256 under a caller key. Anyone holding the key can check a
+//! stored payload with `verify`; any change to it breaks the signature.
+
+use hmac::{Hmac, Mac};
+use pyo3::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::config::{self, Value};
+use crate::finding::Finding;
+
+/// Identifies the payload layout; bump when fields change meaning
+pub const PAYLOAD_FORMAT: &str = "openlabels.signed-findings.v1";
+
+/// Signature algorithm reported alongside signatures
+pub const ALGORITHM: &str = "hmac-sha256";
+
+/// Domain separation so a signature over a payload can't be reused for other keyed hashes
+const SIGNING_CONTEXT: &[u8] = b"openlabels-signed-result\0";
+
+fn finding_value(f: &Finding) -> Value {
+    let mut t = BTreeMap::new();
+    t.insert("entity_type".to_string(), Value::Str(f.entity_type.clone()));
+    t.insert("start".to_string(), Value::Int(f.start as i64));
+    t.insert("end".to_string(), Value::Int(f.end as i64));
+    t.insert("text".to_string(), Value::Str(f.text.clone()));
+    if f.truncated {
+        t.insert("truncated".to_string(), Value::Bool(true));
+    }
+    t.insert("normalized".to_string(), Value::Str(f.normalized.clone()));
+    if let Some(repaired) = &f.repaired {
+        t.insert("repaired".to_string(), Value::Str(repaired.clone()));
+    }
+    t.insert("confidence".to_string(), Value::float(f.confidence));
+    t.insert("score".to_string(), Value::float(f.score));
+    t.insert("pattern_id".to_string(), Value::Int(f.pattern_id as i64));
+    t.insert("context_start".to_string(), Value::Int(f.context_start as i64));
+    t.insert("context_end".to_string(), Value::Int(f.context_end as i64));
+    let verdicts = f.verdicts.iter().map(|(name, &ok)| (name.clone(), Value::Bool(ok))).collect();
+    t.insert("verdicts".to_string(), Value::Table(verdicts));
+    if let Some(ocr) = f.ocr_confidence {
+        t.insert("ocr_confidence".to_string(), Value::float(ocr));
+    }
+    Value::Table(t)
+}
+
+/// Canonical JSON payload for findings of one scan
+///
+/// `config` is the matcher's exported configuration; only its digest is
+/// included, as is the digest of the scanned text.
+pub fn payload(findings: &[Finding], text: &str, config: &str, scan_id: Option<&str>) -> String {
+    let mut root = BTreeMap::new();
+    root.insert("format".to_string(), Value::Str(PAYLOAD_FORMAT.to_string()));
+    root.insert("engine_version".to_string(), Value::Str(env!("CARGO_PKG_VERSION").to_string()));
+    root.insert("text_sha256".to_string(), Value::Str(format!("{:x}", Sha256::digest(text.as_bytes()))));
+    root.insert("text_bytes".to_string(), Value::Int(text.len() as i64));
+    root.insert("config_sha256".to_string(), Value::Str(format!("{:x}", Sha256::digest(config.as_bytes()))));
+    if let Some(scan_id) = scan_id {
+        root.insert("scan_id".to_string(), Value::Str(scan_id.to_string()));
+    }
+    root.insert("findings".to_string(), Value::List(findings.iter().map(finding_value).collect()));
+    config::render(&root, config::Format::Json)
+}
+
+fn payload_mac(key: &[u8], payload: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(SIGNING_CONTEXT);
+    mac.update(payload.as_bytes());
+    mac
+}
+
+/// Bytes of a hex string (any case), or None if it is not hex
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let digit = |b: u8| (b as char).to_digit(16);
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match *pair {
+            [hi, lo] => Some((digit(hi)? * 16 + digit(lo)?) as u8),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Hex HMAC-SHA256 signature of `payload` under `key`
+pub fn sign(key: &[u8], payload: &str) -> String {
+    format!("{:x}", payload_mac(key, payload).finalize().into_bytes())
+}
+
+/// Whether `signature` (hex, any case) is `payload`'s signature under `key`
+///
+/// The comparison is constant-time.
+pub fn verify(key: &[u8], payload: &str, signature: &str) -> bool {
+    decode_hex(signature).is_some_and(|bytes| payload_mac(key, payload).verify_slice(&bytes).is_ok())
+}
+
+/// Findings with their signed canonical serialization
+#[pyclass]
+pub struct SignedResult {
+    /// The findings the payload encodes (copies; editing them does not change the payload)
+    #[pyo3(get)]
+    pub findings: Vec<Finding>,
+    /// Canonical JSON the signature covers; store this, not the findings
+    #[pyo3(get)]
+    pub payload: String,
+    /// Lowercase hex HMAC-SHA256 of the payload
+    #[pyo3(get)]
+    pub signature: String,
+}
+
+#[pymethods]
+impl SignedResult {
+    /// Always "hmac-sha256"
+    #[getter]
+    fn algorithm(&self) -> &'static str {
+        ALGORITHM
+    }
+
+    /// Check the signature against the payload under `key`
+    fn verify(&self, key: &[u8]) -> bool {
+        verify(key, &self.payload, &self.signature)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SignedResult(findings={}, signature='{}...')", self.findings.len(), &self.signature[..16])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark;
+    use crate::feedback::FeedbackStore;
+    use crate::finding::build_findings;
+    use crate::matcher::{compile_patterns, find_matches_impl, CompiledPatterns, RawMatch};
+    use crate::postprocess::PostProcessors;
+    use crate::repair::Repairs;
+    use crate::stream::Chunking;
+
+    fn finding(text: &str, start: usize) -> Finding {
+        Finding::from_raw(RawMatch {
+            pattern_id: 0,
+            start,
+            end: start + text.len(),
+            text: text.to_string(),
+            entity_type: "SSN".to_string(),
+            confidence: 0.9,
+            truncated: false,
+            context: None,
+        })
+    }
+
+    #[test]
+    fn test_payload_is_canonical() {
+        let findings = vec![finding("123-45-6789", 4)];
+        let a = payload(&findings, "SSN 123-45-6789", "{}", Some("scan-1"));
+        assert_eq!(a, payload(&findings, "SSN 123-45-6789", "{}", Some("scan-1")));
+        assert!(a.contains("\"format\": \"openlabels.signed-findings.v1\""));
+        assert!(a.contains("\"ssn_format\": true"));
+        assert_ne!(a, payload(&findings, "SSN 123-45-6789 ", "{}", Some("scan-1")));
+        assert_ne!(a, payload(&findings, "SSN 123-45-6789", "{ }", Some("scan-1")));
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = payload(&[finding("123-45-6789", 4)], "SSN 123-45-6789", "{}", None);
+        let signature = sign(b"audit-key", &body);
+        assert_eq!(signature.len(), 64);
+        assert!(verify(b"audit-key", &body, &signature));
+        assert!(verify(b"audit-key", &body, &signature.to_ascii_uppercase()));
+        assert!(!verify(b"other-key", &body, &signature));
+        assert!(!verify(b"audit-key", &body.replace("123-45-6789", "123-45-6780"), &signature));
+        assert!(!verify(b"audit-key", &body, &signature[..63]));
+    }
+
+    /// SHA-256 of the canonical findings for the embedded benchmark corpus
+    const GOLDEN_CORPUS_FINDINGS: &str = "a6962ba2f4df683d4b1d1e78dfdbc65ede1c1bc2ef646d7cfb4ed220c39e25f9";
+
+    fn corpus_findings_digest(compiled: &CompiledPatterns) -> String {
+        let text = benchmark::CORPUS;
+        let raws = find_matches_impl(compiled, text);
+        let findings = build_findings(
+            text,
+            raws,
+            Vec::new(),
+            0.0,
+            &PostProcessors::default(),
+            &Repairs::default(),
+            &FeedbackStore::default(),
+            None,
+        );
+        let findings = Value::List(findings.iter().map(finding_value).collect());
+        let root = BTreeMap::from([("findings".to_string(), findings)]);
+        format!("{:x}", Sha256::digest(config::render(&root, config::Format::Json).as_bytes()))
+    }
+
+    #[test]
+    fn test_golden_corpus_findings() {
+        // Pins ordering, scores and normalization bit for bit: every OS,
+        // architecture and scan strategy must produce this exact digest
+        let patterns: Vec<(String, String, f32, usize)> = benchmark::PATTERNS
+            .iter()
+            .map(|&(p, e, c, g)| (p.to_string(), e.to_string(), c, g))
+            .collect();
+        let single = compile_patterns(&patterns, false);
+        let two_pass = compile_patterns(&patterns, true);
+        let mut chunked = compile_patterns(&patterns, false);
+        chunked.chunking = Some(Chunking::new(1024, 256).unwrap());
+
+        let digest = corpus_findings_digest(&single);
+        assert_eq!(digest, GOLDEN_CORPUS_FINDINGS);
+        assert_eq!(corpus_findings_digest(&two_pass), digest);
+        assert_eq!(corpus_findings_digest(&chunked), digest);
+    }
+}
@@ -0,0 +1,176 @@
+//! Scan counters and the slow-pattern watchdog
+//!
+//! Counters (scans, bytes, matches) are always kept; they are a few relaxed
+//! atomic adds per scan. The watchdog is opt-in: when enabled, each
+//! pattern's pass over a text is timed and any pass slower than the
+//! threshold is recorded with the pattern and the input length, so a slow
+//! regex shows up by name instead of as unexplained fleet latency.
+
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Most recent slow events kept per matcher
+pub const MAX_SLOW_EVENTS: usize = 256;
+
+/// One pattern pass that exceeded the watchdog threshold
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SlowPattern {
+    #[pyo3(get)]
+    pub pattern_id: usize,
+    #[pyo3(get)]
+    pub entity_type: String,
+    /// Time spent in this pattern's pass
+    #[pyo3(get)]
+    pub elapsed_ms: f64,
+    /// Length in bytes of the text being scanned
+    #[pyo3(get)]
+    pub input_len: usize,
+}
+
+#[pymethods]
+impl SlowPattern {
+    fn __repr__(&self) -> String {
+        format!(
+            "SlowPattern(pattern_id={}, type={}, elapsed_ms={:.2}, input_len={})",
+            self.pattern_id, self.entity_type, self.elapsed_ms, self.input_len
+        )
+    }
+}
+
+/// Per-pattern timing with a slow-pass threshold
+pub struct Watchdog {
+    threshold: Duration,
+    events: Mutex<VecDeque<SlowPattern>>,
+    /// Slow passes seen, including ones evicted from `events`
+    slow_total: AtomicU64,
+}
+
+impl Watchdog {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            events: Mutex::new(VecDeque::new()),
+            slow_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Time `pass` and record it if it ran longer than the threshold
+    pub fn time<T>(&self, pattern_id: usize, entity_type: &str, input_len: usize, pass: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = pass();
+        let elapsed = start.elapsed();
+        if elapsed > self.threshold {
+            self.slow_total.fetch_add(1, Ordering::Relaxed);
+            let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+            if events.len() == MAX_SLOW_EVENTS {
+                events.pop_front();
+            }
+            events.push_back(SlowPattern {
+                pattern_id,
+                entity_type: entity_type.to_string(),
+                elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+                input_len,
+            });
+        }
+        out
+    }
+
+    pub fn threshold_ms(&self) -> f64 {
+        self.threshold.as_secs_f64() * 1000.0
+    }
+
+    /// Recorded slow passes, oldest first
+    pub fn events(&self) -> Vec<SlowPattern> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+
+    fn reset(&self) {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.slow_total.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Cumulative counters for one matcher
+#[derive(Default)]
+pub struct ScanStats {
+    scans: AtomicU64,
+    bytes_scanned: AtomicU64,
+    matches: AtomicU64,
+    pub watchdog: Option<Watchdog>,
+}
+
+impl ScanStats {
+    pub fn with_watchdog(watchdog: Option<Watchdog>) -> Self {
+        Self { watchdog, ..Default::default() }
+    }
+
+    pub fn record_scan(&self, input_len: usize, matches: usize) {
+        self.scans.fetch_add(1, Ordering::Relaxed);
+        self.bytes_scanned.fetch_add(input_len as u64, Ordering::Relaxed);
+        self.matches.fetch_add(matches as u64, Ordering::Relaxed);
+    }
+
+    /// Counter snapshot: scans, bytes_scanned, matches, slow_events
+    pub fn snapshot(&self) -> BTreeMap<&'static str, u64> {
+        let mut out = BTreeMap::new();
+        out.insert("scans", self.scans.load(Ordering::Relaxed));
+        out.insert("bytes_scanned", self.bytes_scanned.load(Ordering::Relaxed));
+        out.insert("matches", self.matches.load(Ordering::Relaxed));
+        let slow = self.watchdog.as_ref().map_or(0, |w| w.slow_total.load(Ordering::Relaxed));
+        out.insert("slow_events", slow);
+        out
+    }
+
+    pub fn reset(&self) {
+        self.scans.store(0, Ordering::Relaxed);
+        self.bytes_scanned.store(0, Ordering::Relaxed);
+        self.matches.store(0, Ordering::Relaxed);
+        if let Some(w) = &self.watchdog {
+            w.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_records_only_slow_passes() {
+        let stats = ScanStats::with_watchdog(Some(Watchdog::new(Duration::from_millis(5))));
+        let w = stats.watchdog.as_ref().unwrap();
+        assert_eq!(w.time(0, "FAST", 10, || 1), 1);
+        w.time(3, "SLOW", 2048, || std::thread::sleep(Duration::from_millis(10)));
+
+        let events = w.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].pattern_id, events[0].input_len), (3, 2048));
+        assert!(events[0].elapsed_ms >= 5.0);
+        assert_eq!(stats.snapshot()["slow_events"], 1);
+    }
+
+    #[test]
+    fn test_counters_and_event_cap() {
+        let stats = ScanStats::with_watchdog(Some(Watchdog::new(Duration::ZERO)));
+        stats.record_scan(100, 3);
+        stats.record_scan(50, 0);
+        let w = stats.watchdog.as_ref().unwrap();
+        for i in 0..MAX_SLOW_EVENTS + 10 {
+            w.time(i, "X", 1, || std::thread::sleep(Duration::from_micros(1)));
+        }
+        assert_eq!(w.events().len(), MAX_SLOW_EVENTS);
+        assert_eq!(w.events()[0].pattern_id, 10);
+
+        let snap = stats.snapshot();
+        assert_eq!((snap["scans"], snap["bytes_scanned"], snap["matches"]), (2, 150, 3));
+        assert_eq!(snap["slow_events"], (MAX_SLOW_EVENTS + 10) as u64);
+
+        stats.reset();
+        assert_eq!(stats.snapshot()["scans"], 0);
+        assert!(w.events().is_empty());
+    }
+}
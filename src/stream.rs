@@ -0,0 +1,376 @@
+//! Chunked streaming scans with overlap deduplication
+//!
+//! Large objects (e.g. S3 multipart uploads) are scanned as chunks that
+//! overlap their neighbours by `overlap` bytes so no match is lost at a
+//! boundary. Without coordination every match inside an overlap is
+//! reported twice. `StreamScanner` converts chunk-relative offsets to
+//! absolute ones and reports each (pattern, span) once, in whatever order
+//! chunks arrive:
+//!
+//! - only matches inside an overlap region can be duplicates, so only
+//!   those are remembered, which keeps the state small
+//! - while chunks arrive in order from offset 0, a match ending before the
+//!   current chunk cannot repeat and is forgotten, so long streams stay
+//!   bounded; once a chunk arrives out of order everything is kept
+//! - a match touching a chunk edge inside the overlap may be cut off by
+//!   the edge; the neighbouring chunk sees it whole, so it is left to that
+//!   chunk (this requires `overlap` >= the longest expected match)
+//! - a repeated chunk id (a retried delivery) reports nothing
+//!
+//! `Chunking` applies the same scheme inside a single scan: a matcher
+//! given a text above its threshold scans it as overlapping chunks, so the
+//! per-scan structures (regions, two-pass candidates) stay chunk-sized
+//! however large the input is. There the whole text is at hand, so a match
+//! cut by a chunk end is re-run on it and may be longer than the overlap.
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::matcher::{find_matches_impl, CompiledPatterns, PatternMatcher, RawMatch};
+
+/// Inputs above this many bytes are scanned in chunks by default (64 MiB)
+pub const DEFAULT_CHUNK_THRESHOLD: usize = 67_108_864;
+
+/// Default bytes shared by adjacent automatic chunks
+pub const DEFAULT_CHUNK_OVERLAP: usize = 4096;
+
+/// Automatic chunking of oversized inputs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chunking {
+    /// Largest text scanned in one piece; also the chunk size
+    pub threshold: usize,
+    /// Bytes adjacent chunks share (at least the longest expected match)
+    pub overlap: usize,
+}
+
+impl Chunking {
+    pub fn new(threshold: usize, overlap: usize) -> Result<Self, String> {
+        if overlap.saturating_mul(2) > threshold {
+            return Err(format!(
+                "chunk_overlap ({}) must be at most half of chunk_threshold ({})",
+                overlap, threshold
+            ));
+        }
+        Ok(Self { threshold, overlap })
+    }
+
+    /// (start, end) of the chunks covering `text`, on character boundaries
+    ///
+    /// Chunks are at most `threshold` bytes (or one character, if longer)
+    /// and overlap their successor by at most `overlap` bytes.
+    pub fn bounds(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut bounds = Vec::new();
+        let mut start = 0;
+        loop {
+            let mut end = floor_char_boundary(text, start + self.threshold);
+            if end <= start {
+                end = ceil_char_boundary(text, start + 1);
+            }
+            bounds.push((start, end));
+            if end == text.len() {
+                return bounds;
+            }
+            let next = ceil_char_boundary(text, end - self.overlap.min(end - start - 1));
+            start = if next > start { next } else { end };
+        }
+    }
+}
+
+fn floor_char_boundary(text: &str, mut pos: usize) -> usize {
+    if pos >= text.len() {
+        return text.len();
+    }
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+fn ceil_char_boundary(text: &str, mut pos: usize) -> usize {
+    while pos < text.len() && !text.is_char_boundary(pos) {
+        pos += 1;
+    }
+    pos.min(text.len())
+}
+
+/// Deduplication state for one stream
+#[derive(Default)]
+pub struct OverlapDedup {
+    overlap: usize,
+    /// (pattern_id, start, end) of reported matches inside overlap regions
+    reported: HashSet<(usize, usize, usize)>,
+    seen_chunks: HashSet<String>,
+    /// Whether every chunk so far continued the previous one, from offset 0
+    in_order: bool,
+    /// (offset, end) of the last chunk of that in-order run
+    frontier: Option<(usize, usize)>,
+}
+
+impl OverlapDedup {
+    pub fn new(overlap: usize) -> Self {
+        Self { overlap, in_order: true, ..Default::default() }
+    }
+
+    /// Filter one chunk's matches (chunk-relative offsets) to new,
+    /// absolute-offset matches
+    pub fn admit(
+        &mut self,
+        matches: Vec<RawMatch>,
+        offset: usize,
+        chunk_len: usize,
+        chunk_id: Option<&str>,
+        last: bool,
+    ) -> Vec<RawMatch> {
+        if let Some(id) = chunk_id {
+            if !self.seen_chunks.insert(id.to_string()) {
+                return Vec::new();
+            }
+        }
+        self.in_order &= match self.frontier {
+            None => offset == 0,
+            Some((prev_offset, prev_end)) => offset > prev_offset && offset < prev_end,
+        };
+        if self.in_order {
+            // Every earlier chunk has been scanned: nothing ending here can repeat
+            self.frontier = Some((offset, offset + chunk_len));
+            self.reported.retain(|&(_, _, end)| end > offset);
+        }
+        let head_end = if offset > 0 { self.overlap } else { 0 };
+        let tail_start = if last { chunk_len } else { chunk_len.saturating_sub(self.overlap) };
+
+        let mut admitted = Vec::with_capacity(matches.len());
+        for mut m in matches {
+            let in_head = m.start < head_end;
+            let in_tail = m.end > tail_start;
+            // Possibly truncated by the chunk edge: the neighbour has it whole
+            if (in_head && m.start == 0) || (in_tail && m.end == chunk_len) {
+                continue;
+            }
+            m.start += offset;
+            m.end += offset;
+            if (in_head || in_tail) && !self.reported.insert((m.pattern_id, m.start, m.end)) {
+                continue;
+            }
+            admitted.push(m);
+        }
+        admitted
+    }
+
+    /// Admit a match found whole outside any chunk (absolute offsets),
+    /// unless a chunk already reported it
+    pub fn admit_whole(&mut self, m: RawMatch) -> Option<RawMatch> {
+        self.reported.insert((m.pattern_id, m.start, m.end)).then_some(m)
+    }
+}
+
+/// Scanner for a stream of overlapping chunks of one object
+#[pyclass]
+pub struct StreamScanner {
+    compiled: Arc<CompiledPatterns>,
+    dedup: OverlapDedup,
+    /// Bytes each chunk shares with its neighbour
+    #[pyo3(get)]
+    overlap: usize,
+}
+
+#[pymethods]
+impl StreamScanner {
+    /// Create a stream scanner using a matcher's patterns
+    ///
+    /// Args:
+    ///     matcher: PatternMatcher whose compiled patterns are shared
+    ///     overlap: Bytes adjacent chunks share; must be at least the
+    ///         longest match you expect
+    #[new]
+    #[pyo3(signature = (matcher, overlap=256))]
+    fn new(matcher: PyRef<PatternMatcher>, overlap: usize) -> Self {
+        Self {
+            compiled: matcher.compiled(),
+            dedup: OverlapDedup::new(overlap),
+            overlap,
+        }
+    }
+
+    /// Scan one chunk and return matches not already reported
+    ///
+    /// Releases the GIL while scanning.
+    ///
+    /// Args:
+    ///     text: Chunk text
+    ///     offset: Byte offset of the chunk within the whole object
+    ///     chunk_id: Optional identity (e.g. part number); a chunk id seen
+    ///         before returns no matches, so retried deliveries are safe
+    ///     last: True for the final chunk (its end is not an overlap)
+    ///
+    /// Returns:
+    ///     List of RawMatch with offsets into the whole object
+    #[pyo3(signature = (text, offset, chunk_id=None, last=false))]
+    fn scan_chunk(
+        &mut self,
+        py: Python<'_>,
+        text: &str,
+        offset: usize,
+        chunk_id: Option<String>,
+        last: bool,
+    ) -> PyResult<Vec<RawMatch>> {
+        if offset > 0 && text.len() <= self.overlap {
+            return Err(PyValueError::new_err(format!(
+                "chunk of {} bytes is not longer than the {}-byte overlap",
+                text.len(),
+                self.overlap
+            )));
+        }
+        let compiled = &self.compiled;
+        let dedup = &mut self.dedup;
+        Ok(py.allow_threads(|| {
+            let matches = find_matches_impl(compiled, text);
+            dedup.admit(matches, offset, text.len(), chunk_id.as_deref(), last)
+        }))
+    }
+
+    /// Number of matches remembered for deduplication
+    #[getter]
+    fn tracked_matches(&self) -> usize {
+        self.dedup.reported.len()
+    }
+
+    /// Number of distinct chunk ids seen
+    #[getter]
+    fn seen_chunks(&self) -> usize {
+        self.dedup.seen_chunks.len()
+    }
+
+    /// Forget all state, ready for a new object
+    fn reset(&mut self) {
+        self.dedup = OverlapDedup::new(self.overlap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::compile_patterns;
+
+    /// Split `text` into chunks of `size` bytes overlapping by `overlap`
+    fn chunks(text: &str, size: usize, overlap: usize) -> Vec<(usize, &str, bool)> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        loop {
+            let end = (start + size).min(text.len());
+            out.push((start, &text[start..end], end == text.len()));
+            if end == text.len() {
+                break;
+            }
+            start = end - overlap;
+        }
+        out
+    }
+
+    fn scan_stream(text: &str, size: usize, overlap: usize, reverse: bool) -> Vec<(usize, usize)> {
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0)];
+        let compiled = compile_patterns(&patterns, false);
+        let mut dedup = OverlapDedup::new(overlap);
+        let mut parts = chunks(text, size, overlap);
+        if reverse {
+            parts.reverse();
+        }
+        let mut spans: Vec<(usize, usize)> = parts
+            .into_iter()
+            .flat_map(|(offset, chunk, last)| {
+                let matches = find_matches_impl(&compiled, chunk);
+                dedup.admit(matches, offset, chunk.len(), None, last)
+            })
+            .map(|m| (m.start, m.end))
+            .collect();
+        spans.sort();
+        spans
+    }
+
+    #[test]
+    fn test_overlap_matches_reported_once() {
+        let text = "ssn 123-45-6789 then 234-56-7890 and 345-67-8901 end";
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0)];
+        let whole: Vec<(usize, usize)> = find_matches_impl(&compile_patterns(&patterns, false), text)
+            .iter()
+            .map(|m| (m.start, m.end))
+            .collect();
+        assert_eq!(whole.len(), 3);
+
+        for size in [20, 24, 30] {
+            assert_eq!(scan_stream(text, size, 12, false), whole, "size {}", size);
+            assert_eq!(scan_stream(text, size, 12, true), whole, "reversed, size {}", size);
+        }
+    }
+
+    #[test]
+    fn test_chunk_bounds() {
+        let chunking = Chunking::new(10, 4).unwrap();
+        assert_eq!(chunking.bounds("0123456789abcdefghijkl"), [(0, 10), (6, 16), (12, 22)]);
+        assert_eq!(chunking.bounds("short"), [(0, 5)]);
+
+        // Multi-byte characters are never split
+        let text = "ééééééééééé";
+        for (start, end) in chunking.bounds(text) {
+            assert!(text.is_char_boundary(start) && text.is_char_boundary(end));
+            assert!(end - start <= 10);
+        }
+        assert!(Chunking::new(10, 6).is_err());
+    }
+
+    #[test]
+    fn test_repeated_chunk_id_reports_nothing() {
+        let m = RawMatch {
+            pattern_id: 0,
+            start: 2,
+            end: 5,
+            text: "abc".to_string(),
+            entity_type: "X".to_string(),
+            confidence: 0.5,
+            truncated: false,
+            context: None,
+        };
+        let mut dedup = OverlapDedup::new(4);
+        assert_eq!(dedup.admit(vec![m.clone()], 100, 50, Some("part-2"), false).len(), 1);
+        assert!(dedup.admit(vec![m], 100, 50, Some("part-2"), false).is_empty());
+    }
+
+    #[test]
+    fn test_in_order_stream_forgets_passed_matches() {
+        let text = "ssn 123-45-6789 x ".repeat(200);
+        let patterns = vec![(r"\d{3}-\d{2}-\d{4}".to_string(), "SSN".to_string(), 0.9, 0)];
+        let compiled = compile_patterns(&patterns, false);
+        let parts = chunks(&text, 40, 20);
+
+        let mut dedup = OverlapDedup::new(20);
+        let mut spans = Vec::new();
+        for &(offset, chunk, last) in &parts {
+            let matches = find_matches_impl(&compiled, chunk);
+            spans.extend(dedup.admit(matches, offset, chunk.len(), None, last).iter().map(|m| m.start));
+            assert!(dedup.reported.len() <= 4, "offset {}", offset);
+        }
+        assert_eq!(spans.len(), 200);
+        spans.dedup();
+        assert_eq!(spans.len(), 200);
+
+        // Out of order, everything is remembered and still reported once
+        let mut dedup = OverlapDedup::new(20);
+        let mut shuffled: Vec<_> = parts.iter().step_by(2).chain(parts.iter().skip(1).step_by(2)).collect();
+        shuffled.swap(0, 1);
+        let mut spans: Vec<usize> = shuffled
+            .into_iter()
+            .flat_map(|&(offset, chunk, last)| {
+                let matches = find_matches_impl(&compiled, chunk);
+                dedup.admit(matches, offset, chunk.len(), None, last)
+            })
+            .map(|m| m.start)
+            .collect();
+        spans.sort();
+        spans.dedup();
+        assert_eq!(spans.len(), 200);
+    }
+}
@@ -0,0 +1,157 @@
+//! Per-entity counts with a few example values
+//!
+//! Compliance reports need how many values of each entity type a document
+//! holds and a handful of examples, not every match. Summarizing natively
+//! avoids building a Python object per finding and keeps the full match
+//! list out of the report.
+//!
+//! Examples are the distinct values with the lowest feedback hash (a
+//! bottom-k sample): the same document always yields the same examples,
+//! independent of match order or thread count, and a value that appears
+//! as an example in one document is likely to in others too.
+
+use pyo3::prelude::*;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use crate::feedback;
+use crate::finding::Finding;
+
+/// Counts and example values for one entity type
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct EntitySummary {
+    #[pyo3(get)]
+    pub entity_type: String,
+    /// Number of findings
+    #[pyo3(get)]
+    pub count: usize,
+    /// Number of distinct normalized values
+    #[pyo3(get)]
+    pub distinct: usize,
+    /// Up to max_examples matched texts, one per distinct value
+    #[pyo3(get)]
+    pub examples: Vec<String>,
+    /// Highest score among the findings
+    #[pyo3(get)]
+    pub max_score: f32,
+}
+
+#[pymethods]
+impl EntitySummary {
+    fn __repr__(&self) -> String {
+        format!(
+            "EntitySummary(type={}, count={}, distinct={}, examples={})",
+            self.entity_type,
+            self.count,
+            self.distinct,
+            self.examples.len()
+        )
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    count: usize,
+    max_score: f32,
+    /// normalized value -> (feedback hash, text of the first occurrence)
+    values: BTreeMap<String, (String, String)>,
+}
+
+/// Summarize findings per entity type, ordered by entity type
+///
+/// Each example is the matched text of the first occurrence (in text
+/// order) of one of the `max_examples` sampled values, listed in sample
+/// order.
+pub fn summarize(mut findings: Vec<Finding>, max_examples: usize) -> Vec<EntitySummary> {
+    findings.sort_by_key(|f| (f.start, f.end));
+    let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+    for f in findings {
+        let tally = tallies.entry(f.entity_type.clone()).or_default();
+        tally.count += 1;
+        tally.max_score = tally.max_score.max(f.score);
+        if let Entry::Vacant(slot) = tally.values.entry(f.normalized) {
+            let hash = feedback::feedback_hash(&f.entity_type, slot.key());
+            slot.insert((hash, f.text));
+        }
+    }
+    tallies
+        .into_iter()
+        .map(|(entity_type, tally)| {
+            let distinct = tally.values.len();
+            let mut sampled: Vec<(String, String)> = tally.values.into_values().collect();
+            sampled.sort_unstable();
+            sampled.truncate(max_examples);
+            EntitySummary {
+                entity_type,
+                count: tally.count,
+                distinct,
+                examples: sampled.into_iter().map(|(_, text)| text).collect(),
+                max_score: tally.max_score,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feedback::FeedbackStore;
+    use crate::finding::build_findings;
+    use crate::matcher::RawMatch;
+    use crate::postprocess::PostProcessors;
+    use crate::repair::Repairs;
+
+    fn findings(text: &str, values: &[(&str, &str)]) -> Vec<Finding> {
+        let raws = values
+            .iter()
+            .map(|&(entity_type, needle)| {
+                let start = text.find(needle).unwrap();
+                RawMatch {
+                    pattern_id: 0,
+                    start,
+                    end: start + needle.len(),
+                    text: needle.to_string(),
+                    entity_type: entity_type.to_string(),
+                    confidence: 0.9,
+                    truncated: false,
+                    context: None,
+                }
+            })
+            .collect();
+        let (post, repairs, feedback) = (PostProcessors::default(), Repairs::default(), FeedbackStore::default());
+        build_findings(text, raws, Vec::new(), 0.0, &post, &repairs, &feedback, None)
+    }
+
+    #[test]
+    fn test_summarize_counts_and_caps_examples() {
+        let text = "a@x.io 123-45-6789 b@x.io 123 45 6789 c@x.io 456-78-9012 A@x.io";
+        let values = [
+            ("EMAIL", "a@x.io"),
+            ("SSN", "123-45-6789"),
+            ("EMAIL", "b@x.io"),
+            ("SSN", "123 45 6789"),
+            ("EMAIL", "c@x.io"),
+            ("SSN", "456-78-9012"),
+            ("EMAIL", "A@x.io"),
+        ];
+        let summary = summarize(findings(text, &values), 2);
+        let shape: Vec<(&str, usize, usize, usize)> =
+            summary.iter().map(|s| (s.entity_type.as_str(), s.count, s.distinct, s.examples.len())).collect();
+        assert_eq!(shape, [("EMAIL", 4, 3, 2), ("SSN", 3, 2, 2)]);
+        // One example per value, taken from its first occurrence
+        assert!(summary[1].examples.contains(&"123-45-6789".to_string()));
+        assert!(!summary[0].examples.contains(&"A@x.io".to_string()));
+    }
+
+    #[test]
+    fn test_summarize_examples_independent_of_order() {
+        let text = "v1@x.io v2@x.io v3@x.io v4@x.io v5@x.io";
+        let mut values: Vec<(&str, &str)> = text.split(' ').map(|v| ("EMAIL", v)).collect();
+        let forward = summarize(findings(text, &values), 2);
+        values.reverse();
+        let backward = summarize(findings(text, &values), 2);
+        assert_eq!(forward[0].examples, backward[0].examples);
+        assert!(summarize(findings(text, &values), 0)[0].examples.is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+//! Incremental scanning of growing log files
+//!
+//! A log-tailing agent calls the scanner every time a file grows. Only the
+//! appended bytes are scanned, together with a short carry-over from the
+//! previous call, so matches that straddle the append boundary are still
+//! found whole:
+//!
+//! - the carry is the last `overlap` bytes already seen, moved back to
+//!   just after a whitespace byte when one is near, so the window does not
+//!   start in the middle of a token; when it still does, matches starting
+//!   exactly at the carry are partial values and are dropped
+//! - a match ending exactly at the end of the data so far may continue in
+//!   the next append, so it is held back until more data arrives (or the
+//!   caller flushes); only matches inside the carry are held, so the carry
+//!   never grows past it and longer ones are reported as they stand
+//! - matches reported from the carry region are remembered until the carry
+//!   moves past them, so nothing is reported twice
+//! - bytes of an incomplete UTF-8 sequence at the end wait for the rest
+//!
+//! Offsets are absolute byte offsets into the file. A file that shrinks
+//! (truncated or rotated in place) or, on Unix, is replaced by a new inode
+//! starts over from offset 0.
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use crate::bytescan::DecodedBytes;
+use crate::matcher::{find_matches_impl, CompiledPatterns, PatternMatcher, RawMatch};
+
+/// Default bytes of already-scanned text rescanned with each append
+pub const DEFAULT_TAIL_OVERLAP: usize = 4096;
+
+/// Bytes read from a file per scan step, bounding memory for large appends
+const READ_BLOCK: usize = 1 << 20;
+
+/// Scan state of one file
+#[derive(Default)]
+struct TailState {
+    /// File bytes consumed so far
+    offset: u64,
+    /// File offset of buffer[0]
+    buffer_start: u64,
+    /// Carry-over text plus any incomplete UTF-8 sequence at the end
+    buffer: Vec<u8>,
+    /// (pattern_id, start, end) of reported matches that may reappear in the carry
+    reported: HashSet<(usize, u64, u64)>,
+    /// (device, inode) the offset refers to
+    identity: Option<(u64, u64)>,
+    /// Whether buffer[0] is inside a token cut by the carry
+    mid_token: bool,
+}
+
+/// Length of `data` without a trailing incomplete UTF-8 sequence
+fn complete_len(data: &[u8]) -> usize {
+    for i in (data.len().saturating_sub(3)..data.len()).rev() {
+        let need = match data[i] {
+            0x80..=0xBF => continue,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1,
+        };
+        return if data.len() - i < need { i } else { data.len() };
+    }
+    data.len()
+}
+
+/// Start of the carry kept from `data`: about `overlap` bytes from the end,
+/// after a whitespace byte if one is within another `overlap` bytes
+fn carry_start(data: &[u8], overlap: usize) -> usize {
+    let mut pos = data.len().saturating_sub(overlap);
+    while pos > 0 && pos < data.len() && data[pos] & 0xC0 == 0x80 {
+        pos -= 1;
+    }
+    let floor = pos.saturating_sub(overlap);
+    data[floor..pos].iter().rposition(|b| b.is_ascii_whitespace()).map_or(pos, |i| floor + i + 1)
+}
+
+/// Whether `pos` in `data` lies between tokens: next to an ASCII byte
+/// that is not a word character (non-ASCII bytes count as word bytes)
+fn token_boundary(data: &[u8], pos: usize) -> bool {
+    let word = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_' || !b.is_ascii();
+    pos == 0 || !word(&data[pos - 1]) || !data.get(pos).is_some_and(word)
+}
+
+impl TailState {
+    /// Scan the appended `data`; `flush` also reports matches at the end of the data
+    fn feed(&mut self, compiled: &CompiledPatterns, data: &[u8], overlap: usize, flush: bool) -> Vec<RawMatch> {
+        self.offset += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+        let end = if flush { self.buffer.len() } else { complete_len(&self.buffer) };
+        let window = &self.buffer[..end];
+
+        let mut matches = match std::str::from_utf8(window) {
+            Ok(text) => find_matches_impl(compiled, text),
+            Err(_) => {
+                let decoded = DecodedBytes::decode(window);
+                let mut matches = find_matches_impl(compiled, &decoded.text);
+                for m in matches.iter_mut() {
+                    m.start = decoded.to_original(m.start);
+                    m.end = decoded.to_original(m.end);
+                }
+                matches
+            }
+        };
+
+        let keep_from = carry_start(window, overlap);
+        let carry_mid_token = if keep_from == 0 { self.mid_token } else { !token_boundary(&self.buffer, keep_from) };
+        let mut admitted = Vec::with_capacity(matches.len());
+        for mut m in matches.drain(..) {
+            // A partial value: the previous window saw the whole token
+            if self.mid_token && m.start == 0 {
+                continue;
+            }
+            if m.end == end && !flush && (m.start > keep_from || (m.start == keep_from && !carry_mid_token)) {
+                // May continue in the next append; the carry holds it, so rescan it then
+                continue;
+            }
+            let key = (m.pattern_id, self.buffer_start + m.start as u64, self.buffer_start + m.end as u64);
+            if !self.reported.insert(key) {
+                continue;
+            }
+            m.start += self.buffer_start as usize;
+            m.end += self.buffer_start as usize;
+            admitted.push(m);
+        }
+
+        self.buffer.drain(..keep_from);
+        self.buffer_start += keep_from as u64;
+        self.mid_token = carry_mid_token;
+        let carry_from = self.buffer_start;
+        self.reported.retain(|&(_, _, end)| end > carry_from);
+        admitted
+    }
+}
+
+/// Incremental scanner for any number of growing files
+///
+/// Not thread-safe across calls for the same file; give each tailing
+/// thread its own scanner or serialize calls.
+#[pyclass]
+pub struct TailScanner {
+    compiled: Arc<CompiledPatterns>,
+    files: HashMap<String, TailState>,
+    /// Bytes of already-scanned text rescanned with each append
+    #[pyo3(get)]
+    overlap: usize,
+}
+
+#[cfg(unix)]
+fn file_identity(meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+impl TailScanner {
+    /// Read and scan everything appended to `path` since the last call
+    fn scan_path(&mut self, path: &str) -> std::io::Result<Vec<RawMatch>> {
+        let mut file = File::open(path)?;
+        let meta = file.metadata()?;
+        let identity = file_identity(&meta);
+        let state = self.files.entry(path.to_string()).or_default();
+        if meta.len() < state.offset || (state.identity.is_some() && state.identity != identity) {
+            *state = TailState::default();
+        }
+        state.identity = identity;
+
+        file.seek(SeekFrom::Start(state.offset))?;
+        let mut found = Vec::new();
+        let mut block = vec![0u8; READ_BLOCK];
+        loop {
+            let n = file.read(&mut block)?;
+            if n == 0 {
+                break;
+            }
+            found.extend(state.feed(&self.compiled, &block[..n], self.overlap, false));
+        }
+        Ok(found)
+    }
+}
+
+#[pymethods]
+impl TailScanner {
+    /// Create a tail scanner using a matcher's patterns
+    ///
+    /// Args:
+    ///     matcher: PatternMatcher whose compiled patterns are shared
+    ///     overlap: Bytes of already-scanned text rescanned with each
+    ///         append; must be at least the longest match you expect
+    ///
+    /// Raises:
+    ///     ValueError: If overlap is 0
+    #[new]
+    #[pyo3(signature = (matcher, overlap=DEFAULT_TAIL_OVERLAP))]
+    fn new(matcher: PyRef<PatternMatcher>, overlap: usize) -> PyResult<Self> {
+        if overlap == 0 {
+            return Err(PyValueError::new_err("overlap must be at least 1 byte"));
+        }
+        Ok(Self { compiled: matcher.compiled(), files: HashMap::new(), overlap })
+    }
+
+    /// Scan the bytes appended to a file since the previous call
+    ///
+    /// The first call for a path scans the whole file. Releases the GIL
+    /// while reading and scanning.
+    ///
+    /// Args:
+    ///     path: File to read; also the key its state is kept under
+    ///
+    /// Returns:
+    ///     List of RawMatch with byte offsets into the file
+    ///
+    /// Raises:
+    ///     OSError: If the file cannot be opened or read
+    fn scan_file(&mut self, py: Python<'_>, path: &str) -> PyResult<Vec<RawMatch>> {
+        Ok(py.allow_threads(|| self.scan_path(path))?)
+    }
+
+    /// Scan bytes the caller read from a stream
+    ///
+    /// For sources the caller reads itself (sockets, journald, object
+    /// storage); `data` must directly follow the data fed before under
+    /// the same key. Releases the GIL while scanning.
+    ///
+    /// Args:
+    ///     key: Stream identity (e.g. a path)
+    ///     data: Newly appended bytes
+    ///
+    /// Returns:
+    ///     List of RawMatch with byte offsets into the stream
+    fn feed(&mut self, py: Python<'_>, key: &str, data: &[u8]) -> Vec<RawMatch> {
+        let compiled = &self.compiled;
+        let overlap = self.overlap;
+        let state = self.files.entry(key.to_string()).or_default();
+        py.allow_threads(|| state.feed(compiled, data, overlap, false))
+    }
+
+    /// Report matches held back because they touch the end of the data
+    ///
+    /// Call when a file is closed or rotated away. Later appends are still
+    /// scanned; a held-back match that turns out to continue is reported
+    /// again with its longer span.
+    ///
+    /// Returns:
+    ///     List of RawMatch (empty for an unknown key)
+    fn flush(&mut self, py: Python<'_>, key: &str) -> Vec<RawMatch> {
+        let compiled = &self.compiled;
+        let overlap = self.overlap;
+        match self.files.get_mut(key) {
+            Some(state) => py.allow_threads(|| state.feed(compiled, &[], overlap, true)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Bytes consumed so far for a key (0 if unknown)
+    fn offset(&self, key: &str) -> u64 {
+        self.files.get(key).map_or(0, |s| s.offset)
+    }
+
+    /// Drop the state for a key; its next scan starts from the beginning
+    fn forget(&mut self, key: &str) -> bool {
+        self.files.remove(key).is_some()
+    }
+
+    /// Keys with state, sorted
+    #[getter]
+    fn files(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.files.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TailScanner(files={}, overlap={})", self.files.len(), self.overlap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::compile_patterns;
+
+    fn compiled() -> CompiledPatterns {
+        let patterns = vec![
+            (r"\b\d{3}-\d{2}-\d{4}\b".to_string(), "SSN".to_string(), 0.9, 0),
+            (r"[a-z]+@[a-z]+\.com".to_string(), "EMAIL".to_string(), 0.8, 0),
+        ];
+        compile_patterns(&patterns, false)
+    }
+
+    /// Feed `text` in pieces split at `cuts`, then flush
+    fn tail(text: &[u8], cuts: &[usize], overlap: usize) -> Vec<(usize, usize)> {
+        let compiled = compiled();
+        let mut state = TailState::default();
+        let mut found = Vec::new();
+        let mut prev = 0;
+        for &cut in cuts.iter().chain([text.len()].iter()) {
+            found.extend(state.feed(&compiled, &text[prev..cut], overlap, false));
+            prev = cut;
+        }
+        found.extend(state.feed(&compiled, &[], overlap, true));
+        let mut spans: Vec<(usize, usize)> = found.iter().map(|m| (m.start, m.end)).collect();
+        spans.sort();
+        spans
+    }
+
+    #[test]
+    fn test_matches_across_append_boundaries_reported_once() {
+        let text = "login ann@ex.com ssn 123-45-6789\nretry ann@ex.com ssn 123-45-6789\n".repeat(3);
+        let whole = tail(text.as_bytes(), &[], 64);
+        assert_eq!(whole.len(), 12);
+        for cuts in [vec![10, 11, 30], vec![1, 2, 3, 4, 5, 100, 101], (1..text.len()).step_by(7).collect()] {
+            assert_eq!(tail(text.as_bytes(), &cuts, 64), whole, "cuts {:?}", cuts);
+        }
+    }
+
+    #[test]
+    fn test_match_at_end_waits_for_more_data() {
+        let compiled = compiled();
+        let mut state = TailState::default();
+        // "123-45-678" could still become an SSN, and "ann@ex.com" a longer address
+        assert!(state.feed(&compiled, b"id 123-45-678", 32, false).is_empty());
+        assert!(state.feed(&compiled, b"9 ann@ex.com", 32, false).iter().any(|m| m.text == "123-45-6789"));
+        let flushed = state.feed(&compiled, &[], 32, true);
+        assert_eq!(flushed.iter().map(|m| (m.start, m.end)).collect::<Vec<_>>(), [(15, 25)]);
+        assert_eq!(state.offset, 25);
+    }
+
+    #[test]
+    fn test_carry_inside_token_drops_partial_values() {
+        let text = b"to:annabelle@ex.com;cc:bob@ex.com;";
+        // No whitespace: the carry starts inside "annabelle"
+        assert_eq!(tail(text, &[20], 14), [(3, 19), (23, 33)]);
+        assert_eq!(tail(text, &[20, 21, 22], 14), [(3, 19), (23, 33)]);
+    }
+
+    #[test]
+    fn test_held_back_match_does_not_grow_carry() {
+        let patterns = vec![(r"key=[a-z]+".to_string(), "SECRET".to_string(), 0.9, 0)];
+        let compiled = compile_patterns(&patterns, false);
+        let mut state = TailState::default();
+        let mut found = state.feed(&compiled, b"key=", 16, false);
+        for _ in 0..50 {
+            found.extend(state.feed(&compiled, b"aaaaaaaaaa", 16, false));
+            assert!(state.buffer.len() <= 32, "carry grew to {}", state.buffer.len());
+        }
+        found.extend(state.feed(&compiled, &[], 16, true));
+        // Reported once, when it outgrew the carry
+        assert_eq!(found.iter().map(|m| (m.start, m.end)).collect::<Vec<_>>(), [(0, 24)]);
+    }
+
+    #[test]
+    fn test_split_utf8_sequence_and_invalid_bytes() {
+        let text = "é 123-45-6789 \u{1F600} 987-65-4321\n".as_bytes();
+        let split_emoji = text.iter().position(|&b| b == 0xF0).unwrap() + 2;
+        assert_eq!(tail(text, &[1, split_emoji], 8), [(3, 14), (20, 31)]);
+
+        let mut bad = b"\xff\xfe 123-45-6789 ".to_vec();
+        bad.extend_from_slice(b"987-65-4321\n");
+        assert_eq!(tail(&bad, &[5, 15], 8), [(3, 14), (15, 26)]);
+    }
+
+    #[test]
+    fn test_scan_file_follows_appends_and_truncation() {
+        use std::io::Write;
+        let path = std::env::temp_dir().join(format!("openlabels-tail-{}.log", std::process::id()));
+        let key = path.to_str().unwrap().to_string();
+        let patterns = vec![(r"\b\d{3}-\d{2}-\d{4}\b".to_string(), "SSN".to_string(), 0.9, 0)];
+        let mut scanner =
+            TailScanner { compiled: Arc::new(compile_patterns(&patterns, false)), files: HashMap::new(), overlap: 16 };
+
+        std::fs::write(&path, "a 123-45-6789\nb 234-").unwrap();
+        assert_eq!(scanner.scan_path(&key).unwrap().len(), 1);
+        std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"56-7890\n").unwrap();
+        let found = scanner.scan_path(&key).unwrap();
+        assert_eq!(found.iter().map(|m| (m.start, m.text.as_str())).collect::<Vec<_>>(), [(16, "234-56-7890")]);
+        assert!(scanner.scan_path(&key).unwrap().is_empty());
+
+        std::fs::write(&path, "c 345-67-8901\n").unwrap();
+        assert_eq!(scanner.scan_path(&key).unwrap().len(), 1);
+        assert_eq!(scanner.files[&key].offset, 14);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
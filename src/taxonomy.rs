@@ -0,0 +1,289 @@
+//! Identifier taxonomy and k-anonymity style document risk
+//!
+//! Entities fall into three classes for re-identification risk:
+//!
+//! - direct identifiers single out a person on their own (SSN, email)
+//! - quasi-identifiers do so in combination (ZIP + date of birth + gender
+//!   identify most of the US population)
+//! - sensitive attributes are what is disclosed once a person is
+//!   identified (diagnosis, religion)
+//!
+//! Anything else is "other" and does not affect the risk. The built-in
+//! table can be overridden per entity type.
+
+// pyo3 wrappers of PyResult methods (see functions.rs)
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::detmath;
+
+/// Re-identification class of an entity type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdentifierClass {
+    Direct,
+    Quasi,
+    Sensitive,
+    Other,
+}
+
+impl IdentifierClass {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "direct" => Some(Self::Direct),
+            "quasi" => Some(Self::Quasi),
+            "sensitive" => Some(Self::Sensitive),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Direct => "direct",
+            Self::Quasi => "quasi",
+            Self::Sensitive => "sensitive",
+            Self::Other => "other",
+        }
+    }
+}
+
+const DIRECT: &[&str] = &[
+    "SSN", "PASSPORT", "DRIVERS_LICENSE", "STATE_ID", "TAX_ID", "AADHAAR", "AADHAAR_IN", "A_NUMBER",
+    "GREEN_CARD_NUMBER", "SIN_CA", "NINO_UK", "INSEE_FR", "PERSONALAUSWEIS_DE", "CODICE_FISCALE_IT", "DNI_ES",
+    "CPF_BR", "CURP_MX", "PAN_IN", "TFN_AU", "MY_NRIC", "CHINA_RESIDENT_ID", "JAPAN_MY_NUMBER", "TEUDAT_ZEHUT_IL",
+    "AMKA_GR", "MEDICARE_ID",
+    "MRN", "MBI", "HICN", "NHS_NUMBER", "HEALTH_PLAN_ID", "NAME_PATIENT", "EMAIL", "PHONE", "PHONE_MOBILE",
+    "CREDIT_CARD", "BANK_ACCOUNT", "IBAN", "ACCOUNT_NUMBER", "FINGERPRINT_TEMPLATE", "FACE_TEMPLATE",
+    "IRIS_TEMPLATE", "VOICE_PRINT", "RETINAL_SCAN", "PALM_PRINT",
+];
+
+const QUASI: &[&str] = &[
+    "NAME", "NAME_RELATIVE", "DATE_DOB", "DOB", "AGE", "GENDER", "ZIP", "ADDRESS", "CITY", "STATE", "LOCATION",
+    "EMPLOYER", "PROFESSION", "EMPLOYEE_ID", "IP_ADDRESS", "DEVICE_ID", "USERNAME", "VIN", "LICENSE_PLATE",
+    "HASHED_PII",
+];
+
+const SENSITIVE: &[&str] = &[
+    "DIAGNOSIS", "MEDICATION", "LAB_TEST", "RACE_ETHNICITY", "ETHNICITY", "RELIGION", "SEXUAL_ORIENTATION",
+    "DNA_SEQUENCE", "GENETIC_MARKER", "CRIMINAL_RECORD", "SALARY",
+];
+
+/// Built-in class of an entity type
+pub fn default_class(entity_type: &str) -> IdentifierClass {
+    if DIRECT.contains(&entity_type) {
+        IdentifierClass::Direct
+    } else if QUASI.contains(&entity_type) {
+        IdentifierClass::Quasi
+    } else if SENSITIVE.contains(&entity_type) {
+        IdentifierClass::Sensitive
+    } else {
+        IdentifierClass::Other
+    }
+}
+
+/// Identifiability lost per distinct quasi-identifier: 1 - DECAY^n
+pub const QUASI_DECAY: f32 = 0.5;
+/// Risk weight of an identifiable document without sensitive attributes
+pub const NO_SENSITIVE_WEIGHT: f32 = 0.7;
+/// Score of a document with sensitive attributes but nothing identifying
+pub const SENSITIVE_ONLY_SCORE: f32 = 10.0;
+
+/// Tier for a 0-100 score (same thresholds as the Python scorer)
+pub fn tier(score: f32) -> &'static str {
+    match score {
+        s if s >= 80.0 => "CRITICAL",
+        s if s >= 55.0 => "HIGH",
+        s if s >= 31.0 => "MEDIUM",
+        s if s >= 11.0 => "LOW",
+        _ => "MINIMAL",
+    }
+}
+
+/// Re-identification risk of one document
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct DocumentRisk {
+    /// 0-100
+    #[pyo3(get)]
+    pub score: f32,
+    /// MINIMAL, LOW, MEDIUM, HIGH or CRITICAL
+    #[pyo3(get)]
+    pub tier: &'static str,
+    /// Probability-like 0-1 measure of how identifiable subjects are
+    #[pyo3(get)]
+    pub identifiability: f32,
+    /// Distinct entity types per class, sorted
+    #[pyo3(get)]
+    pub direct: Vec<String>,
+    #[pyo3(get)]
+    pub quasi: Vec<String>,
+    #[pyo3(get)]
+    pub sensitive: Vec<String>,
+}
+
+#[pymethods]
+impl DocumentRisk {
+    fn __repr__(&self) -> String {
+        format!(
+            "DocumentRisk(score={:.1}, tier={}, direct={}, quasi={}, sensitive={})",
+            self.score,
+            self.tier,
+            self.direct.len(),
+            self.quasi.len(),
+            self.sensitive.len()
+        )
+    }
+}
+
+/// Entity classification with per-type overrides
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct Taxonomy {
+    overrides: HashMap<String, IdentifierClass>,
+}
+
+impl Taxonomy {
+    /// Overrides as {entity_type: class}, sorted by entity type
+    pub fn to_config(&self) -> BTreeMap<String, String> {
+        self.overrides.iter().map(|(entity_type, class)| (entity_type.clone(), class.as_str().to_string())).collect()
+    }
+
+    pub fn classify_type(&self, entity_type: &str) -> IdentifierClass {
+        let key = entity_type.to_ascii_uppercase();
+        self.overrides.get(&key).copied().unwrap_or_else(|| default_class(&key))
+    }
+
+    /// Risk of a document containing the given entity types
+    pub fn risk<'a>(&self, entity_types: impl IntoIterator<Item = &'a str>) -> DocumentRisk {
+        let mut classes: [BTreeSet<String>; 3] = Default::default();
+        for entity_type in entity_types {
+            let slot = match self.classify_type(entity_type) {
+                IdentifierClass::Direct => 0,
+                IdentifierClass::Quasi => 1,
+                IdentifierClass::Sensitive => 2,
+                IdentifierClass::Other => continue,
+            };
+            classes[slot].insert(entity_type.to_ascii_uppercase());
+        }
+        let [direct, quasi, sensitive] = classes;
+
+        let identifiability = if !direct.is_empty() {
+            1.0
+        } else {
+            1.0 - detmath::powi(QUASI_DECAY, quasi.len() as u32)
+        };
+        let score = if identifiability > 0.0 {
+            let weight = if sensitive.is_empty() { NO_SENSITIVE_WEIGHT } else { 1.0 };
+            100.0 * identifiability * weight
+        } else if !sensitive.is_empty() {
+            SENSITIVE_ONLY_SCORE
+        } else {
+            0.0
+        };
+        DocumentRisk {
+            score,
+            tier: tier(score),
+            identifiability,
+            direct: direct.into_iter().collect(),
+            quasi: quasi.into_iter().collect(),
+            sensitive: sensitive.into_iter().collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl Taxonomy {
+    /// Create a taxonomy, optionally overriding built-in classes
+    ///
+    /// Args:
+    ///     overrides: Entity type -> "direct", "quasi", "sensitive" or "other"
+    ///
+    /// Raises:
+    ///     ValueError: If an override names an unknown class
+    #[new]
+    #[pyo3(signature = (overrides=None))]
+    fn new(overrides: Option<HashMap<String, String>>) -> PyResult<Self> {
+        let mut taxonomy = Self::default();
+        // Sorted: keys differing only in case must resolve the same way every run
+        for (entity_type, class) in overrides.unwrap_or_default().into_iter().collect::<BTreeMap<_, _>>() {
+            let parsed = IdentifierClass::parse(&class).ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "unknown identifier class '{}' for {}: expected direct, quasi, sensitive or other",
+                    class, entity_type
+                ))
+            })?;
+            taxonomy.overrides.insert(entity_type.to_ascii_uppercase(), parsed);
+        }
+        Ok(taxonomy)
+    }
+
+    /// Class of an entity type: "direct", "quasi", "sensitive" or "other"
+    fn classify(&self, entity_type: &str) -> &'static str {
+        self.classify_type(entity_type).as_str()
+    }
+
+    /// Re-identification risk of a document
+    ///
+    /// Args:
+    ///     findings: Entity type strings or objects with an entity_type
+    ///         attribute (RawMatch, Finding, FieldMatch, ...)
+    ///
+    /// Any direct identifier makes subjects identifiable; otherwise each
+    /// distinct quasi-identifier type halves the remaining anonymity.
+    /// Sensitive attributes raise the score of identifiable documents.
+    fn document_risk(&self, findings: Vec<Bound<'_, PyAny>>) -> PyResult<DocumentRisk> {
+        let types = findings
+            .iter()
+            .map(|f| match f.extract::<String>() {
+                Ok(s) => Ok(s),
+                Err(_) => f.getattr("entity_type")?.extract::<String>(),
+            })
+            .collect::<PyResult<Vec<String>>>()?;
+        Ok(self.risk(types.iter().map(String::as_str)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_classes() {
+        assert_eq!(default_class("SSN"), IdentifierClass::Direct);
+        assert_eq!(default_class("ZIP"), IdentifierClass::Quasi);
+        assert_eq!(default_class("DIAGNOSIS"), IdentifierClass::Sensitive);
+        assert_eq!(default_class("AWS_ACCESS_KEY"), IdentifierClass::Other);
+        assert!(DIRECT.iter().all(|t| !QUASI.contains(t) && !SENSITIVE.contains(t)));
+        assert!(QUASI.iter().all(|t| !SENSITIVE.contains(t)));
+    }
+
+    #[test]
+    fn test_risk_scoring() {
+        let taxonomy = Taxonomy::default();
+        assert_eq!(taxonomy.risk([]).tier, "MINIMAL");
+
+        let direct = taxonomy.risk(["SSN", "SSN"]);
+        assert_eq!((direct.score, direct.tier), (70.0, "HIGH"));
+        assert_eq!(direct.direct, ["SSN"]);
+
+        // The classic ZIP + DOB + gender combination, with and without a diagnosis
+        let quasi = taxonomy.risk(["ZIP", "DATE_DOB", "GENDER"]);
+        assert!((quasi.identifiability - 0.875).abs() < 1e-6);
+        assert_eq!(quasi.tier, "HIGH");
+        assert_eq!(taxonomy.risk(["ZIP", "DATE_DOB", "GENDER", "DIAGNOSIS"]).tier, "CRITICAL");
+
+        assert_eq!(taxonomy.risk(["DIAGNOSIS", "AWS_ACCESS_KEY"]).score, SENSITIVE_ONLY_SCORE);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut taxonomy = Taxonomy::default();
+        taxonomy.overrides.insert("NAME".to_string(), IdentifierClass::Direct);
+        assert_eq!(taxonomy.classify_type("name"), IdentifierClass::Direct);
+        assert_eq!(taxonomy.risk(["NAME"]).tier, "HIGH");
+        assert_eq!(Taxonomy::default().risk(["NAME"]).tier, "MEDIUM");
+    }
+}
@@ -0,0 +1,183 @@
+//! Two-pass scanning: cheap candidate detection, then anchored verification
+//!
+//! Pass one runs a single Aho-Corasick automaton built from the literal
+//! prefixes of every pattern. Each hit marks a position where a pattern
+//! could start. Pass two runs only the triggered patterns, anchored at
+//! those candidate positions, instead of sweeping every regex over the
+//! whole document. Clean documents therefore cost one automaton pass.
+//!
+//! Patterns without a usable literal prefix (e.g. unbounded leading
+//! classes) are marked as full-scan and handled by the normal path.
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+use std::collections::HashMap;
+
+/// Maximum number of distinct prefix literals a single pattern may
+/// contribute before it is treated as full-scan
+const MAX_TRIGGER_LITERALS: usize = 2048;
+
+/// Candidate density above which anchored searches stop paying off and
+/// the pattern is swept with a normal find_iter instead (one candidate
+/// per N bytes of input)
+pub const DENSE_CANDIDATE_RATIO: usize = 4;
+
+/// Literal triggers for the first pass
+pub struct TwoPassPlan {
+    /// Automaton over the union of all trigger literals
+    trigger: AhoCorasick,
+    /// For each trigger literal, the patterns it can start
+    owners: Vec<Vec<usize>>,
+    /// Whether each pattern (by compiled index) has literal triggers
+    triggered: Vec<bool>,
+}
+
+impl TwoPassPlan {
+    /// Build trigger literals for a list of successfully compiled patterns
+    ///
+    /// Returns None if no pattern has usable prefixes or the automaton
+    /// cannot be built, in which case scanning falls back to one pass.
+    pub fn build(patterns: &[String]) -> Option<Self> {
+        let mut literals: Vec<Vec<u8>> = Vec::new();
+        let mut owners: Vec<Vec<usize>> = Vec::new();
+        let mut literal_index: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut triggered = vec![false; patterns.len()];
+
+        for (idx, pattern) in patterns.iter().enumerate() {
+            let Some(prefixes) = prefix_literals(pattern) else {
+                continue;
+            };
+            triggered[idx] = true;
+            for lit in prefixes {
+                let slot = *literal_index.entry(lit.clone()).or_insert_with(|| {
+                    literals.push(lit);
+                    owners.push(Vec::new());
+                    owners.len() - 1
+                });
+                if owners[slot].last() != Some(&idx) {
+                    owners[slot].push(idx);
+                }
+            }
+        }
+
+        if literals.is_empty() {
+            return None;
+        }
+
+        let trigger = AhoCorasick::builder()
+            .match_kind(MatchKind::Standard)
+            .build(&literals)
+            .ok()?;
+
+        Some(Self {
+            trigger,
+            owners,
+            triggered,
+        })
+    }
+
+    /// Whether a pattern is verified from candidates rather than swept
+    pub fn is_triggered(&self, pattern_idx: usize) -> bool {
+        self.triggered.get(pattern_idx).copied().unwrap_or(false)
+    }
+
+    /// Whether any pattern needs a full sweep of the text
+    pub fn has_full_scan_patterns(&self) -> bool {
+        self.triggered.iter().any(|t| !t)
+    }
+
+    /// First pass: candidate start positions per pattern, sorted and deduped
+    ///
+    /// Fills `per_pattern` with one list per pattern, reusing its buffers.
+    pub fn candidates(&self, text: &str, per_pattern: &mut Vec<Vec<usize>>) {
+        per_pattern.resize_with(self.triggered.len(), Vec::new);
+        per_pattern.truncate(self.triggered.len());
+        for positions in per_pattern.iter_mut() {
+            positions.clear();
+        }
+        for m in self.trigger.find_overlapping_iter(text) {
+            for &pattern_idx in &self.owners[m.pattern().as_usize()] {
+                per_pattern[pattern_idx].push(m.start());
+            }
+        }
+        for positions in per_pattern.iter_mut() {
+            positions.sort_unstable();
+            positions.dedup();
+        }
+    }
+
+    /// Approximate heap size of the trigger automaton and owner tables
+    pub fn memory_usage(&self) -> usize {
+        self.trigger.memory_usage()
+            + self
+                .owners
+                .iter()
+                .map(|o| o.capacity() * std::mem::size_of::<usize>())
+                .sum::<usize>()
+            + self.triggered.capacity()
+    }
+}
+
+/// Extract the literal prefixes every match of `pattern` must start with
+///
+/// Returns None when the set is infinite, contains the empty string, or is
+/// too large to be a useful trigger.
+pub(crate) fn prefix_literals(pattern: &str) -> Option<Vec<Vec<u8>>> {
+    let hir = regex_syntax::parse(pattern).ok()?;
+    let mut extractor = Extractor::new();
+    extractor
+        .kind(ExtractKind::Prefix)
+        .limit_class(1024)
+        .limit_total(MAX_TRIGGER_LITERALS);
+    let seq = extractor.extract(&hir);
+    let lits = seq.literals()?;
+    if lits.is_empty() || lits.len() > MAX_TRIGGER_LITERALS {
+        return None;
+    }
+    if lits.iter().any(|l| l.as_bytes().is_empty()) {
+        return None;
+    }
+
+    // Any literal extending a shorter one is redundant as a trigger
+    let mut sorted: Vec<Vec<u8>> = lits.iter().map(|l| l.as_bytes().to_vec()).collect();
+    sorted.sort_unstable();
+    let mut minimal: Vec<Vec<u8>> = Vec::with_capacity(sorted.len());
+    for lit in sorted {
+        if minimal.last().is_some_and(|prev| lit.starts_with(prev)) {
+            continue;
+        }
+        minimal.push(lit);
+    }
+    Some(minimal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_literals() {
+        let lits = prefix_literals(r"SSN:\s*\d{3}").unwrap();
+        assert_eq!(lits, vec![b"SSN:".to_vec()]);
+
+        // Unicode digit class is large but finite
+        assert!(prefix_literals(r"\d{3}-\d{2}-\d{4}").is_some());
+
+        // Leading class too large to enumerate
+        assert!(prefix_literals(r".*bar").is_none());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let patterns = vec![r"foo\d+".to_string(), r".*bar".to_string()];
+        let plan = TwoPassPlan::build(&patterns).unwrap();
+        assert!(plan.is_triggered(0));
+        assert!(!plan.is_triggered(1));
+        assert!(plan.has_full_scan_patterns());
+
+        // Stale buffers from an earlier scan are resized and cleared
+        let mut cands = vec![vec![3]; 4];
+        plan.candidates("foo1 xx foo2", &mut cands);
+        assert_eq!(cands, [vec![0, 8], vec![]]);
+    }
+}
@@ -6,6 +6,7 @@
 use memchr::memchr;
 
 /// Validate credit card number using Luhn algorithm
+#[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
 pub fn luhn(number: &str) -> bool {
     let digits: Vec<u32> = number
         .chars()
@@ -133,35 +134,103 @@ pub fn is_private_ip(ip: &str) -> bool {
     a >= 224                            // Multicast/Reserved
 }
 
-/// Validate SSN format (basic format check, not context)
-pub fn ssn_format(ssn: &str) -> bool {
+/// What a nine-digit US taxpayer number is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SsnClass {
+    /// Social Security number
+    Ssn,
+    /// IRS Individual Taxpayer Identification Number (9xx area)
+    Itin,
+    /// Neither
+    Invalid,
+}
+
+impl SsnClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SsnClass::Ssn => "ssn",
+            SsnClass::Itin => "itin",
+            SsnClass::Invalid => "invalid",
+        }
+    }
+}
+
+/// How SSN validation treats 9xx areas, which the SSA never issues
+///
+/// Only the Python validate_ssn_format/classify_ssn functions take a
+/// policy. Findings always validate SSN matches with `Reject` and ITIN
+/// matches as ITINs, so detection keeps a 9xx number only when an ITIN
+/// pattern reports it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Area9xxPolicy {
+    /// Every 9xx area is invalid
+    Reject,
+    /// 9xx areas with an ITIN group (50-65, 70-88, 90-92, 94-99) are ITINs
+    Itin,
+}
+
+impl Area9xxPolicy {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "reject" => Some(Area9xxPolicy::Reject),
+            "itin" => Some(Area9xxPolicy::Itin),
+            _ => None,
+        }
+    }
+}
+
+/// Classify a nine-digit taxpayer number (basic format check, not context)
+pub fn classify_ssn(ssn: &str, policy: Area9xxPolicy) -> SsnClass {
     let digits: String = ssn.chars().filter(|c| c.is_ascii_digit()).collect();
 
     if digits.len() != 9 {
-        return false;
+        return SsnClass::Invalid;
     }
 
     // Parse area, group, serial
     let area: u32 = match digits[0..3].parse() {
         Ok(n) => n,
-        Err(_) => return false,
+        Err(_) => return SsnClass::Invalid,
     };
     let group: u32 = match digits[3..5].parse() {
         Ok(n) => n,
-        Err(_) => return false,
+        Err(_) => return SsnClass::Invalid,
     };
     let serial: u32 = match digits[5..9].parse() {
         Ok(n) => n,
-        Err(_) => return false,
+        Err(_) => return SsnClass::Invalid,
     };
 
-    // Invalid areas: 000, 666, 900-999
-    if area == 0 || area == 666 || area >= 900 {
-        return false;
+    // Serial can't be 0 for either kind
+    if serial == 0 {
+        return SsnClass::Invalid;
+    }
+
+    if area >= 900 {
+        let itin_group = matches!(group, 50..=65 | 70..=88 | 90..=92 | 94..=99);
+        return match policy {
+            Area9xxPolicy::Itin if itin_group => SsnClass::Itin,
+            _ => SsnClass::Invalid,
+        };
     }
 
-    // Group and serial can't be 0
-    group > 0 && serial > 0
+    // Invalid areas: 000, 666; group can't be 0
+    if area == 0 || area == 666 || group == 0 {
+        return SsnClass::Invalid;
+    }
+    SsnClass::Ssn
+}
+
+/// Validate SSN format (basic format check, not context)
+///
+/// ITINs are rejected; use `classify_ssn` with `Area9xxPolicy::Itin` to keep them.
+pub fn ssn_format(ssn: &str) -> bool {
+    classify_ssn(ssn, Area9xxPolicy::Reject) == SsnClass::Ssn
+}
+
+/// Validate ITIN format: 9xx area with an ITIN group
+pub fn itin_format(itin: &str) -> bool {
+    classify_ssn(itin, Area9xxPolicy::Itin) == SsnClass::Itin
 }
 
 #[cfg(test)]
@@ -199,6 +268,21 @@ mod tests {
         assert!(!ssn_format("12345678")); // Too short
     }
 
+    #[test]
+    fn test_ssn_area_9xx_policy() {
+        let itin = "912-70-1234";
+        assert_eq!(classify_ssn(itin, Area9xxPolicy::Reject), SsnClass::Invalid);
+        assert_eq!(classify_ssn(itin, Area9xxPolicy::Itin), SsnClass::Itin);
+        assert_eq!(classify_ssn("123-45-6789", Area9xxPolicy::Itin), SsnClass::Ssn);
+        assert!(!ssn_format(itin));
+        assert!(itin_format(itin));
+
+        assert!(!itin_format("912-45-1234")); // Group outside ITIN ranges
+        assert!(!itin_format("912-93-1234")); // 93 is not an ITIN group
+        assert!(!itin_format("912-70-0000")); // Invalid serial
+        assert!(!itin_format("123-70-1234")); // Not a 9xx area
+    }
+
     #[test]
     fn test_phone_valid() {
         assert!(phone_format("212-555-1234"));
@@ -0,0 +1,48 @@
+"""
+Tests for the native identifier taxonomy in openlabels.core.scorer.
+
+When passed to score(), the taxonomy's classes feed the co-occurrence
+categories, so overrides change document scores. Without a taxonomy,
+scores are unchanged.
+"""
+
+import pytest
+
+from openlabels.core import scorer
+
+requires_native = pytest.mark.skipif(scorer.Taxonomy is None, reason="Rust extension not built")
+
+
+@requires_native
+class TestTaxonomyCategories:
+    """Tests for taxonomy-derived co-occurrence categories."""
+
+    def test_scores_unchanged_without_taxonomy(self):
+        """The taxonomy is opt-in; default scoring uses registry categories only."""
+        entities = {'ZIP': 1, 'DATE_DOB': 1, 'GENDER': 1}
+        result = scorer.score(entities)
+        assert result.identifiability == 0.0
+        assert result.categories == scorer.get_categories(entities)
+        assert 'direct_identifier' not in result.categories
+
+    def test_contact_identifier_counts_as_direct(self):
+        """A direct identifier from the taxonomy should trigger PHI rules."""
+        result = scorer.score({'EMAIL': 1, 'DIAGNOSIS': 1}, taxonomy=scorer.Taxonomy())
+        assert 'direct_identifier' in result.categories
+        assert result.co_occurrence_rules == ['hipaa_phi']
+
+    def test_quasi_combination_counts_as_direct(self):
+        """ZIP + DOB + gender should be treated as identifying."""
+        result = scorer.score({'ZIP': 1, 'DATE_DOB': 1, 'GENDER': 1}, taxonomy=scorer.Taxonomy())
+        assert result.identifiability == pytest.approx(0.875)
+        assert 'direct_identifier' in result.categories
+
+    def test_overrides_change_score(self):
+        """A caller-configured taxonomy should change the document score."""
+        entities = {'NAME': 1, 'DIAGNOSIS': 1}
+        default = scorer.score(entities, taxonomy=scorer.Taxonomy())
+        custom = scorer.score(entities, taxonomy=scorer.Taxonomy({'NAME': 'direct'}))
+        assert default.co_occurrence_rules == ['phi_without_id']
+        assert custom.co_occurrence_rules == ['hipaa_phi']
+        assert custom.score > default.score
+        assert custom.identifiability == 1.0
@@ -0,0 +1,45 @@
+"""
+Tests that the Rust extension's type stubs match the Rust sources.
+
+openlabels/_rust.pyi is generated by scripts/generate_stubs.py; these
+tests fail when a Rust API change was made without regenerating it.
+"""
+
+import ast
+import importlib.util
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+
+
+def _load_generator():
+    spec = importlib.util.spec_from_file_location("generate_stubs", ROOT / "scripts" / "generate_stubs.py")
+    module = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(module)
+    return module
+
+
+class TestRustStubs:
+    """Tests for the generated openlabels/_rust.pyi."""
+
+    def test_stubs_are_up_to_date(self):
+        """Committed stubs should equal freshly generated ones."""
+        generator = _load_generator()
+        expected = generator.generate()
+        current = (ROOT / "openlabels" / "_rust.pyi").read_text(encoding="utf-8")
+        assert current == expected, "run scripts/generate_stubs.py to regenerate openlabels/_rust.pyi"
+
+    def test_stubs_parse(self):
+        """Generated stubs should be valid Python."""
+        ast.parse(_load_generator().generate())
+
+    def test_registered_api_is_covered(self):
+        """Every class and function registered in lib.rs should have a stub."""
+        generator = _load_generator()
+        tree = ast.parse(generator.generate())
+        stubbed = {node.name for node in tree.body if isinstance(node, (ast.ClassDef, ast.FunctionDef))}
+        classes, functions = generator.registered((ROOT / "src" / "lib.rs").read_text(encoding="utf-8"))
+        assert "PatternMatcher" in stubbed
+        assert set(classes) <= stubbed
+        # Functions may be exported under a #[pyo3(name = ...)] alias
+        assert len([n for n in tree.body if isinstance(n, ast.FunctionDef)]) == len(functions)